@@ -2,23 +2,147 @@
 //!
 //! This module defines the core data structures and logic for the Minesweeper game board,
 //! including board size presets, cell types, cell states, and all board operations such as
-//! flagging, uncovering, mine placement, neighbor calculation, and flood fill reveal.
+//! flagging, uncovering, mine placement, neighbor calculation, flood fill reveal, and logical
+//! deduction of safe cells from visible information.
 //!
 //! It is the foundation for the game's state and rules, but does not handle UI or rendering.
 
 use rand::prelude::*;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::io::{self, Read, Write};
+
+/// Mine density (percent of cells) at which each harder difficulty label starts;
+/// anything below the first threshold is "Easy".
+const DIFFICULTY_THRESHOLDS: [(usize, &str); 3] = [(13, "Medium"), (17, "Hard"), (21, "Insane")];
+
+/// A single player action on the board, applied with `Board::apply_move`.
+/// Each variant holds the (row, col) of the cell acted on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Reveal(usize, usize), // Uncover a covered cell (empty cells flood-fill)
+    Flag(usize, usize),   // Flag a covered cell
+    Unflag(usize, usize), // Remove a flag
+    Chord(usize, usize),  // Uncover the remaining neighbors of a satisfied number
+    Mark(usize, usize),   // Mark a covered or flagged cell as a probable mine (`CellState::Maybe`)
+    Unmark(usize, usize), // Remove a maybe mark
+    Absorb(usize, usize), // Flag an uncovered mine whose hit was forgiven (zen mode or a life)
+}
+
+impl Move {
+    /// Returns the (row, col) of the cell the move acts on.
+    pub fn cell(self) -> (usize, usize) {
+        match self {
+            Move::Reveal(row, col)
+            | Move::Flag(row, col)
+            | Move::Unflag(row, col)
+            | Move::Chord(row, col)
+            | Move::Mark(row, col)
+            | Move::Unmark(row, col)
+            | Move::Absorb(row, col) => (row, col),
+        }
+    }
+}
+
+/// Cells proven safe and cells proven to be mines, from `Board::deduce_cells`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Deductions {
+    pub safe: HashSet<(usize, usize)>,
+    pub mines: HashSet<(usize, usize)>,
+}
+
+/// Counts over a cell's neighbors, from `Board::tally_neighbors`. Every neighbor counts
+/// toward `mines` if it holds a mine, and toward exactly one of `flagged`, `maybe`,
+/// `covered`, and `uncovered` by its state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NeighborTally {
+    pub mines: usize,     // Neighbors holding a mine (whatever their state)
+    pub flagged: usize,   // Flagged neighbors
+    pub maybe: usize,     // Neighbors marked as maybe
+    pub covered: usize,   // Covered neighbors (not flagged or marked)
+    pub uncovered: usize, // Uncovered neighbors
+}
+
+/// The outcome of `Board::apply_move`.
+/// - `revealed`: newly uncovered cells as (row, col, distance), where the distance is the
+///   flood-fill wave distance for reveals and the ring around the number for chords
+///   (useful for animations); empty for flag moves and moves that changed nothing.
+/// - `mine_hit`: the mine uncovered by this move, if any (the game is lost).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MoveResult {
+    pub revealed: Vec<(usize, usize, usize)>,
+    pub mine_hit: Option<(usize, usize)>,
+}
+
+/// What a reveal found, from `Board::reveal`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevealOutcome {
+    Mine,       // A mine was uncovered (the game is lost)
+    Number(u8), // A number was uncovered
+    Empty {
+        flooded: Vec<(usize, usize, usize)>, // Cells uncovered, as from `flood_fill_wave`
+    },
+    AlreadyRevealed, // The cell was uncovered already (or is off the board); nothing changed
+    Flagged,         // The cell is flagged or marked as maybe, so it wasn't uncovered
+}
+
+/// The outcome of `Board::toggle_flag`, which follows the right-click cycle
+/// covered -> flagged -> maybe -> covered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagChange {
+    Flagged,   // A covered cell was flagged
+    Unflagged, // A flag was removed (the cell is now marked as maybe)
+    Unmarked,  // A maybe mark was removed (the cell is covered again)
+    NoChange,  // The cell can't be flagged (uncovered or out of range)
+}
+
+impl FlagChange {
+    /// Returns the move that makes this change on the given cell, e.g. for a replay.
+    pub fn as_move(self, row: usize, col: usize) -> Option<Move> {
+        match self {
+            FlagChange::Flagged => Some(Move::Flag(row, col)),
+            FlagChange::Unflagged => Some(Move::Mark(row, col)),
+            FlagChange::Unmarked => Some(Move::Unmark(row, col)),
+            FlagChange::NoChange => None,
+        }
+    }
+}
+
+/// How a board's cells connect to their neighbors. The topology decides which cells a number
+/// counts, which cells a flood fill spreads to, and which cells a chord uncovers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    Square,     // The classic 8 neighbors, diagonals included (default)
+    VonNeumann, // Only the 4 orthogonal neighbors (up, down, left, right)
+}
+
+impl Topology {
+    /// Returns whether a cell at the given row and column distance from another (each 0 or 1,
+    /// not both 0) is its neighbor.
+    pub fn connects(self, row_distance: usize, col_distance: usize) -> bool {
+        match self {
+            Topology::Square => true,
+            Topology::VonNeumann => row_distance == 0 || col_distance == 0,
+        }
+    }
+}
 
 /// Represents the standard Minesweeper board sizes.
 /// - Small: 8x8 with 10 mines (classic beginner)
 /// - Medium: 16x16 with 40 mines (classic intermediate)
 /// - Large: 24x24 with 99 mines (classic expert)
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// - Custom: any size and mine count entered by the player
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BoardSize {
     Small,
     Medium,
     Large,
+    Custom {
+        width: usize,
+        height: usize,
+        mines: usize,
+    },
 }
 
 impl BoardSize {
@@ -28,6 +152,11 @@ impl BoardSize {
             BoardSize::Small => (8, 8, 10),    // Beginner
             BoardSize::Medium => (16, 16, 40), // Intermediate
             BoardSize::Large => (24, 24, 99),  // Expert
+            BoardSize::Custom {
+                width,
+                height,
+                mines,
+            } => (width, height, mines),
         }
     }
 
@@ -37,6 +166,7 @@ impl BoardSize {
             BoardSize::Small => "Small",
             BoardSize::Medium => "Medium",
             BoardSize::Large => "Large",
+            BoardSize::Custom { .. } => "Custom",
         }
     }
 
@@ -51,13 +181,28 @@ impl BoardSize {
         }
     }
 
-    /// Returns the recommended cell size (in pixels) for each board size.
-    /// Used for scaling the UI and board so it fits nicely on screen.
+    /// Returns the recommended cell size (in pixels) for each board size: the size its window
+    /// opens at. Once the window is up, the cells are scaled to fit it (see
+    /// `MinesweeperApp::compute_cell_size`). Custom boards get cells that keep their longer
+    /// side about as wide as a Large board.
     pub fn cell_size(self) -> f32 {
         match self {
             BoardSize::Small => 48.0,
             BoardSize::Medium => 36.0,
             BoardSize::Large => 28.0,
+            BoardSize::Custom { width, height, .. } => (672.0 / width.max(height).max(1) as f32)
+                .floor()
+                .clamp(16.0, 48.0),
+        }
+    }
+
+    /// Returns the next size in Small -> Medium -> Large order, wrapping back to Small.
+    /// A custom size moves on to Small.
+    pub fn next(self) -> BoardSize {
+        match self {
+            BoardSize::Small => BoardSize::Medium,
+            BoardSize::Medium => BoardSize::Large,
+            BoardSize::Large | BoardSize::Custom { .. } => BoardSize::Small,
         }
     }
 }
@@ -79,11 +224,27 @@ pub enum Cell {
 /// - `Covered`: The cell has not been revealed yet.
 /// - `Uncovered`: The cell has been revealed.
 /// - `Flagged`: The cell has been flagged by the player as potentially containing a mine.
+/// - `Maybe`: The player thinks the cell is a mine but hasn't committed a flag. It counts as a
+///   flag for chording but not for the flags-left counter, and it can't be uncovered.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum CellState {
     Covered,
     Uncovered,
     Flagged,
+    Maybe,
+}
+
+/// A copy of a board's contents taken by `Board::snapshot`, for undo checkpoints:
+/// push one before a risky operation and hand it to `Board::restore` to go back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardSnapshot {
+    width: usize,
+    height: usize,
+    mines: usize,
+    cells: Vec<Vec<Cell>>,
+    states: Vec<Vec<CellState>>,
+    mine_positions: HashSet<(usize, usize)>,
+    topology: Topology,
 }
 
 /// Represents the Minesweeper game board and all its state.
@@ -95,7 +256,9 @@ pub enum CellState {
 /// - `cells`: A 2D vector representing the contents of each cell (mine, number, or empty).
 /// - `states`: A 2D vector representing the state of each cell (covered, uncovered, or flagged).
 /// - `mine_positions`: A set containing the coordinates of all mines on the board.
-#[derive(Clone)]
+///
+/// Two boards are equal when all of these fields match, so tests can use `assert_eq!` on boards.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Board {
     width: usize,
     height: usize,
@@ -103,6 +266,7 @@ pub struct Board {
     cells: Vec<Vec<Cell>>,
     states: Vec<Vec<CellState>>,
     mine_positions: HashSet<(usize, usize)>,
+    topology: Topology,
 }
 
 impl Board {
@@ -120,7 +284,57 @@ impl Board {
             cells,
             states,
             mine_positions: HashSet::new(),
+            topology: Topology::Square,
+        }
+    }
+
+    /// Smallest side length `try_new` accepts (the first click clears a 3x3 area).
+    pub const MIN_SIDE: usize = 5;
+    /// Largest side length `try_new` accepts, to keep the window on screen.
+    pub const MAX_SIDE: usize = 50;
+
+    /// Creates a board like `new`, but checks the parameters first: each side must be between
+    /// `MIN_SIDE` and `MAX_SIDE` cells, and the mines must fit (see `set_mine_count`).
+    /// Used for sizes typed in by the player.
+    pub fn try_new(width: usize, height: usize, mines: usize) -> Result<Self, String> {
+        for (name, side) in [("width", width), ("height", height)] {
+            if !(Self::MIN_SIDE..=Self::MAX_SIDE).contains(&side) {
+                return Err(format!(
+                    "{} must be between {} and {}",
+                    name,
+                    Self::MIN_SIDE,
+                    Self::MAX_SIDE
+                ));
+            }
+        }
+        let mut board = Board::new(width, height, 0);
+        board.set_mine_count(mines)?;
+        Ok(board)
+    }
+
+    /// Creates a board from a known mine layout, one string per row.
+    /// `*` marks a mine and any other character is an empty cell.
+    /// The width is taken from the longest row, the mine count from the number of `*`s,
+    /// and numbers are calculated so the board is ready to play.
+    pub fn from_mine_layout(layout: &[&str]) -> Self {
+        let height = layout.len();
+        let width = layout
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut board = Board::new(width, height, 0);
+        for (row, line) in layout.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if ch == '*' {
+                    board.cells[row][col] = Cell::Mine;
+                    board.mine_positions.insert((row, col));
+                }
+            }
         }
+        board.mines = board.mine_positions.len();
+        board.calculate_numbers();
+        board
     }
 
     /// Returns the width of the board.
@@ -138,44 +352,218 @@ impl Board {
         self.mines
     }
 
+    /// Returns the number of cells on the board (width * height).
+    pub fn total_cells(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// Returns the number of cells without a mine: the cells to uncover to win.
+    pub fn safe_cell_count(&self) -> usize {
+        self.total_cells().saturating_sub(self.mines)
+    }
+
+    /// Changes the number of mines to place, e.g. for a custom-difficulty slider.
+    /// Only allowed before mines are placed, and the count must leave room for the
+    /// mine-free 3x3 area around the first click.
+    pub fn set_mine_count(&mut self, mines: usize) -> Result<(), String> {
+        if !self.mine_positions.is_empty() {
+            return Err("mines are already placed".to_string());
+        }
+        let capacity = self.total_cells().saturating_sub(9);
+        if mines > capacity {
+            return Err(format!(
+                "{} mines don't fit on a {}x{} board (at most {})",
+                mines, self.width, self.height, capacity
+            ));
+        }
+        self.mines = mines;
+        Ok(())
+    }
+
+    /// Classifies the board by mine density, for custom boards that don't match a preset:
+    /// "Easy" below 13% mines, "Medium" from 13%, "Hard" from 17%, and "Insane" from 21%.
+    pub fn difficulty_label(&self) -> &'static str {
+        let cells = self.total_cells();
+        DIFFICULTY_THRESHOLDS
+            .iter()
+            .rev()
+            .find(|&&(percent, _)| cells > 0 && self.mines * 100 >= percent * cells)
+            .map_or("Easy", |&(_, label)| label)
+    }
+
+    /// Returns how many cells are in the given state.
+    pub fn count_by_state(&self, state: CellState) -> usize {
+        self.states
+            .iter()
+            .flatten()
+            .filter(|&&s| s == state)
+            .count()
+    }
+
+    /// Returns the number of flagged cells.
+    pub fn flag_count(&self) -> usize {
+        self.count_by_state(CellState::Flagged)
+    }
+
+    /// Returns the mines left to flag: the mine count minus the flags placed.
+    /// Negative when the player has placed more flags than there are mines.
+    pub fn remaining_mines(&self) -> isize {
+        self.mines as isize - self.flag_count() as isize
+    }
+
     // === Cell and State Access ===
 
+    /// Returns whether (row, col) is a cell on the board. All bounds checks go through here.
+    pub fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
     /// Returns the cell at the given position, if valid.
-    ///
-    /// Note: Returns an owned value (`Option<Cell>`) using `.copied()`.
     pub fn cell(&self, row: usize, col: usize) -> Option<Cell> {
-        self.cells.get(row).and_then(|r| r.get(col)).copied()
+        self.in_bounds(row, col).then(|| self.cells[row][col])
     }
 
     /// Returns the state of the cell at the given position, if valid.
-    ///
-    /// Note: Returns an owned value (`Option<CellState>`) using `.copied()`.
     pub fn cell_state(&self, row: usize, col: usize) -> Option<CellState> {
-        self.states.get(row).and_then(|r| r.get(col)).copied()
+        self.in_bounds(row, col).then(|| self.states[row][col])
+    }
+
+    /// Returns all cells, one `Vec` per row (`height` rows of `width` cells), for bulk reading.
+    pub fn cells(&self) -> &[Vec<Cell>] {
+        &self.cells
+    }
+
+    /// Returns all cell states, one `Vec` per row (`height` rows of `width` states).
+    pub fn states(&self) -> &[Vec<CellState>] {
+        &self.states
     }
 
     // === Cell Manipulation ===
 
     /// Flags the cell at the given position, if valid.
     pub fn flag_cell(&mut self, row: usize, col: usize) {
-        if let Some(state) = self.states.get_mut(row).and_then(|r| r.get_mut(col)) {
-            *state = CellState::Flagged;
+        if self.in_bounds(row, col) {
+            self.states[row][col] = CellState::Flagged;
         }
     }
 
     /// Unflags the cell at the given position, if valid.
     pub fn unflag_cell(&mut self, row: usize, col: usize) {
-        if let Some(state) = self.states.get_mut(row).and_then(|r| r.get_mut(col)) {
-            if *state == CellState::Flagged {
-                *state = CellState::Covered;
+        if self.cell_state(row, col) == Some(CellState::Flagged) {
+            self.states[row][col] = CellState::Covered;
+        }
+    }
+
+    /// Advances a cell one step through the right-click cycle (covered -> flagged -> maybe ->
+    /// covered) and reports what changed, so callers can pick feedback from the result alone.
+    pub fn toggle_flag(&mut self, row: usize, col: usize) -> FlagChange {
+        let change = match self.cell_state(row, col) {
+            Some(CellState::Covered) => FlagChange::Flagged,
+            Some(CellState::Flagged) => FlagChange::Unflagged,
+            Some(CellState::Maybe) => FlagChange::Unmarked,
+            Some(CellState::Uncovered) | None => FlagChange::NoChange,
+        };
+        if let Some(mv) = change.as_move(row, col) {
+            self.apply_move(mv);
+        }
+        change
+    }
+
+    /// Uncovers a covered cell and reports what was there: a mine, a number, or an empty cell
+    /// along with everything its flood fill uncovered. Flagged, maybe-marked, and uncovered
+    /// cells are left alone. All reveals (including `apply_move`'s) go through here.
+    pub fn reveal(&mut self, row: usize, col: usize) -> RevealOutcome {
+        match self.cell_state(row, col) {
+            Some(CellState::Covered) => {}
+            Some(CellState::Flagged | CellState::Maybe) => return RevealOutcome::Flagged,
+            Some(CellState::Uncovered) | None => return RevealOutcome::AlreadyRevealed,
+        }
+        match self.cells[row][col] {
+            Cell::Empty => RevealOutcome::Empty {
+                flooded: self.flood_fill_wave(row, col),
+            },
+            Cell::Mine => {
+                self.uncover_cell(row, col);
+                RevealOutcome::Mine
+            }
+            Cell::Number(n) => {
+                self.uncover_cell(row, col);
+                RevealOutcome::Number(n)
+            }
+        }
+    }
+
+    /// Uncovers every cell that isn't a mine (wrong flags and maybe marks included) and leaves
+    /// the mines as they are, so the board looks solved without showing where the mines are.
+    /// Used on a copy of the board for the screenshot peek.
+    pub fn reveal_all_safe(&mut self) {
+        for (cells, states) in self.cells.iter().zip(self.states.iter_mut()) {
+            for (cell, state) in cells.iter().zip(states.iter_mut()) {
+                if *cell != Cell::Mine {
+                    *state = CellState::Uncovered;
+                }
+            }
+        }
+    }
+
+    /// Applies a player move and reports what it revealed. This is the single entry point the
+    /// GUI uses to change the board, so all game rules live here.
+    /// Moves that don't apply (revealing a flagged or uncovered cell, flagging an uncovered
+    /// cell, chording an unsatisfied number, out-of-range cells) change nothing.
+    pub fn apply_move(&mut self, mv: Move) -> MoveResult {
+        let mut result = MoveResult::default();
+        match mv {
+            Move::Reveal(row, col) => match self.reveal(row, col) {
+                RevealOutcome::Mine => {
+                    result.revealed.push((row, col, 0));
+                    result.mine_hit = Some((row, col));
+                }
+                RevealOutcome::Number(_) => result.revealed.push((row, col, 0)),
+                RevealOutcome::Empty { flooded } => result.revealed = flooded,
+                RevealOutcome::AlreadyRevealed | RevealOutcome::Flagged => {}
+            },
+            Move::Flag(row, col) => {
+                let state = self.cell_state(row, col);
+                if matches!(state, Some(CellState::Covered | CellState::Maybe)) {
+                    self.flag_cell(row, col);
+                }
+            }
+            Move::Unflag(row, col) => self.unflag_cell(row, col),
+            Move::Mark(row, col) => {
+                let state = self.cell_state(row, col);
+                if matches!(state, Some(CellState::Covered | CellState::Flagged)) {
+                    self.states[row][col] = CellState::Maybe;
+                }
+            }
+            Move::Unmark(row, col) => {
+                if self.cell_state(row, col) == Some(CellState::Maybe) {
+                    self.states[row][col] = CellState::Covered;
+                }
+            }
+            Move::Absorb(row, col) => {
+                let uncovered = self.cell_state(row, col) == Some(CellState::Uncovered);
+                if uncovered && self.cells[row][col] == Cell::Mine {
+                    self.states[row][col] = CellState::Flagged;
+                }
+            }
+            Move::Chord(row, col) => {
+                for (r, c) in self.chord(row, col) {
+                    if self.cells[r][c] == Cell::Mine && result.mine_hit.is_none() {
+                        result.mine_hit = Some((r, c));
+                    }
+                    result
+                        .revealed
+                        .push((r, c, r.abs_diff(row).max(c.abs_diff(col))));
+                }
             }
         }
+        result
     }
 
     /// Uncovers the cell at the given position, if valid.
     pub fn uncover_cell(&mut self, row: usize, col: usize) {
-        if let Some(state) = self.states.get_mut(row).and_then(|r| r.get_mut(col)) {
-            *state = CellState::Uncovered;
+        if self.in_bounds(row, col) {
+            self.states[row][col] = CellState::Uncovered;
         }
     }
 
@@ -188,21 +576,269 @@ impl Board {
 
     /// Randomly places mines, avoiding the given cell and its neighbors.
     pub fn place_mines_avoiding(&mut self, avoid_row: usize, avoid_col: usize) {
-        // Build a list of all positions except the avoid cell and its neighbors
-        let mut positions = Vec::new();
+        self.place_mines_excluding(&self.first_click_zone(avoid_row, avoid_col));
+    }
+
+    /// Randomly places mines in any cell not in `protected`, e.g. a multi-cell safe zone or
+    /// cells kept clear in the editor. If fewer cells are eligible than there are mines,
+    /// only that many mines are placed.
+    pub fn place_mines_excluding(&mut self, protected: &HashSet<(usize, usize)>) {
+        self.place_mines_with_rng(protected, &mut thread_rng());
+    }
+
+    /// Finds an edge 50/50: two side-by-side cells, at least one on the board's edge, where one
+    /// is a mine and the other is safe, and every cell that borders only one of them is a mine
+    /// (or off the board). No revealed number can tell such a pair apart, so the player has
+    /// to guess. Returns the (mine, safe) pair of the first one found in row-major order.
+    pub fn edge_fifty_fifty(&self) -> Option<((usize, usize), (usize, usize))> {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                for other in [(row, col + 1), (row + 1, col)] {
+                    let cell = (row, col);
+                    if !self.in_bounds(other.0, other.1)
+                        || !(self.is_on_edge(cell) || self.is_on_edge(other))
+                    {
+                        continue;
+                    }
+                    let (mine, safe) = match (self.is_mine_at(cell), self.is_mine_at(other)) {
+                        (true, false) => (cell, other),
+                        (false, true) => (other, cell),
+                        _ => continue,
+                    };
+                    let near_mine: HashSet<_> = self.neighbors(mine.0, mine.1).collect();
+                    let near_safe: HashSet<_> = self.neighbors(safe.0, safe.1).collect();
+                    let hidden = near_mine
+                        .symmetric_difference(&near_safe)
+                        .filter(|&&c| c != mine && c != safe)
+                        .all(|&c| self.is_mine_at(c));
+                    if hidden {
+                        return Some((mine, safe));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// A lighter-weight alternative to no-guess generation: after mines are placed, breaks up
+    /// edge 50/50s (see `edge_fifty_fifty`) by moving the mine of each one to a safe cell
+    /// outside `avoid`, preferring cells away from the edges. The mine count is unchanged and
+    /// the result is deterministic for a given layout, so seeded boards stay reproducible.
+    /// Returns how many mines were moved.
+    pub fn reduce_edge_ambiguity(&mut self, avoid: &HashSet<(usize, usize)>) -> usize {
+        let mut moved = 0;
+        // Moving a mine can occasionally complete another pattern; cap the passes so a
+        // crowded board can't keep shuffling forever
+        let max_moves = self.mines.max(1) * 2;
+        while moved < max_moves {
+            let Some((mine, safe)) = self.edge_fifty_fifty() else {
+                break;
+            };
+            let target = (0..self.height)
+                .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+                .filter(|&cell| cell != safe && !avoid.contains(&cell) && !self.is_mine_at(cell))
+                .min_by_key(|&cell| self.is_on_edge(cell));
+            let Some((row, col)) = target else {
+                break; // No room to move the mine anywhere
+            };
+            self.cells[mine.0][mine.1] = Cell::Empty;
+            self.mine_positions.remove(&mine);
+            self.cells[row][col] = Cell::Mine;
+            self.mine_positions.insert((row, col));
+            moved += 1;
+        }
+        if moved > 0 {
+            self.calculate_numbers();
+        }
+        moved
+    }
+
+    /// Returns whether the cell at (row, col) is in the board's outer ring.
+    fn is_on_edge(&self, (row, col): (usize, usize)) -> bool {
+        row == 0 || col == 0 || row + 1 == self.height || col + 1 == self.width
+    }
+
+    /// Returns whether the cell at (row, col) holds a mine (the cell must be on the board).
+    fn is_mine_at(&self, (row, col): (usize, usize)) -> bool {
+        self.cells[row][col] == Cell::Mine
+    }
+
+    /// Returns the cell and its in-bounds neighbors: the area a first click keeps mine-free.
+    fn first_click_zone(&self, row: usize, col: usize) -> HashSet<(usize, usize)> {
+        let mut zone: HashSet<(usize, usize)> = self.neighbors(row, col).collect();
+        zone.insert((row, col));
+        zone
+    }
+
+    /// Places mines with a per-cell probability weight, e.g. for a "graduated difficulty" variant
+    /// where mines get denser toward one corner. Mines are sampled without replacement,
+    /// proportionally to `weight_fn(row, col)` of each eligible cell. The `avoid` cell and its
+    /// neighbors never get a mine, nor does any cell whose weight is zero, negative, or not finite.
+    /// If fewer cells are eligible than there are mines, only that many mines are placed.
+    /// Mines placed before are cleared first, and the numbers are calculated afterwards.
+    pub fn place_mines_weighted(
+        &mut self,
+        avoid: (usize, usize),
+        weight_fn: impl Fn(usize, usize) -> f32,
+    ) {
+        let zone = self.first_click_zone(avoid.0, avoid.1);
+        let mut weighted = Vec::new();
         for row in 0..self.height {
             for col in 0..self.width {
                 // Avoid the clicked cell and its neighbors
-                if (row as isize - avoid_row as isize).abs() <= 1
-                    && (col as isize - avoid_col as isize).abs() <= 1
+                if zone.contains(&(row, col)) {
+                    continue;
+                }
+                let weight = weight_fn(row, col);
+                if weight.is_finite() && weight > 0.0 {
+                    weighted.push(((row, col), weight));
+                }
+            }
+        }
+        let amount = self.mines.min(weighted.len());
+        let chosen: Vec<(usize, usize)> = weighted
+            .choose_multiple_weighted(&mut thread_rng(), amount, |&(_, weight)| weight)
+            .map(|picked| picked.map(|&(pos, _)| pos).collect())
+            .unwrap_or_default();
+
+        for (row, col) in self.mine_positions.drain() {
+            self.cells[row][col] = Cell::Empty;
+        }
+        for (row, col) in chosen {
+            self.cells[row][col] = Cell::Mine;
+            self.mine_positions.insert((row, col));
+        }
+        self.calculate_numbers();
+    }
+
+    /// Returns the mines inside the given rows and columns (half-open ranges), sorted by position.
+    /// Useful for checking how mines are spread across zones such as board quadrants.
+    pub fn mine_positions_within(
+        &self,
+        rows: std::ops::Range<usize>,
+        cols: std::ops::Range<usize>,
+    ) -> Vec<(usize, usize)> {
+        let mut mines: Vec<(usize, usize)> = self
+            .mine_positions
+            .iter()
+            .filter(|(row, col)| rows.contains(row) && cols.contains(col))
+            .copied()
+            .collect();
+        mines.sort();
+        mines
+    }
+
+    /// Places mines like `place_mines_avoiding`, but from a seeded RNG.
+    /// The same seed, board size, and first click always produce the same layout,
+    /// so a game can be shared and replayed from its seed.
+    pub fn place_mines_avoiding_seeded(&mut self, avoid_row: usize, avoid_col: usize, seed: u64) {
+        let zone = self.first_click_zone(avoid_row, avoid_col);
+        self.place_mines_with_rng(&zone, &mut StdRng::seed_from_u64(seed));
+    }
+
+    /// Builds the daily puzzle for the given size and date (any string, e.g. `"2026-10-16"`):
+    /// everyone using the same size and date gets the same layout. Mines are placed by
+    /// `place_mines_avoiding_seeded` from `daily_seed`, around the canonical opening cell
+    /// `(height / 2, width / 2)`, which is always empty. Numbers are calculated, nothing is uncovered.
+    pub fn daily(size: BoardSize, date: &str) -> Board {
+        let (width, height, mines) = size.params();
+        let mut board = Board::new(width, height, mines);
+        board.place_mines_avoiding_seeded(height / 2, width / 2, Board::daily_seed(size, date));
+        board.calculate_numbers();
+        board
+    }
+
+    /// Returns the seed of the daily puzzle for the given size and date.
+    /// Uses FNV-1a rather than the std hasher, whose output may change between Rust releases.
+    pub fn daily_seed(size: BoardSize, date: &str) -> u64 {
+        let key = format!("{}:{}", size.label(), date);
+        key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
+    /// Moves every mine out of the 3x3 area around the given cell, so revealing it opens an
+    /// empty region, then recalculates numbers. Used when mines were placed before the player's
+    /// first click (e.g. by the auto-open). Mines go to random covered cells, preferring cells
+    /// away from uncovered ones so the numbers already on screen stay the same.
+    /// If there is not enough room outside the area, the clicked cell's own mine is moved first
+    /// (into the area if need be), so the cell itself is safe whenever any covered cell is.
+    /// Returns how many mines were moved (mines with nowhere to go stay put).
+    pub fn clear_opening(&mut self, row: usize, col: usize) -> usize {
+        let in_opening = |r: usize, c: usize| r.abs_diff(row) <= 1 && c.abs_diff(col) <= 1;
+        let mut to_move: Vec<(usize, usize)> = self
+            .mine_positions
+            .iter()
+            .copied()
+            .filter(|&(r, c)| in_opening(r, c))
+            .collect();
+        to_move.sort_by_key(|&cell| cell != (row, col)); // The clicked cell first
+        if to_move.is_empty() {
+            return 0;
+        }
+        let mut hidden = Vec::new(); // Not next to an uncovered cell
+        let mut visible = Vec::new();
+        for r in 0..self.height {
+            for c in 0..self.width {
+                if in_opening(r, c)
+                    || self.cells[r][c] == Cell::Mine
+                    || self.states[r][c] != CellState::Covered
                 {
                     continue;
                 }
-                positions.push((row, col));
+                let next_to_uncovered = self
+                    .neighbors(r, c)
+                    .any(|(nr, nc)| self.states[nr][nc] == CellState::Uncovered);
+                if next_to_uncovered {
+                    visible.push((r, c));
+                } else {
+                    hidden.push((r, c));
+                }
             }
         }
         let mut rng = thread_rng();
-        positions.shuffle(&mut rng);
+        hidden.shuffle(&mut rng);
+        visible.shuffle(&mut rng);
+        // Last resort for the clicked cell: another safe covered cell of the area
+        let inside = self.neighbors_matching(row, col, |cell, state| {
+            cell != Cell::Mine && state == CellState::Covered
+        });
+        let mut destinations = hidden.into_iter().chain(visible);
+
+        let mut moved = 0;
+        for (i, from) in to_move.into_iter().enumerate() {
+            let to = match destinations.next() {
+                Some(to) => to,
+                None if i == 0 && from == (row, col) => match inside.first() {
+                    Some(&to) => to,
+                    None => break,
+                },
+                None => break,
+            };
+            self.move_mine(from, to);
+            moved += 1;
+        }
+        self.calculate_numbers();
+        moved
+    }
+
+    /// Moves a mine from one cell to another (numbers are not recalculated).
+    fn move_mine(&mut self, from: (usize, usize), to: (usize, usize)) {
+        self.cells[from.0][from.1] = Cell::Empty;
+        self.mine_positions.remove(&from);
+        self.cells[to.0][to.1] = Cell::Mine;
+        self.mine_positions.insert(to);
+    }
+
+    /// Shared mine placement logic: shuffles all positions outside `protected` with the given
+    /// RNG and places mines on the first `mines` of them.
+    fn place_mines_with_rng(&mut self, protected: &HashSet<(usize, usize)>, rng: &mut impl Rng) {
+        // All unprotected positions in row-major order, so seeded layouts stay reproducible
+        let mut positions: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|pos| !protected.contains(pos))
+            .collect();
+        positions.shuffle(rng);
 
         self.mine_positions.clear();
         for &(row, col) in positions.iter().take(self.mines) {
@@ -211,37 +847,251 @@ impl Board {
         }
     }
 
+    /// Toggles a mine at the given position, if valid (used by the level editor).
+    /// A mine becomes an empty cell and any other cell becomes a mine (see `add_mine` and
+    /// `remove_mine`).
+    pub fn toggle_mine(&mut self, row: usize, col: usize) {
+        if !self.remove_mine(row, col) {
+            self.add_mine(row, col);
+        }
+    }
+
+    /// Places a mine at (row, col). The mine count and mine positions are kept in sync, and
+    /// the numbers around the cell are updated. Returns false (and changes nothing) if the
+    /// cell is off the board or already a mine.
+    pub fn add_mine(&mut self, row: usize, col: usize) -> bool {
+        if !matches!(self.cell(row, col), Some(cell) if cell != Cell::Mine) {
+            return false;
+        }
+        self.cells[row][col] = Cell::Mine;
+        self.mine_positions.insert((row, col));
+        self.mines = self.mine_positions.len();
+        self.recount_around(row, col);
+        true
+    }
+
+    /// Removes the mine at (row, col), leaving a number or empty cell. The mine count and mine
+    /// positions are kept in sync, and the numbers around the cell are updated. Returns false
+    /// (and changes nothing) if there is no mine there.
+    pub fn remove_mine(&mut self, row: usize, col: usize) -> bool {
+        if self.cell(row, col) != Some(Cell::Mine) {
+            return false;
+        }
+        self.cells[row][col] = Cell::Empty; // Recounted below
+        self.mine_positions.remove(&(row, col));
+        self.mines = self.mine_positions.len();
+        self.recount_around(row, col);
+        true
+    }
+
+    /// Recalculates the numbers of a cell and its neighbors after the mine there changed.
+    fn recount_around(&mut self, row: usize, col: usize) {
+        let (coords, len) = self.neighbor_coords(row, col);
+        for (r, c) in coords[..len].iter().copied().chain([(row, col)]) {
+            if self.cells[r][c] != Cell::Mine {
+                self.cells[r][c] = self.number_for(r, c);
+            }
+        }
+    }
+
     // === Neighbor and Number Logic ===
 
-    /// Returns an iterator over all valid neighbor coordinates for a given cell.
-    /// This helper avoids code duplication in neighbor logic.
+    /// Returns how the board's cells connect to their neighbors.
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Changes how the board's cells connect to their neighbors and recalculates the numbers.
+    /// Flood fill, chording, and deduction follow the new topology from then on.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+        self.calculate_numbers();
+    }
+
+    /// Returns an iterator over all valid neighbor coordinates for a given cell, following the
+    /// board's topology. This helper avoids code duplication in neighbor logic.
     pub fn neighbors(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
-        (-1..=1).flat_map(move |dr| {
-            (-1..=1).filter_map(move |dc| {
+        self.neighbors_radius(row, col, 1)
+            .filter(move |&(nr, nc)| self.topology.connects(nr.abs_diff(row), nc.abs_diff(col)))
+    }
+
+    /// Returns the valid neighbor coordinates of a cell in a stack array, plus how many are valid.
+    /// Only the first `count` entries are meaningful (at most 8, fewer on edges and corners or
+    /// with the 4-neighbor topology).
+    /// Same cells and order as `neighbors`, but without iterator/closure overhead, for hot loops
+    /// such as `calculate_numbers` and flood fill.
+    pub fn neighbor_coords(&self, row: usize, col: usize) -> ([(usize, usize); 8], usize) {
+        let mut coords = [(0, 0); 8];
+        let mut count = 0;
+        for nr in row.saturating_sub(1)..=(row + 1).min(self.height.saturating_sub(1)) {
+            for nc in col.saturating_sub(1)..=(col + 1).min(self.width.saturating_sub(1)) {
+                let connected = self.topology.connects(nr.abs_diff(row), nc.abs_diff(col));
+                if (nr != row || nc != col) && connected {
+                    coords[count] = (nr, nc);
+                    count += 1;
+                }
+            }
+        }
+        (coords, count)
+    }
+
+    /// Returns an iterator over all valid cells within the given Chebyshev radius of a cell,
+    /// excluding the cell itself. A radius of 1 gives the classic 8 neighbors; larger radii
+    /// support "big neighborhood" variants.
+    pub fn neighbors_radius(
+        &self,
+        row: usize,
+        col: usize,
+        radius: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let r = radius as isize;
+        (-r..=r).flat_map(move |dr| {
+            (-r..=r).filter_map(move |dc| {
                 if dr == 0 && dc == 0 {
                     None
                 } else {
-                    let nr = row as isize + dr;
-                    let nc = col as isize + dc;
-                    if nr >= 0 && nr < self.height as isize && nc >= 0 && nc < self.width as isize {
-                        Some((nr as usize, nc as usize))
-                    } else {
-                        None
-                    }
+                    let nr = row.checked_add_signed(dr)?;
+                    let nc = col.checked_add_signed(dc)?;
+                    self.in_bounds(nr, nc).then_some((nr, nc))
                 }
             })
         })
     }
 
+    /// Returns an iterator over the neighbors of a cell that are still covered (not flagged).
+    pub fn covered_neighbors(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(row, col)
+            .filter(move |&(nr, nc)| self.states[nr][nc] == CellState::Covered)
+    }
+
+    /// Returns the set of a cell's covered neighbors (see `covered_neighbors`), for comparing
+    /// the neighborhoods of two numbers.
+    pub fn covered_neighbor_set(&self, row: usize, col: usize) -> HashSet<(usize, usize)> {
+        self.covered_neighbors(row, col).collect()
+    }
+
+    /// Returns an iterator over the neighbors of a cell that are flagged.
+    pub fn flagged_neighbors(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(row, col)
+            .filter(move |&(nr, nc)| self.states[nr][nc] == CellState::Flagged)
+    }
+
+    /// Returns the neighbors of a cell whose content and state satisfy `pred`, e.g.
+    /// `|cell, state| cell == Cell::Mine && state == CellState::Covered` for covered mines.
+    pub fn neighbors_matching(
+        &self,
+        row: usize,
+        col: usize,
+        pred: impl Fn(Cell, CellState) -> bool,
+    ) -> Vec<(usize, usize)> {
+        self.neighbors(row, col)
+            .filter(|&(nr, nc)| pred(self.cells[nr][nc], self.states[nr][nc]))
+            .collect()
+    }
+
+    /// Counts a cell's mine, flagged, maybe, covered, and uncovered neighbors in a single pass,
+    /// for callers that need several of these counts at once (chording, dimming, the solver).
+    pub fn tally_neighbors(&self, row: usize, col: usize) -> NeighborTally {
+        let mut tally = NeighborTally::default();
+        let (coords, len) = self.neighbor_coords(row, col);
+        for &(nr, nc) in &coords[..len] {
+            if self.cells[nr][nc] == Cell::Mine {
+                tally.mines += 1;
+            }
+            match self.states[nr][nc] {
+                CellState::Flagged => tally.flagged += 1,
+                CellState::Maybe => tally.maybe += 1,
+                CellState::Covered => tally.covered += 1,
+                CellState::Uncovered => tally.uncovered += 1,
+            }
+        }
+        tally
+    }
+
+    /// Returns how many neighbors of a cell are flagged.
+    pub fn adjacent_flag_count(&self, row: usize, col: usize) -> usize {
+        self.flagged_neighbors(row, col).count()
+    }
+
+    /// Returns how many neighbors of a cell are flagged or marked as maybe; chording treats
+    /// both as mines.
+    pub fn adjacent_marked_count(&self, row: usize, col: usize) -> usize {
+        let tally = self.tally_neighbors(row, col);
+        tally.flagged + tally.maybe
+    }
+
+    /// Returns the number of cells marked as maybe (these don't count as flags).
+    pub fn maybe_count(&self) -> usize {
+        self.count_by_state(CellState::Maybe)
+    }
+
+    /// Returns how many safe cells hold each value 0-8 (index 0 counts empty cells), e.g. to
+    /// check that generated boards are fair. Mine cells aren't counted, and neither are
+    /// numbers above 8 (only `calculate_numbers_radius` with a radius above 1 makes those).
+    /// Only meaningful after `calculate_numbers`.
+    pub fn number_histogram(&self) -> [usize; 9] {
+        let mut histogram = [0; 9];
+        for cell in self.cells.iter().flatten() {
+            match *cell {
+                Cell::Empty => histogram[0] += 1,
+                Cell::Number(n) if n <= 8 => histogram[n as usize] += 1,
+                Cell::Number(_) | Cell::Mine => {}
+            }
+        }
+        histogram
+    }
+
+    /// Returns how many neighbors of a cell are still covered (not flagged).
+    pub fn adjacent_covered_count(&self, row: usize, col: usize) -> usize {
+        self.covered_neighbors(row, col).count()
+    }
+
     /// Calculates numbers for each cell based on adjacent mines.
+    /// Same result as `calculate_numbers_radius(1)`, using the allocation-free `neighbor_coords`.
     pub fn calculate_numbers(&mut self) {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if let Cell::Mine = self.cells[row][col] {
+                    continue;
+                }
+                self.cells[row][col] = self.number_for(row, col);
+            }
+        }
+    }
+
+    /// Returns the content a safe cell should have: its adjacent mine count as a number, or
+    /// empty when no neighbor is a mine.
+    fn number_for(&self, row: usize, col: usize) -> Cell {
+        let (coords, len) = self.neighbor_coords(row, col);
+        let count = coords[..len]
+            .iter()
+            .filter(|&&(nr, nc)| self.cells[nr][nc] == Cell::Mine)
+            .count();
+        if count == 0 {
+            Cell::Empty
+        } else {
+            Cell::Number(count as u8)
+        }
+    }
+
+    /// Calculates numbers for each cell by counting mines within the given Chebyshev radius.
+    /// `calculate_numbers` uses radius 1; radius 2 counts up to 24 surrounding cells.
+    pub fn calculate_numbers_radius(&mut self, radius: usize) {
         for row in 0..self.height {
             for col in 0..self.width {
                 if let Cell::Mine = self.cells[row][col] {
                     continue;
                 }
                 let count = self
-                    .neighbors(row, col)
+                    .neighbors_radius(row, col, radius)
                     .filter(|&(nr, nc)| self.cells[nr][nc] == Cell::Mine)
                     .count();
                 self.cells[row][col] = if count == 0 {
@@ -253,12 +1103,52 @@ impl Board {
         }
     }
 
+    /// Returns a rough danger hint for a cell: the sum of the numbers on its uncovered neighbors.
+    /// Only information visible to the player is used (never `mine_positions` or covered cells),
+    /// so it is safe to show as an accessibility overlay.
+    pub fn visible_pressure(&self, row: usize, col: usize) -> f32 {
+        self.adjacent_number_sum(row, col) as f32
+    }
+
+    /// Returns the sum of the values of the uncovered numbers around a cell.
+    /// Covered cells next to higher numbers are more constrained, so revealing one tells the
+    /// player more; hints use this to pick the most informative safe cell.
+    pub fn adjacent_number_sum(&self, row: usize, col: usize) -> usize {
+        self.neighbors(row, col)
+            .filter(|&(nr, nc)| self.states[nr][nc] == CellState::Uncovered)
+            .map(|(nr, nc)| match self.cells[nr][nc] {
+                Cell::Number(n) => n as usize,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Returns whether a cell is on the frontier: still covered (not flagged or marked) and
+    /// next to at least one uncovered number. Only these cells have visible pressure.
+    pub fn is_frontier(&self, row: usize, col: usize) -> bool {
+        self.cell_state(row, col) == Some(CellState::Covered)
+            && self.neighbors(row, col).any(|(nr, nc)| {
+                self.states[nr][nc] == CellState::Uncovered
+                    && matches!(self.cells[nr][nc], Cell::Number(_))
+            })
+    }
+
+    /// Returns every frontier cell (see `is_frontier`) in row-major order.
+    pub fn frontier_cells(&self) -> Vec<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.is_frontier(row, col))
+            .collect()
+    }
+
     // === Flood Fill (Reveal) Logic ===
 
     /// Reveals all connected empty cells and their neighbors (flood fill), and returns their positions and wave distance.
     /// Each tuple is (row, col, distance_from_origin).
     /// This is a classic BFS flood fill, revealing all connected empty cells and their neighbors,
     /// and tracking the "wave" distance from the starting cell.
+    /// Only covered cells are uncovered: flagged and maybe-marked cells (even empty ones) are
+    /// left as they are and the fill doesn't spread through them.
     pub fn flood_fill_wave(&mut self, row: usize, col: usize) -> Vec<(usize, usize, usize)> {
         let mut queue = VecDeque::new();
         let mut revealed = Vec::new();
@@ -268,13 +1158,15 @@ impl Board {
         visited[row][col] = true;
 
         while let Some((r, c, dist)) = queue.pop_front() {
-            if self.states[r][c] == CellState::Uncovered {
+            // Neighbors are only queued while covered, but the starting cell may be marked
+            if self.states[r][c] != CellState::Covered {
                 continue;
             }
             self.states[r][c] = CellState::Uncovered;
             revealed.push((r, c, dist));
             if self.cells[r][c] == Cell::Empty {
-                for (nr, nc) in self.neighbors(r, c) {
+                let (coords, len) = self.neighbor_coords(r, c);
+                for &(nr, nc) in &coords[..len] {
                     if !visited[nr][nc] && self.states[nr][nc] == CellState::Covered {
                         queue.push_back((nr, nc, dist + 1));
                         visited[nr][nc] = true;
@@ -285,23 +1177,809 @@ impl Board {
         revealed
     }
 
+    /// Returns the cells a `flood_fill_wave` from (row, col) would uncover, in the same order,
+    /// without changing the board. Used for previews and analysis.
+    pub fn connected_empty_region(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        if !self.in_bounds(row, col) {
+            return Vec::new();
+        }
+        let mut queue = VecDeque::new();
+        let mut region = Vec::new();
+        let mut visited = vec![vec![false; self.width]; self.height];
+
+        queue.push_back((row, col));
+        visited[row][col] = true;
+
+        while let Some((r, c)) = queue.pop_front() {
+            if self.states[r][c] != CellState::Covered {
+                continue;
+            }
+            region.push((r, c));
+            if self.cells[r][c] == Cell::Empty {
+                let (coords, len) = self.neighbor_coords(r, c);
+                for &(nr, nc) in &coords[..len] {
+                    if !visited[nr][nc] && self.states[nr][nc] == CellState::Covered {
+                        queue.push_back((nr, nc));
+                        visited[nr][nc] = true;
+                    }
+                }
+            }
+        }
+        region
+    }
+
+    /// Same as `flood_fill_wave`, but every distance above `max_dist` is clamped to `max_dist`.
+    /// All the same cells are revealed; only their wave distances change, so far cells animate
+    /// together and the wave animation length stays bounded on nearly empty boards.
+    pub fn flood_fill_wave_capped(
+        &mut self,
+        row: usize,
+        col: usize,
+        max_dist: usize,
+    ) -> Vec<(usize, usize, usize)> {
+        let mut revealed = self.flood_fill_wave(row, col);
+        for (_, _, dist) in &mut revealed {
+            *dist = (*dist).min(max_dist);
+        }
+        revealed
+    }
+
+    // === Assist Logic ===
+
+    /// Chords an uncovered number: if its flagged-neighbor count (maybe marks included) equals
+    /// its value, every remaining covered neighbor is uncovered (empty cells flood-fill as usual).
+    /// Returns the newly uncovered cells, or nothing if the cell isn't a satisfied number.
+    /// If a flag was wrong the chord hits a mine; such mines are uncovered and included in
+    /// the result, so the caller should check the returned cells for `Cell::Mine`.
+    pub fn chord(&mut self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let satisfied = match self.cell(row, col) {
+            Some(Cell::Number(n)) => {
+                self.states[row][col] == CellState::Uncovered
+                    && self.adjacent_marked_count(row, col) == n as usize
+            }
+            _ => false,
+        };
+        if !satisfied {
+            return Vec::new();
+        }
+        self.uncover_covered_neighbors(row, col)
+    }
+
+    /// The safe core of chording: if the uncovered number at (row, col) has exactly its mines
+    /// flagged (maybe marks included) and none of its remaining covered neighbors is a mine,
+    /// uncovers those neighbors (empty cells flood-fill as usual) and returns them.
+    /// Unlike `chord`, this never hits a mine; with a wrong flag it does nothing.
+    pub fn reveal_safe_neighbors(&mut self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let satisfied = match self.cell(row, col) {
+            Some(Cell::Number(n)) => {
+                self.states[row][col] == CellState::Uncovered
+                    && self.adjacent_marked_count(row, col) == n as usize
+            }
+            _ => false,
+        };
+        let flags_correct = !self
+            .covered_neighbors(row, col)
+            .any(|(r, c)| self.cells[r][c] == Cell::Mine);
+        if !(satisfied && flags_correct) {
+            return Vec::new();
+        }
+        self.uncover_covered_neighbors(row, col)
+    }
+
+    /// Uncovers every covered neighbor of a cell (flood filling empties) and returns the newly
+    /// uncovered cells, mines included.
+    fn uncover_covered_neighbors(&mut self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let mut revealed = Vec::new();
+        let targets: Vec<(usize, usize)> = self.covered_neighbors(row, col).collect();
+        for (r, c) in targets {
+            if self.states[r][c] == CellState::Covered {
+                let wave = self.flood_fill_wave(r, c);
+                revealed.extend(wave.into_iter().map(|(r, c, _)| (r, c)));
+            }
+        }
+        revealed
+    }
+
+    /// Performs one assisted "chord" pass over the board.
+    /// Every uncovered number whose flagged-neighbor count equals its value is chorded
+    /// (see `chord`). Returns every newly uncovered cell, including any mines hit because
+    /// of wrong flags, so the caller should check the returned cells for `Cell::Mine`.
+    pub fn auto_chord_all(&mut self) -> Vec<(usize, usize)> {
+        // Find all satisfied numbers first, so this pass only chords what was visible at its start
+        let satisfied: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|&(row, col)| match self.cells[row][col] {
+                Cell::Number(n) => {
+                    self.states[row][col] == CellState::Uncovered
+                        && self.adjacent_marked_count(row, col) == n as usize
+                }
+                _ => false,
+            })
+            .collect();
+
+        let mut revealed = Vec::new();
+        for (row, col) in satisfied {
+            revealed.extend(self.chord(row, col));
+        }
+        revealed
+    }
+
+    // === Deduction Logic ===
+
+    /// Returns every covered or flagged cell that can be proven safe from visible information only:
+    /// the numbers on uncovered cells and the total mine count (flags are not trusted).
+    /// Uses the single-number rule (a number whose unknown neighbors are all mines or all safe)
+    /// and the subset rule (if one number's unknown cells lie inside another's, the extra cells
+    /// hold the difference), adding every derived constraint back until nothing new follows.
+    pub fn deduce_safe_cells(&self) -> HashSet<(usize, usize)> {
+        self.deduce_cells().safe
+    }
+
+    /// Like `deduce_safe_cells`, but returns both the cells proven safe and the cells proven
+    /// to be mines. Both sets may include flagged cells.
+    pub fn deduce_cells(&self) -> Deductions {
+        self.deduce(true)
+    }
+
+    /// Like `deduce_cells`, but only compares the visible numbers with each other (the subset
+    /// rule); the total mine count isn't used.
+    pub fn deduce_cells_from_numbers(&self) -> Deductions {
+        self.deduce(false)
+    }
+
+    /// Shared engine of `deduce_cells` and `deduce_cells_from_numbers`: the visible number
+    /// constraints closed under the subset rule, plus the total mine count if `use_mine_count`.
+    fn deduce(&self, use_mine_count: bool) -> Deductions {
+        let mut safe = HashSet::new();
+        let mut mines = HashSet::new();
+        loop {
+            let mut constraints = self.visible_constraints(&safe, &mines);
+
+            // Close the number constraints under the subset rule. Derived sets are always
+            // subsets of a single number's neighbors, so this stays small.
+            let mut seen: HashSet<BTreeSet<(usize, usize)>> =
+                constraints.iter().map(|(cells, _)| cells.clone()).collect();
+            let mut i = 0;
+            while i < constraints.len() {
+                for j in 0..i {
+                    for (small, large) in [(j, i), (i, j)] {
+                        let derived = subset_difference(&constraints[small], &constraints[large]);
+                        if let Some(derived) = derived {
+                            if seen.insert(derived.0.clone()) {
+                                constraints.push(derived);
+                            }
+                        }
+                    }
+                }
+                i += 1;
+            }
+
+            // The total mine count constrains all unknown cells together
+            if use_mine_count {
+                let all_unknown: BTreeSet<(usize, usize)> = (0..self.height)
+                    .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+                    .filter(|&(r, c)| self.states[r][c] != CellState::Uncovered)
+                    .filter(|cell| !safe.contains(cell) && !mines.contains(cell))
+                    .collect();
+                let global = (all_unknown, self.mines.saturating_sub(mines.len()));
+                let from_global: Vec<_> = constraints
+                    .iter()
+                    .filter_map(|constraint| subset_difference(constraint, &global))
+                    .collect();
+                constraints.push(global);
+                constraints.extend(from_global);
+            }
+
+            // Single-number rule on every constraint
+            let before = safe.len() + mines.len();
+            for (cells, need) in &constraints {
+                if *need == 0 {
+                    safe.extend(cells.iter().copied());
+                } else if *need == cells.len() {
+                    mines.extend(cells.iter().copied());
+                }
+            }
+            if safe.len() + mines.len() == before {
+                return Deductions { safe, mines };
+            }
+        }
+    }
+
+    /// Builds one (unknown cells, mines among them) constraint per uncovered cell with unknown
+    /// neighbors. Cells already deduced as safe or as mines are removed from every constraint.
+    fn visible_constraints(
+        &self,
+        safe: &HashSet<(usize, usize)>,
+        mines: &HashSet<(usize, usize)>,
+    ) -> Vec<(BTreeSet<(usize, usize)>, usize)> {
+        let mut constraints = Vec::new();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.states[row][col] != CellState::Uncovered {
+                    continue;
+                }
+                let value = match self.cells[row][col] {
+                    Cell::Number(n) => n as usize,
+                    Cell::Empty => 0,
+                    Cell::Mine => continue,
+                };
+                let cells: BTreeSet<(usize, usize)> = self
+                    .neighbors(row, col)
+                    .filter(|&(r, c)| self.states[r][c] != CellState::Uncovered)
+                    .filter(|cell| !safe.contains(cell) && !mines.contains(cell))
+                    .collect();
+                if cells.is_empty() {
+                    continue;
+                }
+                let known_mines = self
+                    .neighbors(row, col)
+                    .filter(|n| mines.contains(n))
+                    .count();
+                constraints.push((cells, value.saturating_sub(known_mines)));
+            }
+        }
+        constraints
+    }
+
+    /// Returns true when the game is in progress but no covered cell can be proven safe,
+    /// so the player has to guess. Only visible information is used.
+    /// Returns false before the first uncover (the first click is always safe), after a
+    /// mine was uncovered, once the board is cleared, and whenever a logical move remains.
+    pub fn is_forced_guess(&self) -> bool {
+        let mut any_uncovered = false;
+        let mut cleared = true;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let uncovered = self.states[row][col] == CellState::Uncovered;
+                match self.cells[row][col] {
+                    Cell::Mine if uncovered => return false,
+                    Cell::Mine => {}
+                    _ if uncovered => any_uncovered = true,
+                    _ => cleared = false,
+                }
+            }
+        }
+        any_uncovered && !cleared && self.deduce_safe_cells().is_empty()
+    }
+
+    // === Statistics ===
+
+    /// Returns the fraction of placed flags that are on mines (0.0 to 1.0), for a post-game
+    /// flagging-accuracy stat. With no flags placed there are no wrong flags, so this returns 1.0.
+    pub fn correct_flag_ratio(&self) -> f32 {
+        let flags = self.flag_count();
+        if flags == 0 {
+            return 1.0;
+        }
+        self.flags_on_mines().len() as f32 / flags as f32
+    }
+
+    /// Returns the flagged cells that really hold a mine (correct flags), in row-major order.
+    /// Every other flagged cell is a wrong flag.
+    pub fn flags_on_mines(&self) -> Vec<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                self.states[row][col] == CellState::Flagged && self.cells[row][col] == Cell::Mine
+            })
+            .collect()
+    }
+
+    /// Returns the safe cells still needed to win (covered, flagged, or marked as maybe),
+    /// in row-major order. The game is won when this is empty.
+    pub fn unrevealed_safe_cells(&self) -> Vec<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                self.cells[row][col] != Cell::Mine && self.states[row][col] != CellState::Uncovered
+            })
+            .collect()
+    }
+
+    /// Returns the covered safe cell closest to (row, col) by straight-line distance, ties going
+    /// to the first in row-major order. Flagged and maybe-marked cells are skipped. This uses
+    /// the real mine layout, so it's a safety net rather than a deduction.
+    pub fn nearest_safe_covered(&self, row: usize, col: usize) -> Option<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|r| (0..self.width).map(move |c| (r, c)))
+            .filter(|&(r, c)| {
+                self.cells[r][c] != Cell::Mine && self.states[r][c] == CellState::Covered
+            })
+            .min_by_key(|&(r, c)| {
+                let (dr, dc) = (r.abs_diff(row), c.abs_diff(col));
+                dr * dr + dc * dc
+            })
+    }
+
+    /// Groups the mines into clusters of mines touching each other (any of the 8 surrounding
+    /// cells, whatever the topology), for difficulty tooling: dense clusters make boards harder.
+    /// Clusters are ordered by their first mine, and each lists its mines in row-major order.
+    pub fn mine_clusters(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut seen = vec![vec![false; self.width]; self.height];
+        let mut clusters = Vec::new();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.cells[row][col] != Cell::Mine || seen[row][col] {
+                    continue;
+                }
+                seen[row][col] = true;
+                let mut cluster = Vec::new();
+                let mut stack = vec![(row, col)];
+                while let Some((r, c)) = stack.pop() {
+                    cluster.push((r, c));
+                    for (nr, nc) in self.neighbors_radius(r, c, 1) {
+                        if self.cells[nr][nc] == Cell::Mine && !seen[nr][nc] {
+                            seen[nr][nc] = true;
+                            stack.push((nr, nc));
+                        }
+                    }
+                }
+                cluster.sort_unstable();
+                clusters.push(cluster);
+            }
+        }
+        clusters
+    }
+
+    // === Snapshots ===
+
+    /// Takes a snapshot of the board (its size, mine count, cells, cell states, mines, and
+    /// topology) that `restore` can bring back.
+    /// A snapshot is a plain copy, so it costs time and memory in proportion to the board
+    /// (about 1.8 microseconds for a Large board in a release build, as measured by the
+    /// ignored `bench_large_board_snapshot` test; restoring a clone takes about as long).
+    pub fn snapshot(&self) -> BoardSnapshot {
+        BoardSnapshot {
+            width: self.width,
+            height: self.height,
+            mines: self.mines,
+            cells: self.cells.clone(),
+            states: self.states.clone(),
+            mine_positions: self.mine_positions.clone(),
+            topology: self.topology,
+        }
+    }
+
+    /// Puts the board back exactly as it was when `snapshot` was taken.
+    pub fn restore(&mut self, snapshot: BoardSnapshot) {
+        self.width = snapshot.width;
+        self.height = snapshot.height;
+        self.mines = snapshot.mines;
+        self.cells = snapshot.cells;
+        self.states = snapshot.states;
+        self.mine_positions = snapshot.mine_positions;
+        self.topology = snapshot.topology;
+    }
+
+    // === Symmetry ===
+
+    /// Returns the board rotated a quarter turn clockwise: a `width` x `height` board becomes
+    /// `height` x `width`, and cell (row, col) moves to (col, height - 1 - row).
+    /// Cell states move with their cells, and numbers stay valid (neighborhoods are symmetric).
+    pub fn rotated(&self) -> Board {
+        let height = self.height;
+        self.transformed(self.height, self.width, |row, col| (col, height - 1 - row))
+    }
+
+    /// Returns the board mirrored left to right: cell (row, col) moves to (row, width - 1 - col).
+    pub fn mirrored(&self) -> Board {
+        let width = self.width;
+        self.transformed(self.width, self.height, |row, col| (row, width - 1 - col))
+    }
+
+    /// Builds a `width` x `height` board with every cell of this one moved to `place(row, col)`.
+    fn transformed(
+        &self,
+        width: usize,
+        height: usize,
+        place: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Board {
+        let mut board = Board::new(width, height, self.mines);
+        board.topology = self.topology;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let (r, c) = place(row, col);
+                board.cells[r][c] = self.cells[row][col];
+                board.states[r][c] = self.states[row][col];
+            }
+        }
+        board.mine_positions = self
+            .mine_positions
+            .iter()
+            .map(|&(r, c)| place(r, c))
+            .collect();
+        board
+    }
+
+    /// Returns true if `other` has the same mine layout as this board under some rotation or
+    /// reflection (the eight symmetries of a square, including none). Used to keep a puzzle
+    /// pool from serving the same puzzle twice in different orientations.
+    /// Only the mines are compared, not which cells are uncovered or flagged.
+    pub fn is_symmetric_equivalent(&self, other: &Board) -> bool {
+        let mut candidate = self.clone();
+        for _ in 0..4 {
+            for board in [&candidate, &candidate.mirrored()] {
+                if (board.width, board.height) == (other.width, other.height)
+                    && board.mine_positions == other.mine_positions
+                {
+                    return true;
+                }
+            }
+            candidate = candidate.rotated();
+        }
+        false
+    }
+
+    // === Export ===
+
+    /// Returns a copy of the board holding only what the player can see, so a bot handed it
+    /// can't cheat: every cell that isn't uncovered becomes `Cell::Empty`, and only uncovered
+    /// (exploded) mines stay in the mine positions. The states grid, the uncovered numbers,
+    /// and the total mine count are kept.
+    pub fn masked_view(&self) -> Board {
+        let mut view = self.clone();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.states[row][col] != CellState::Uncovered {
+                    view.cells[row][col] = Cell::Empty;
+                }
+            }
+        }
+        view.mine_positions
+            .retain(|&(row, col)| self.states[row][col] == CellState::Uncovered);
+        view
+    }
+
+    /// Returns only what the player can see, one `Vec<char>` per row, for external solvers and
+    /// bots: `#` covered, `F` flagged, `?` marked as maybe, ` ` uncovered empty, uncovered
+    /// numbers as one symbol each (see `number_symbol`), and `*` for an uncovered (exploded)
+    /// mine. Unlike `to_ascii`, covered
+    /// content is never revealed.
+    pub fn export_visible(&self) -> Vec<Vec<char>> {
+        (0..self.height)
+            .map(|row| {
+                (0..self.width)
+                    .map(|col| match self.states[row][col] {
+                        CellState::Covered => '#',
+                        CellState::Flagged => 'F',
+                        CellState::Maybe => '?',
+                        CellState::Uncovered => match self.cells[row][col] {
+                            Cell::Mine => '*',
+                            Cell::Empty => ' ',
+                            Cell::Number(n) => Self::number_symbol(n),
+                        },
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns what the player can see as one float per cell, one `Vec<f32>` per row, e.g. as
+    /// the input of a neural network. Like `export_visible`, covered content is never revealed.
+    /// The encoding:
+    /// - `-1.0` covered, `-0.75` marked as maybe, `-0.5` flagged
+    /// - `-0.25` an uncovered (exploded) mine
+    /// - `0.0` to `1.0` an uncovered safe cell: its number divided by 8 (`0.0` when empty)
+    pub fn feature_grid(&self) -> Vec<Vec<f32>> {
+        (0..self.height)
+            .map(|row| {
+                (0..self.width)
+                    .map(|col| match self.states[row][col] {
+                        CellState::Covered => -1.0,
+                        CellState::Maybe => -0.75,
+                        CellState::Flagged => -0.5,
+                        CellState::Uncovered => match self.cells[row][col] {
+                            Cell::Mine => -0.25,
+                            Cell::Empty => 0.0,
+                            Cell::Number(n) => n as f32 / 8.0,
+                        },
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the single character a number is written as in `to_ascii` and `export_visible`:
+    /// its digit up to 9, then base-36 letters (`a` = 10 up to `z` = 35, for the bigger counts
+    /// of `calculate_numbers_radius`), and `+` for anything larger.
+    fn number_symbol(n: u8) -> char {
+        char::from_digit(n as u32, 36).unwrap_or('+')
+    }
+
+    /// Returns the full board contents as ASCII art, one line per row.
+    /// Mines are `*`, numbers are one symbol each (see `number_symbol`), and empty cells
+    /// are `.`.
+    /// The output can be fed back into `from_mine_layout` to rebuild the same board.
+    pub fn to_ascii(&self) -> String {
+        self.cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Cell::Mine => '*',
+                        Cell::Number(n) => Self::number_symbol(*n),
+                        Cell::Empty => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns a compact text encoding of the board for sharing (e.g. in chat).
+    ///
+    /// Format: `WxH:` followed by a run-length-encoded stream of cells in row-major order.
+    /// Boards with only orthogonal neighbors (`Topology::VonNeumann`) write `WxH/4:` instead,
+    /// so their numbers come back the same. Each cell is one symbol combining its content and state:
+    /// `.`/`*` covered safe/mine, `o`/`x` uncovered safe/mine, `f`/`F` flagged safe/mine,
+    /// `m`/`M` marked-maybe safe/mine.
+    /// A run of more than one identical symbol is written as its length followed by the symbol.
+    /// Numbers are not stored; `from_compact` recalculates them from the mines.
+    pub fn to_compact(&self) -> String {
+        let mut out = format!("{}x{}", self.width, self.height);
+        if self.topology == Topology::VonNeumann {
+            out.push_str("/4");
+        }
+        out.push(':');
+        let mut run: Option<(char, usize)> = None;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let is_mine = self.cells[row][col] == Cell::Mine;
+                let symbol = match (self.states[row][col], is_mine) {
+                    (CellState::Covered, false) => '.',
+                    (CellState::Covered, true) => '*',
+                    (CellState::Uncovered, false) => 'o',
+                    (CellState::Uncovered, true) => 'x',
+                    (CellState::Flagged, false) => 'f',
+                    (CellState::Flagged, true) => 'F',
+                    (CellState::Maybe, false) => 'm',
+                    (CellState::Maybe, true) => 'M',
+                };
+                run = match run {
+                    Some((prev, count)) if prev == symbol => Some((prev, count + 1)),
+                    Some((prev, count)) => {
+                        Self::push_compact_run(&mut out, prev, count);
+                        Some((symbol, 1))
+                    }
+                    None => Some((symbol, 1)),
+                };
+            }
+        }
+        if let Some((prev, count)) = run {
+            Self::push_compact_run(&mut out, prev, count);
+        }
+        out
+    }
+
+    /// Appends one run to a compact encoding (the count is omitted for single cells).
+    fn push_compact_run(out: &mut String, symbol: char, count: usize) {
+        if count > 1 {
+            out.push_str(&count.to_string());
+        }
+        out.push(symbol);
+    }
+
+    /// Parses a board from the compact format produced by `to_compact`.
+    /// Returns an error message if the header is malformed or out of range (see `parse_size`),
+    /// or if the cell stream is malformed or doesn't fill the board exactly.
+    pub fn from_compact(text: &str) -> Result<Board, String> {
+        let (header, stream) = text
+            .trim()
+            .split_once(':')
+            .ok_or("missing ':' after the size header")?;
+        let (size, topology) = match header.split_once('/') {
+            None => (header, Topology::Square),
+            Some((size, "4")) => (size, Topology::VonNeumann),
+            Some((_, other)) => return Err(format!("unknown topology '/{}'", other)),
+        };
+        let (width, height) = size
+            .split_once('x')
+            .ok_or("size header must look like WxH")?;
+        let (width, height) = Self::parse_size(width, height)?;
+        let total = width * height; // At most MAX_SIDE squared, so it can't overflow
+
+        let mut board = Board::new(width, height, 0);
+        let mut index: usize = 0;
+        let mut count = String::new();
+        for ch in stream.chars() {
+            if ch.is_ascii_digit() {
+                count.push(ch);
+                continue;
+            }
+            let (state, is_mine) = match ch {
+                '.' => (CellState::Covered, false),
+                '*' => (CellState::Covered, true),
+                'o' => (CellState::Uncovered, false),
+                'x' => (CellState::Uncovered, true),
+                'f' => (CellState::Flagged, false),
+                'F' => (CellState::Flagged, true),
+                'm' => (CellState::Maybe, false),
+                'M' => (CellState::Maybe, true),
+                _ => return Err(format!("unknown cell symbol '{}'", ch)),
+            };
+            let run: usize = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse()
+                    .map_err(|_| format!("invalid run length '{}'", count))?
+            };
+            count.clear();
+            let end = index.checked_add(run).filter(|&end| end <= total);
+            let Some(end) = end else {
+                return Err(format!("cell stream is longer than {}x{}", width, height));
+            };
+            for i in index..end {
+                let (row, col) = (i / width, i % width);
+                board.states[row][col] = state;
+                if is_mine {
+                    board.cells[row][col] = Cell::Mine;
+                    board.mine_positions.insert((row, col));
+                }
+            }
+            index = end;
+        }
+        if !count.is_empty() {
+            return Err("cell stream ends with a run length but no symbol".to_string());
+        }
+        if index != total {
+            return Err(format!(
+                "cell stream has {} cells, expected {}",
+                index, total
+            ));
+        }
+        board.mines = board.mine_positions.len();
+        board.set_topology(topology); // Also calculates the numbers
+        Ok(board)
+    }
+
+    /// Parses the width and height of a saved board's `WxH` size. Returns an error message for
+    /// a malformed number or a side that is 0 or longer than `MAX_SIDE`, before anything is
+    /// allocated.
+    fn parse_size(width: &str, height: &str) -> Result<(usize, usize), String> {
+        let parse = |name: &str, text: &str| -> Result<usize, String> {
+            let side: usize = text
+                .parse()
+                .map_err(|_| format!("invalid {} '{}'", name, text))?;
+            if !(1..=Self::MAX_SIDE).contains(&side) {
+                return Err(format!("{} must be between 1 and {}", name, Self::MAX_SIDE));
+            }
+            Ok(side)
+        };
+        Ok((parse("width", width)?, parse("height", height)?))
+    }
+
+    /// Returns the mine layout in the level editor's `.mine` file format: a `WxH` size line
+    /// followed by one `row,col` line per mine, in row-major order. Cell states aren't saved.
+    pub fn to_mine_file(&self) -> String {
+        let mines: BTreeSet<&(usize, usize)> = self.mine_positions.iter().collect();
+        let mut out = format!("{}x{}\n", self.width, self.height);
+        for &(row, col) in mines {
+            out.push_str(&format!("{},{}\n", row, col));
+        }
+        out
+    }
+
+    /// Parses a mine layout written by `to_mine_file` into a fresh, fully covered board.
+    /// Blank lines are ignored. Returns an error message for a malformed or out-of-range size
+    /// (see `parse_size`), a malformed mine line, a mine off the board, or a mine listed twice.
+    pub fn from_mine_file(text: &str) -> Result<Board, String> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        let header = lines.next().ok_or("missing the WxH size line")?;
+        let (width, height) = header
+            .split_once('x')
+            .ok_or("size line must look like WxH")?;
+        let (width, height) = Self::parse_size(width, height)?;
+
+        let mut board = Board::new(width, height, 0);
+        for line in lines {
+            let parsed = line
+                .split_once(',')
+                .and_then(|(row, col)| Some((row.trim().parse().ok()?, col.trim().parse().ok()?)));
+            let Some((row, col)) = parsed else {
+                return Err(format!("mine line must look like row,col: '{}'", line));
+            };
+            if !board.in_bounds(row, col) {
+                return Err(format!(
+                    "mine {},{} is off the {}x{} board",
+                    row, col, width, height
+                ));
+            }
+            if !board.add_mine(row, col) {
+                return Err(format!("mine {},{} is listed twice", row, col));
+            }
+        }
+        Ok(board)
+    }
+
+    /// Prefix of the checksum line that follows the board in a save file.
+    const CHECKSUM_PREFIX: &'static str = "checksum:";
+
+    /// Longest save file `load_from_reader` accepts: the largest board's compact encoding (at
+    /// most one symbol per cell) plus the size and checksum lines, with room to spare.
+    const MAX_SAVE_LEN: usize = 4 * Self::MAX_SIDE * Self::MAX_SIDE;
+
+    /// Returns a stable 64-bit hash of the board: its dimensions, every cell and cell state,
+    /// and the mine positions in sorted order. Save files carry it so edits can be detected.
+    pub fn checksum(&self) -> u64 {
+        // FNV-1a: a fixed algorithm, so checksums stay the same across Rust versions
+        // (unlike `DefaultHasher`)
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut feed = |value: usize| {
+            for byte in (value as u64).to_le_bytes() {
+                hash = (hash ^ byte as u64).wrapping_mul(FNV_PRIME);
+            }
+        };
+        feed(self.width);
+        feed(self.height);
+        for (cells, states) in self.cells.iter().zip(&self.states) {
+            for (&cell, &state) in cells.iter().zip(states) {
+                feed(match cell {
+                    Cell::Empty => 0,
+                    Cell::Number(n) => n as usize,
+                    Cell::Mine => 9,
+                });
+                feed(state as usize);
+            }
+        }
+        let sorted: BTreeSet<&(usize, usize)> = self.mine_positions.iter().collect();
+        for &(row, col) in sorted {
+            feed(row);
+            feed(col);
+        }
+        hash
+    }
+
+    /// Writes the board in the compact format (see `to_compact`) to any sink: a file, a socket,
+    /// an in-memory buffer, a compressed stream, etc. The encoding is followed by a line with
+    /// the board's checksum (`checksum:<hex>`), which `load_from_reader` verifies.
+    pub fn save_to_writer(&self, mut w: impl Write) -> io::Result<()> {
+        writeln!(w, "{}", self.to_compact())?;
+        writeln!(w, "{}{:016x}", Self::CHECKSUM_PREFIX, self.checksum())?;
+        w.flush()
+    }
+
+    /// Reads a board written by `save_to_writer` from any source.
+    /// Returns an error message if reading fails, the data is longer than any save could be,
+    /// the data isn't a valid compact board (its size is checked before the board is built),
+    /// or the checksum is missing or doesn't match the board (the save file was edited).
+    pub fn load_from_reader(r: impl Read) -> Result<Board, String> {
+        let mut text = String::new();
+        r.take(Self::MAX_SAVE_LEN as u64 + 1)
+            .read_to_string(&mut text)
+            .map_err(|err| format!("could not read board: {}", err))?;
+        if text.len() > Self::MAX_SAVE_LEN {
+            return Err("save file is too large".to_string());
+        }
+        let mut lines = text.lines();
+        let board = Board::from_compact(lines.next().unwrap_or(""))?;
+        let stored = lines
+            .next()
+            .and_then(|line| line.trim().strip_prefix(Self::CHECKSUM_PREFIX))
+            .ok_or("missing checksum line")?;
+        let stored = u64::from_str_radix(stored, 16)
+            .map_err(|_| format!("invalid checksum '{}'", stored))?;
+        if stored != board.checksum() {
+            return Err("checksum mismatch: the save file was modified".to_string());
+        }
+        Ok(board)
+    }
+
     // === Testing Helpers ===
 
     /// Allows tests to set a cell value directly.
     pub fn set_cell(&mut self, row: usize, col: usize, cell: Cell) {
-        if let Some(r) = self.cells.get_mut(row) {
-            if let Some(c) = r.get_mut(col) {
-                *c = cell;
-            }
+        if self.in_bounds(row, col) {
+            self.cells[row][col] = cell;
         }
     }
 
     /// Allows tests to set a cell state directly.
     pub fn set_cell_state(&mut self, row: usize, col: usize, state: CellState) {
-        if let Some(r) = self.states.get_mut(row) {
-            if let Some(s) = r.get_mut(col) {
-                *s = state;
-            }
+        if self.in_bounds(row, col) {
+            self.states[row][col] = state;
         }
     }
 
@@ -315,3 +1993,17 @@ impl Board {
         self.mine_positions.is_empty()
     }
 }
+
+/// Applies the subset rule to two (cells, mines) constraints: if `small`'s cells are a strict
+/// subset of `large`'s, the remaining cells of `large` hold the difference in mines.
+/// Returns that derived constraint, or None if the rule does not apply.
+fn subset_difference(
+    small: &(BTreeSet<(usize, usize)>, usize),
+    large: &(BTreeSet<(usize, usize)>, usize),
+) -> Option<(BTreeSet<(usize, usize)>, usize)> {
+    if small.0.len() >= large.0.len() || large.1 < small.1 || !small.0.is_subset(&large.0) {
+        return None;
+    }
+    let extra = large.0.difference(&small.0).copied().collect();
+    Some((extra, large.1 - small.1))
+}
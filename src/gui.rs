@@ -7,31 +7,33 @@
 //!
 //! Key responsibilities:
 //! - Holds the main game state and board
-//! - Loads assets (textures)
+//! - Loads assets (textures and sounds, with placeholders for missing files)
 //! - Runs the main async game loop (`run`)
 //! - Delegates drawing, input, and animation to submodules
 //! - Handles game reset and state transitions
 
 use crate::board::*;
+use crate::gui_assets::*;
+use crate::gui_popup::CustomBoardDialog;
+use crate::gui_ui::today_utc;
+use crate::gui_skin::*;
 use crate::particle::*;
+use crate::replay::Replay;
+use crate::solver;
 use macroquad::audio::*;
 use macroquad::prelude::*;
+use std::collections::{HashMap, VecDeque};
 
-// --- Asset file paths ---
-const FLAG_TEXTURE_PATH: &str = "assets/flag.png"; // Flag icon
-const MINE_TEXTURE_PATH: &str = "assets/blast.png"; // Mine icon
-const CLOCK_TEXTURE_PATH: &str = "assets/clock.png"; // Clock icon
-const MUTE_TEXTURE_PATH: &str = "assets/mute.png"; // Mute/sound icon
-const SYNCHRONIZE_TEXTURE_PATH: &str = "assets/synchronize.png"; // New game/restart icon
-const VOLUME_TEXTURE_PATH: &str = "assets/volume.png"; // Volume/sound-on icon
-const FLAG_SOUND_PATH: &str = "assets/flag.wav";
-const BOMB_SOUND_PATH: &str = "assets/bomb.wav";
-const REMOVE_FLAG_SOUND_PATH: &str = "assets/remove_flag.wav";
-const FLIP_SOUND_PATH: &str = "assets/flip.wav";
-const WAVE_SOUND_PATH: &str = "assets/wave.wav";
-const MISTAKE_SOUND_PATH: &str = "assets/mistake.wav";
-const GAME_OVER_SOUND_PATH: &str = "assets/game_over.wav";
-const WIN_SOUND_PATH: &str = "assets/win.wav";
+const DEFAULT_GRID_LINE_WIDTH: f32 = 2.0; // Thickness of the cell borders (0.0 = no borders)
+const GRID_LINE_WIDTHS: [f32; 3] = [DEFAULT_GRID_LINE_WIDTH, 1.0, 0.0]; // Cycled with the V key
+const DEFAULT_DOUBLE_CLICK_WINDOW: f64 = 0.3; // Seconds between the clicks of a double-click
+const COUNTDOWN_SECONDS: f32 = 3.0; // Length of the "3-2-1" countdown before a game
+const DEFAULT_MOVE_TIME_LIMIT: f64 = 10.0; // Seconds per move when time attack is turned on
+const AUTO_SOLVE_HOLD_DELAY: f32 = 0.4; // Seconds S must be held before the solver repeats
+const AUTO_SOLVE_REPEAT_INTERVAL: f32 = 0.12; // Seconds between solver moves while S is held
+const SAFE_BUDGET_CHOICES: [usize; 4] = [0, 1, 3, 5]; // Lives per game, cycled with F11
+const FLAG_COUNT_TWEEN_DURATION: f32 = 0.2; // Seconds for the flags-left counter to count over
+const EDITOR_LAYOUT_PATH: &str = "layout.mine"; // Level editor save (F8) and load (F9) file
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Represents the current state of the game.
@@ -44,6 +46,78 @@ pub enum GameState {
     Lost,       // Game is lost (for loss popup)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Controls the order in which the remaining mines are revealed after game over.
+/// `Nearest` and `Farthest` are measured by Manhattan distance from the fatal click.
+pub enum RevealOrder {
+    Random,   // Shuffled order (default)
+    Nearest,  // Closest mines first, spreading outward from the click
+    Farthest, // Farthest mines first, closing in on the click
+    RowMajor, // Top-left to bottom-right, row by row
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A request that abandons the current game (see `MinesweeperApp::request_new_game`).
+pub enum NewGameRequest {
+    Next,            // The "New Game" button: the next game (or a retry of the same layout)
+    Random,          // A new random layout, even when retrying the same board
+    Size(BoardSize), // A new game at another board size
+    Daily,           // Today's daily puzzle
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Controls the order in which a flood fill's cells are revealed by the wave animation,
+/// relative to the clicked cell.
+pub enum WaveOrder {
+    Radial,      // Rings spreading out from the click (default)
+    Spiral,      // Rings swept around the click, like a spiral
+    LeftToRight, // Columns from the leftmost revealed cell to the rightmost
+}
+
+impl WaveOrder {
+    /// Returns the next order in Radial -> Spiral -> LeftToRight order, wrapping back to Radial.
+    pub fn next(self) -> WaveOrder {
+        match self {
+            WaveOrder::Radial => WaveOrder::Spiral,
+            WaveOrder::Spiral => WaveOrder::LeftToRight,
+            WaveOrder::LeftToRight => WaveOrder::Radial,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Controls how precisely the top-bar timer is shown.
+pub enum TimerPrecision {
+    Seconds, // Whole seconds, e.g. "01:23" (default)
+    Tenths,  // Tenths of a second, e.g. "01:23.4"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Selects what the counter next to the top-bar flag icon shows.
+pub enum CounterMode {
+    Flags, // Flags left to place (mines minus flags) (default)
+    Cells, // Cells not uncovered yet
+    Both,  // Both, as "flags|cells"
+}
+
+impl CounterMode {
+    /// Returns the next mode in Flags -> Cells -> Both order, wrapping back to Flags.
+    pub fn next(self) -> CounterMode {
+        match self {
+            CounterMode::Flags => CounterMode::Cells,
+            CounterMode::Cells => CounterMode::Both,
+            CounterMode::Both => CounterMode::Flags,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Selects the colors used to draw cell numbers.
+pub enum NumberPalette {
+    Classic,  // Classic Minesweeper colors (blue, green, red, ...) (default)
+    OkabeIto, // Colorblind-safe Okabe-Ito colors
+}
+
 /// The main application struct for the Minesweeper game.
 /// Holds the board, game state, and all UI/animation state.
 pub struct MinesweeperApp {
@@ -52,28 +126,116 @@ pub struct MinesweeperApp {
 
     // --- Booleans (game state flags) ---
     state: GameState, // The current game state
+    editor_mode: bool, // Whether left-click toggles mines (level editor) instead of playing
+    forced_guess: bool, // Whether the current position needs a guess (no logical move left)
+    hint: Option<(usize, usize)>, // Safe cell suggested by the last hint request
+    last_reveal_cell: Option<(usize, usize)>, // Cell of the player's last reveal click
+    safe_reveals_used: u32, // Safe cells revealed by the emergency button this game
+    wins_with_help: u32, // Wins this session that used the emergency safe reveal
 
     // --- Board size selection state ---
     board_size: BoardSize, // Current selected board size (Small, Medium, Large)
     show_size_popup: bool, // Whether the board size dropdown is visible
     ignore_next_size_popup_click: bool, // Flag to ignore the next click (prevents dropdown reopening)
     cell_size: f32,                     // Size of each cell in pixels
+    dpi_scale: f32, // Physical pixels per logical pixel (2.0 on most retina displays)
 
     sound: bool, // Whether sound is muted
+    skins: Vec<String>, // Names of all available skins (default first)
+    skin_name: String,  // Name of the selected skin
+    show_pressure_overlay: bool, // Whether covered cells are tinted by visible number pressure
+    show_probability_overlay: bool, // Whether covered cells show their estimated mine probability
+    mine_probabilities: HashMap<(usize, usize), f32>, // Cached solver estimates (updated after each move)
+    dim_satisfied_numbers: bool, // Whether numbers with all their flags placed are drawn faded
+    grid_line_width: f32, // Thickness of the cell borders; 0.0 draws no borders
+    win_wave: bool, // Whether a win ripples out from the board center
+    loss_flash_enabled: bool, // Whether hitting a mine flashes the window red
+    flag_limit: bool, // Whether flagging is refused once there are as many flags as mines
+    zen_mode: bool, // Relaxed play: no timer, and hitting a mine flags it instead of losing
+    safe_budget_size: usize, // Lives per game: mine hits forgiven before a loss (0 = off)
+    safe_budget: usize, // Lives left in this game
+    flag_mode: bool, // Whether left-clicks place flags instead of uncovering (for trackpads)
+    number_palette: NumberPalette, // Colors used to draw cell numbers
+    show_number_legend: bool,      // Whether the number color legend is shown
+    screen_reader: bool, // Whether accessibility announcements are printed
+    reveal_order: RevealOrder, // Order of the game-over mine reveal
+    wave_order: WaveOrder, // Order in which flood-filled cells are revealed
+
+    seed: Option<u64>, // RNG seed of the current game (captured on first click)
+    replay: Option<Replay>, // Moves of the current seeded game (None for unseeded layouts)
+    auto_open: bool,   // Whether reset_game performs a safe opening reveal automatically
+    auto_open_cell: Option<(usize, usize)>, // Cell used for the auto-open (None = board center)
+    opening_assist: bool, // Whether the first click opens the size's preferred cell instead
+    preferred_openings: HashMap<BoardSize, (usize, usize)>, // Per-size opening cells (default: center)
+    first_click_grace: bool,   // Beginner setting: the first reveal after a reset never hits a mine
+    first_reveal_pending: bool, // Whether the player hasn't revealed a cell since the last reset
+    retry_same_board: bool, // Whether "New Game" replays the last seeded layout instead of a new one
+    countdown_enabled: bool, // Whether each game starts with a "3-2-1" countdown
+    confirm_new_game: bool, // Whether "New Game" asks for confirmation while a game is in progress
+    pending_new_game: Option<NewGameRequest>, // What the "start a new game?" popup would start
+    countdown: Option<f32>, // Seconds left in the start countdown (None = not counting down)
+
+    // --- Marathon mode state ---
+    marathon: bool,           // Whether wins chain straight into a new board
+    marathon_count: u32,      // Boards cleared in the current marathon
+    marathon_total_time: f64, // Cumulative winning time (seconds) in the current marathon
+
+    // --- Win streak state ---
+    win_streak: u32, // Wins in a row this session (a loss resets it)
+    streak_banner: Option<(u32, f64)>, // Milestone being celebrated and when its banner appeared
+    fanfare_pending: bool, // Whether the milestone fanfare should play on this frame
 
     // --- Timers and time tracking ---
     start_time: f64,       // Time when the game started (seconds since epoch)
+    move_time_limit: Option<f64>, // Time attack: seconds allowed per move (None = off)
+    last_move_time: f64,          // Time of the last reveal/flag (0.0 = no move yet)
+    move_clock_paused_at: Option<f64>, // When a modal overlay paused the move countdown
     end_time: Option<f64>, // Time when the player won (if any)
+    timer_precision: TimerPrecision, // How precisely the timer is shown
+    counter_mode: CounterMode, // What the counter next to the flag icon shows
+    timer_text: String,      // Timer text shown on the current frame
+    timer_prev_text: String, // Timer text before the last change (rolls out of view)
+    timer_roll: f32,         // Seconds since the timer text last changed
+    displayed_flags: f32,    // Flags-left value the counter shows, counting toward the real one
+    flags_tween_speed: f32,  // Counter steps per second while it counts
 
     // --- Animation and effect state ---
     pop_timers: Vec<Vec<Option<f32>>>, // 2D array of timers for pop animations for each cell
     wave_timers: Vec<Vec<Option<f32>>>, // 2D array of timers for wave/flood-fill animations
+    flag_timers: Vec<Vec<Option<f32>>>, // 2D array of timers for flag-planting animations
     particles: Vec<Particle>, // List of all active particle effects (confetti, explosions, etc.)
     shockwaves: Vec<(f32, f32, f32)>, // List of active shockwave effects (x, y, timer)
+    loss_flash: Option<f32>, // Seconds since the red loss flash started (None = no flash)
+    flag_refusal: Option<(usize, usize, f32)>, // Cell shaking after a refused flag, and seconds since
+    reveal_pulse: bool, // Whether the last revealed cell gets a brief pulsing outline
+    last_revealed: Option<(usize, usize, f32)>, // Cell with the reveal pulse, and seconds since
+    recent_sound_times: VecDeque<f64>, // When recent effect sounds played, for ducking bursts
+    instant_numbers: bool, // Whether numbers show during the reveal pop instead of after it
 
     // --- Reveal and flag state ---
     mine_reveal_queue: Vec<(usize, usize, bool)>, // Queue of mines to reveal (for animated mine reveal)
+    mine_reveal_delay: f32, // Seconds between mine reveals (shorter for longer queues)
+    skip_mine_reveal: bool, // Whether the game-over mine reveal shows everything at once
     wrong_flags: Vec<(usize, usize)>, // List of wrongly flagged cells (for highlighting mistakes)
+
+    // --- Debug overlay state ---
+    show_debug_overlay: bool,   // Whether the F2 performance overlay is visible
+    frame_times: VecDeque<f32>, // Recent frame times (seconds) for the rolling average
+
+    // --- Asset state ---
+    asset_error: Option<String>, // Message listing assets that failed to load (None once dismissed)
+    screenshot_pending: bool, // Whether the frame being drawn should be saved as a PNG
+    safe_peek: Option<Board>, // Copy of the board with every safe cell shown (the peek)
+    replay_view: Option<(usize, Board)>, // Replay step shown after the game, and its board
+    screenshot_message: Option<String>, // Result of the last screenshot export (None once dismissed)
+    editor_message: Option<String>, // Result of the last editor save or load (None once dismissed)
+
+    // --- Input state ---
+    click_queue: VecDeque<(usize, usize, MouseButton)>, // Board clicks waiting to be processed (row, col, button)
+    double_click_chord: bool, // Whether chording needs a double-click instead of a single click
+    double_click_window: f64, // Longest gap (seconds) between the two clicks of a double-click
+    last_click: Option<(f64, usize, usize)>, // Time and cell of the last click on an uncovered cell
+    custom_dialog: Option<CustomBoardDialog>, // The "Custom..." board dialog, while it is open
 }
 
 impl MinesweeperApp {
@@ -97,6 +259,412 @@ impl MinesweeperApp {
         self.state = state;
     }
 
+    /// Returns whether the level editor is active.
+    pub fn editor_mode(&self) -> bool {
+        self.editor_mode
+    }
+
+    /// Sets whether the level editor is active.
+    pub fn set_editor_mode(&mut self, value: bool) {
+        self.editor_mode = value;
+    }
+
+    /// Returns the replay of the current game, if its mines were placed from a seed.
+    pub fn replay(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
+
+    /// Sets the replay of the current game (started when seeded mines are placed).
+    pub fn set_replay(&mut self, replay: Option<Replay>) {
+        self.replay = replay;
+    }
+
+    /// Applies a player move to the board and, if it changed anything, records it in the replay.
+    pub fn play_move(&mut self, mv: Move) -> MoveResult {
+        let (row, col) = mv.cell();
+        let before = self.board.cell_state(row, col);
+        let result = self.board.apply_move(mv);
+        let changed = result != MoveResult::default() || self.board.cell_state(row, col) != before;
+        if changed {
+            if let Some(replay) = &mut self.replay {
+                replay.push(mv);
+            }
+        }
+        result
+    }
+
+    /// Reveals a cell (see `Board::reveal`) and records the reveal in the replay if it
+    /// uncovered anything.
+    pub fn play_reveal(&mut self, row: usize, col: usize) -> RevealOutcome {
+        let outcome = self.board.reveal(row, col);
+        let changed = !matches!(outcome, RevealOutcome::AlreadyRevealed | RevealOutcome::Flagged);
+        if let (true, Some(replay)) = (changed, &mut self.replay) {
+            replay.push(Move::Reveal(row, col));
+        }
+        outcome
+    }
+
+    /// Toggles a cell through the right-click cycle (see `Board::toggle_flag`) and records the
+    /// change in the replay. Returns None (and changes nothing) if a new flag is refused by
+    /// the flag limit.
+    pub fn play_flag_toggle(&mut self, row: usize, col: usize) -> Option<FlagChange> {
+        let limit_reached = self.flag_limit && self.board.flag_count() >= self.board.mines();
+        if limit_reached && self.board.cell_state(row, col) == Some(CellState::Covered) {
+            return None;
+        }
+        let change = self.board.toggle_flag(row, col);
+        if let (Some(replay), Some(mv)) = (&mut self.replay, change.as_move(row, col)) {
+            replay.push(mv);
+        }
+        Some(change)
+    }
+
+    /// Returns the cell of the player's last reveal click, if any this game.
+    pub fn last_reveal_cell(&self) -> Option<(usize, usize)> {
+        self.last_reveal_cell
+    }
+
+    /// Sets the cell of the player's last reveal click.
+    pub fn set_last_reveal_cell(&mut self, cell: Option<(usize, usize)>) {
+        self.last_reveal_cell = cell;
+    }
+
+    /// Returns how many safe cells the emergency button revealed this game.
+    pub fn safe_reveals_used(&self) -> u32 {
+        self.safe_reveals_used
+    }
+
+    /// Sets how many safe cells the emergency button revealed this game.
+    pub fn set_safe_reveals_used(&mut self, count: u32) {
+        self.safe_reveals_used = count;
+    }
+
+    /// Returns how many wins this session used the emergency safe reveal.
+    pub fn wins_with_help(&self) -> u32 {
+        self.wins_with_help
+    }
+
+    /// Sets how many wins this session used the emergency safe reveal.
+    pub fn set_wins_with_help(&mut self, count: u32) {
+        self.wins_with_help = count;
+    }
+
+    /// Returns the RNG seed of the current game, if mines have been placed from one.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Sets the RNG seed of the current game.
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Returns whether the safe auto-open on start is enabled.
+    pub fn auto_open(&self) -> bool {
+        self.auto_open
+    }
+
+    /// Enables or disables the safe auto-open on start (takes effect on the next reset).
+    pub fn set_auto_open(&mut self, value: bool) {
+        self.auto_open = value;
+    }
+
+    /// Returns the cell used for the auto-open, if one was chosen (None means the board center).
+    pub fn auto_open_cell(&self) -> Option<(usize, usize)> {
+        self.auto_open_cell
+    }
+
+    /// Sets the cell used for the auto-open (None means the board center).
+    pub fn set_auto_open_cell(&mut self, cell: Option<(usize, usize)>) {
+        self.auto_open_cell = cell;
+    }
+
+    /// Returns whether the opening assist is on (the first click opens the preferred cell).
+    pub fn opening_assist(&self) -> bool {
+        self.opening_assist
+    }
+
+    /// Enables or disables the opening assist: when on, the first click of a game, wherever it
+    /// lands, opens the current size's preferred cell (see `preferred_opening`) instead.
+    pub fn set_opening_assist(&mut self, value: bool) {
+        self.opening_assist = value;
+    }
+
+    /// Returns the preferred opening cells set per board size.
+    pub fn preferred_openings(&self) -> &HashMap<BoardSize, (usize, usize)> {
+        &self.preferred_openings
+    }
+
+    /// Sets the preferred opening cell for a board size (None goes back to the board center).
+    pub fn set_preferred_opening(&mut self, size: BoardSize, cell: Option<(usize, usize)>) {
+        match cell {
+            Some(cell) => self.preferred_openings.insert(size, cell),
+            None => self.preferred_openings.remove(&size),
+        };
+    }
+
+    /// Returns whether the beginner first-click grace is on.
+    pub fn first_click_grace(&self) -> bool {
+        self.first_click_grace
+    }
+
+    /// Enables or disables the beginner first-click grace: the first reveal after a reset
+    /// always opens an empty region, even if mines were already placed (e.g. by the auto-open).
+    pub fn set_first_click_grace(&mut self, value: bool) {
+        self.first_click_grace = value;
+    }
+
+    /// Returns whether the player hasn't revealed a cell since the last reset.
+    pub fn first_reveal_pending(&self) -> bool {
+        self.first_reveal_pending
+    }
+
+    /// Sets whether the player hasn't revealed a cell since the last reset.
+    pub fn set_first_reveal_pending(&mut self, value: bool) {
+        self.first_reveal_pending = value;
+    }
+
+    /// Returns the number of games won in a row this session.
+    pub fn win_streak(&self) -> u32 {
+        self.win_streak
+    }
+
+    /// Sets the number of games won in a row.
+    pub fn set_win_streak(&mut self, streak: u32) {
+        self.win_streak = streak;
+    }
+
+    /// Returns the milestone streak whose banner is showing and when it appeared, if any.
+    pub fn streak_banner(&self) -> Option<(u32, f64)> {
+        self.streak_banner
+    }
+
+    /// Sets the milestone streak banner.
+    pub fn set_streak_banner(&mut self, banner: Option<(u32, f64)>) {
+        self.streak_banner = banner;
+    }
+
+    /// Returns whether the milestone fanfare is waiting to be played.
+    pub fn fanfare_pending(&self) -> bool {
+        self.fanfare_pending
+    }
+
+    /// Sets whether the milestone fanfare is waiting to be played.
+    pub fn set_fanfare_pending(&mut self, pending: bool) {
+        self.fanfare_pending = pending;
+    }
+
+    /// Returns whether new games start with a countdown.
+    pub fn countdown_enabled(&self) -> bool {
+        self.countdown_enabled
+    }
+
+    /// Sets whether new games start with a countdown (takes effect on the next game).
+    pub fn set_countdown_enabled(&mut self, enabled: bool) {
+        self.countdown_enabled = enabled;
+    }
+
+    /// Returns the seconds left in the start countdown, or None when not counting down.
+    /// Board clicks are ignored and the clock doesn't run until it ends.
+    pub fn countdown(&self) -> Option<f32> {
+        self.countdown
+    }
+
+    /// Sets the seconds left in the start countdown.
+    pub fn set_countdown(&mut self, countdown: Option<f32>) {
+        self.countdown = countdown;
+    }
+
+    /// Returns whether "New Game" replays the current layout (see `new_game`).
+    pub fn retry_same_board(&self) -> bool {
+        self.retry_same_board
+    }
+
+    /// Sets whether "New Game" replays the current layout.
+    pub fn set_retry_same_board(&mut self, retry: bool) {
+        self.retry_same_board = retry;
+    }
+
+    /// Returns whether the current position requires a guess (see `Board::is_forced_guess`).
+    pub fn forced_guess(&self) -> bool {
+        self.forced_guess
+    }
+
+    /// Recomputes whether the current position requires a guess.
+    /// Only a running game can require one. Called after the board changes, not every frame.
+    pub fn update_forced_guess(&mut self) {
+        self.forced_guess = self.state == GameState::Running && self.board.is_forced_guess();
+    }
+
+    /// Returns the display's DPI scale (physical pixels per logical pixel), updated every frame.
+    /// All layout (cell size, positions, mouse coordinates) is in logical pixels; macroquad
+    /// maps them to the full-resolution framebuffer, so only text rasterization uses this.
+    pub fn dpi_scale(&self) -> f32 {
+        self.dpi_scale
+    }
+
+    /// Sets the display's DPI scale (values below 1.0 are treated as 1.0).
+    pub fn set_dpi_scale(&mut self, scale: f32) {
+        self.dpi_scale = scale.max(1.0);
+    }
+
+    /// Returns the font size (in physical pixels) and scale to rasterize text at, so text
+    /// drawn at `font_size` logical pixels stays sharp on high-DPI displays.
+    pub fn sharp_font_params(&self, font_size: f32) -> (u16, f32) {
+        ((font_size * self.dpi_scale).round() as u16, 1.0 / self.dpi_scale)
+    }
+
+    /// Draws text like `draw_text`, but rasterized at the display's full resolution.
+    pub fn draw_text_sharp(
+        &self,
+        text: &str,
+        x: f32,
+        y: f32,
+        font_size: f32,
+        color: Color,
+    ) -> TextDimensions {
+        let (font_size, font_scale) = self.sharp_font_params(font_size);
+        draw_text_ex(
+            text,
+            x,
+            y,
+            TextParams {
+                font_size,
+                font_scale,
+                color,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Returns the cell suggested by the last hint request, if any.
+    pub fn hint(&self) -> Option<(usize, usize)> {
+        self.hint
+    }
+
+    /// Suggests the most informative cell that is proven safe from the visible numbers:
+    /// the one next to the highest sum of numbers (see `Board::adjacent_number_sum`),
+    /// with ties going to the first cell in row-major order. Returns None (and clears the
+    /// hint) if the game isn't running or no cell can be proven safe.
+    pub fn request_hint(&mut self) -> Option<(usize, usize)> {
+        self.hint = if self.state == GameState::Running {
+            self.board
+                .deduce_safe_cells()
+                .into_iter()
+                .min_by_key(|&(row, col)| {
+                    (std::cmp::Reverse(self.board.adjacent_number_sum(row, col)), row, col)
+                })
+        } else {
+            None
+        };
+        self.hint
+    }
+
+    /// Returns the cached mine probability estimates (see `solver::mine_probabilities`).
+    /// Empty unless the probability overlay is on and a game is running.
+    pub fn mine_probabilities(&self) -> &HashMap<(usize, usize), f32> {
+        &self.mine_probabilities
+    }
+
+    /// Recomputes the mine probability estimates shown by the probability overlay.
+    /// Skipped while the overlay is hidden; called after the board changes, not every frame.
+    pub fn update_mine_probabilities(&mut self) {
+        let shown = self.show_probability_overlay && self.state == GameState::Running;
+        self.mine_probabilities = if shown {
+            solver::mine_probabilities(&self.board)
+        } else {
+            HashMap::new()
+        };
+    }
+
+    /// Returns the time attack limit in seconds per move (None when time attack is off).
+    pub fn move_time_limit(&self) -> Option<f64> {
+        self.move_time_limit
+    }
+
+    /// Sets the time attack limit in seconds per move (None turns time attack off).
+    pub fn set_move_time_limit(&mut self, limit: Option<f64>) {
+        self.move_time_limit = limit;
+    }
+
+    /// Returns the time of the last reveal or flag (0.0 if no move was made yet).
+    pub fn last_move_time(&self) -> f64 {
+        self.last_move_time
+    }
+
+    /// Records that the player made a move (reveal or flag) at time `now`.
+    pub fn record_move(&mut self, now: f64) {
+        self.last_move_time = now;
+    }
+
+    /// Returns the seconds left before the next move is required, clamped at 0.0
+    /// (None unless time attack is on and the game is running with a move recorded).
+    /// While the countdown is paused, this is the time that was left when it paused.
+    pub fn move_time_remaining(&self, now: f64) -> Option<f64> {
+        let limit = self.move_time_limit?;
+        if self.state != GameState::Running || self.last_move_time == 0.0 {
+            return None;
+        }
+        let now = self.move_clock_paused_at.unwrap_or(now);
+        Some((limit - (now - self.last_move_time)).max(0.0))
+    }
+
+    /// Ends a running time attack game as lost once the per-move limit has elapsed at `now`
+    /// (see `lose_game`; the mine reveal spreads from the last revealed cell, or the center).
+    /// A running game with no recorded move (e.g. after auto-open) starts its countdown here.
+    /// The countdown is paused while a modal overlay is open and resumes where it left off.
+    /// Returns true if the game was just lost.
+    pub fn check_move_time_limit(&mut self, now: f64) -> bool {
+        if self.move_time_limit.is_none() || self.state != GameState::Running || self.zen_mode {
+            return false;
+        }
+        if self.last_move_time == 0.0 {
+            self.last_move_time = now;
+        }
+        if self.modal_active() {
+            self.move_clock_paused_at.get_or_insert(now);
+            return false;
+        }
+        if let Some(paused) = self.move_clock_paused_at.take() {
+            self.last_move_time += now - paused;
+        }
+        if self.move_time_remaining(now) == Some(0.0) {
+            let center = (self.board.height() / 2, self.board.width() / 2);
+            let (row, col) = self.last_reveal_cell.unwrap_or(center);
+            self.lose_game(row, col, now);
+            return true;
+        }
+        false
+    }
+
+    /// Returns whether marathon mode is on.
+    pub fn marathon(&self) -> bool {
+        self.marathon
+    }
+
+    /// Turns marathon mode on or off. The counters start over either way.
+    pub fn set_marathon(&mut self, value: bool) {
+        self.marathon = value;
+        self.marathon_count = 0;
+        self.marathon_total_time = 0.0;
+    }
+
+    /// Returns the number of boards cleared in the current marathon.
+    pub fn marathon_count(&self) -> u32 {
+        self.marathon_count
+    }
+
+    /// Returns the cumulative winning time (seconds) of the current marathon.
+    pub fn marathon_total_time(&self) -> f64 {
+        self.marathon_total_time
+    }
+
+    /// Records a cleared board in the current marathon.
+    pub fn record_marathon_win(&mut self, elapsed: f64) {
+        self.marathon_count += 1;
+        self.marathon_total_time += elapsed;
+    }
+
     /// Returns the start time (read-only).
     pub fn start_time(&self) -> f64 {
         self.start_time
@@ -117,6 +685,73 @@ impl MinesweeperApp {
         self.end_time = time;
     }
 
+    /// Returns the timer precision.
+    pub fn timer_precision(&self) -> TimerPrecision {
+        self.timer_precision
+    }
+
+    /// Sets the timer precision.
+    pub fn set_timer_precision(&mut self, precision: TimerPrecision) {
+        self.timer_precision = precision;
+    }
+
+    /// Returns what the counter next to the flag icon shows.
+    pub fn counter_mode(&self) -> CounterMode {
+        self.counter_mode
+    }
+
+    /// Sets what the counter next to the flag icon shows.
+    pub fn set_counter_mode(&mut self, mode: CounterMode) {
+        self.counter_mode = mode;
+    }
+
+    /// Returns the timer text shown on the current frame (read-only).
+    pub fn timer_text(&self) -> &str {
+        &self.timer_text
+    }
+
+    /// Returns the timer text before the last change (read-only).
+    pub fn timer_prev_text(&self) -> &str {
+        &self.timer_prev_text
+    }
+
+    /// Returns the seconds since the timer text last changed.
+    pub fn timer_roll(&self) -> f32 {
+        self.timer_roll
+    }
+
+    /// Records the timer text for this frame. When it changes, the old text is kept
+    /// so changed digits can roll out while the new ones roll in.
+    pub fn update_timer_text(&mut self, text: String, dt: f32) {
+        if text != self.timer_text {
+            self.timer_prev_text = std::mem::replace(&mut self.timer_text, text);
+            self.timer_roll = 0.0;
+        } else {
+            self.timer_roll += dt;
+        }
+    }
+
+    /// Returns the flags-left value the counter shows; it trails the real value while it counts.
+    pub fn displayed_flags(&self) -> f32 {
+        self.displayed_flags
+    }
+
+    /// Moves the counter's flags-left value `dt` seconds toward the real one. A change counts
+    /// over in `FLAG_COUNT_TWEEN_DURATION` seconds (a change mid-count speeds it up so it still
+    /// ends in time) and settles exactly on the real value.
+    pub fn advance_displayed_flags(&mut self, dt: f32) {
+        let target = self.board.remaining_mines() as f32;
+        let distance = (target - self.displayed_flags).abs();
+        self.flags_tween_speed = self.flags_tween_speed.max(distance / FLAG_COUNT_TWEEN_DURATION);
+        let step = self.flags_tween_speed * dt;
+        if step >= distance {
+            self.displayed_flags = target;
+            self.flags_tween_speed = 0.0;
+        } else {
+            self.displayed_flags += step.copysign(target - self.displayed_flags);
+        }
+    }
+
     /// Returns a reference to the pop_timers (read-only).
     pub fn pop_timers(&self) -> &Vec<Vec<Option<f32>>> {
         &self.pop_timers
@@ -127,11 +762,26 @@ impl MinesweeperApp {
         &mut self.pop_timers
     }
 
+    /// Returns a reference to the wave_timers (read-only).
+    pub fn wave_timers(&self) -> &Vec<Vec<Option<f32>>> {
+        &self.wave_timers
+    }
+
     /// Returns a mutable reference to the wave_timers (for modification).
     pub fn wave_timers_mut(&mut self) -> &mut Vec<Vec<Option<f32>>> {
         &mut self.wave_timers
     }
 
+    /// Returns a reference to the flag_timers (read-only).
+    pub fn flag_timers(&self) -> &Vec<Vec<Option<f32>>> {
+        &self.flag_timers
+    }
+
+    /// Returns a mutable reference to the flag_timers (for modification).
+    pub fn flag_timers_mut(&mut self) -> &mut Vec<Vec<Option<f32>>> {
+        &mut self.flag_timers
+    }
+
     /// Returns a reference to the particles (read-only).
     pub fn particles(&self) -> &Vec<Particle> {
         &self.particles
@@ -162,6 +812,37 @@ impl MinesweeperApp {
         &mut self.mine_reveal_queue
     }
 
+    /// Returns the seconds between mine reveals, set when the reveal queue is built.
+    pub fn mine_reveal_delay(&self) -> f32 {
+        self.mine_reveal_delay
+    }
+
+    /// Sets the seconds between mine reveals.
+    pub fn set_mine_reveal_delay(&mut self, delay: f32) {
+        self.mine_reveal_delay = delay;
+    }
+
+    /// Returns whether the game-over mine reveal is skipped (everything shown at once).
+    pub fn skip_mine_reveal(&self) -> bool {
+        self.skip_mine_reveal
+    }
+
+    /// Sets whether the game-over mine reveal is skipped (everything shown at once).
+    pub fn set_skip_mine_reveal(&mut self, skip: bool) {
+        self.skip_mine_reveal = skip;
+    }
+
+    /// Returns whether a revealed number is drawn throughout its pop animation
+    /// (otherwise it appears once the pop finishes).
+    pub fn instant_numbers(&self) -> bool {
+        self.instant_numbers
+    }
+
+    /// Sets whether a revealed number is drawn throughout its pop animation.
+    pub fn set_instant_numbers(&mut self, value: bool) {
+        self.instant_numbers = value;
+    }
+
     /// Returns a reference to the wrongly flagged cells (read-only).
     pub fn wrong_flags(&self) -> &Vec<(usize, usize)> {
         &self.wrong_flags
@@ -217,6 +898,380 @@ impl MinesweeperApp {
         self.sound = value;
     }
 
+    /// Returns the order used for the game-over mine reveal.
+    pub fn reveal_order(&self) -> RevealOrder {
+        self.reveal_order
+    }
+
+    /// Sets the order used for the game-over mine reveal.
+    pub fn set_reveal_order(&mut self, order: RevealOrder) {
+        self.reveal_order = order;
+    }
+
+    /// Returns the order of the flood-fill reveal wave.
+    pub fn wave_order(&self) -> WaveOrder {
+        self.wave_order
+    }
+
+    /// Sets the order of the flood-fill reveal wave.
+    pub fn set_wave_order(&mut self, order: WaveOrder) {
+        self.wave_order = order;
+    }
+
+    /// Returns a reference to the queued board clicks (read-only).
+    pub fn click_queue(&self) -> &VecDeque<(usize, usize, MouseButton)> {
+        &self.click_queue
+    }
+
+    /// Returns a mutable reference to the queued board clicks (for modification).
+    pub fn click_queue_mut(&mut self) -> &mut VecDeque<(usize, usize, MouseButton)> {
+        &mut self.click_queue
+    }
+
+    /// Returns the custom board dialog, if it is open.
+    pub fn custom_dialog(&self) -> Option<&CustomBoardDialog> {
+        self.custom_dialog.as_ref()
+    }
+
+    /// Returns a mutable reference to the custom board dialog, if it is open.
+    pub fn custom_dialog_mut(&mut self) -> Option<&mut CustomBoardDialog> {
+        self.custom_dialog.as_mut()
+    }
+
+    /// Opens (Some) or closes (None) the custom board dialog.
+    pub fn set_custom_dialog(&mut self, dialog: Option<CustomBoardDialog>) {
+        self.custom_dialog = dialog;
+    }
+
+    /// Returns whether chording needs a double-click on the number instead of a single click.
+    pub fn double_click_chord(&self) -> bool {
+        self.double_click_chord
+    }
+
+    /// Sets whether chording needs a double-click on the number instead of a single click.
+    pub fn set_double_click_chord(&mut self, value: bool) {
+        self.double_click_chord = value;
+        self.last_click = None;
+    }
+
+    /// Returns the longest gap (seconds) between the two clicks of a chording double-click.
+    pub fn double_click_window(&self) -> f64 {
+        self.double_click_window
+    }
+
+    /// Sets the longest gap (seconds) between the two clicks of a chording double-click.
+    pub fn set_double_click_window(&mut self, seconds: f64) {
+        self.double_click_window = seconds.max(0.0);
+    }
+
+    /// Returns the time and cell of the last click on an uncovered cell, if any.
+    pub fn last_click(&self) -> Option<(f64, usize, usize)> {
+        self.last_click
+    }
+
+    /// Sets the time and cell of the last click on an uncovered cell.
+    pub fn set_last_click(&mut self, click: Option<(f64, usize, usize)>) {
+        self.last_click = click;
+    }
+
+    /// Returns whether the debug overlay is shown.
+    pub fn show_debug_overlay(&self) -> bool {
+        self.show_debug_overlay
+    }
+
+    /// Sets whether the debug overlay is shown.
+    pub fn set_show_debug_overlay(&mut self, show: bool) {
+        self.show_debug_overlay = show;
+    }
+
+    /// Returns the missing-assets message, if it is still shown.
+    pub fn asset_error(&self) -> Option<&str> {
+        self.asset_error.as_deref()
+    }
+
+    /// Sets the missing-assets message (None dismisses it).
+    pub fn set_asset_error(&mut self, message: Option<String>) {
+        self.asset_error = message;
+    }
+
+    /// Returns whether the current frame will be saved as a screenshot.
+    pub fn screenshot_pending(&self) -> bool {
+        self.screenshot_pending
+    }
+
+    /// Sets whether the current frame will be saved as a screenshot.
+    pub fn set_screenshot_pending(&mut self, pending: bool) {
+        self.screenshot_pending = pending;
+    }
+
+    /// Returns whether the safe-cell peek is showing (drawn instead of the real board).
+    pub fn safe_peek_active(&self) -> bool {
+        self.safe_peek.is_some()
+    }
+
+    /// Returns the board copy shown by the safe-cell peek, if it is showing (read-only).
+    pub fn safe_peek(&self) -> Option<&Board> {
+        self.safe_peek.as_ref()
+    }
+
+    /// Sets the board copy shown by the safe-cell peek (None ends the peek).
+    pub fn set_safe_peek(&mut self, peek: Option<Board>) {
+        self.safe_peek = peek;
+    }
+
+    /// Returns the replay step being viewed and the board after it, if the replay is being
+    /// stepped through (drawn instead of the real board).
+    pub fn replay_view(&self) -> Option<&(usize, Board)> {
+        self.replay_view.as_ref()
+    }
+
+    /// Sets the replay step being viewed and its board (None goes back to the real board).
+    pub fn set_replay_view(&mut self, view: Option<(usize, Board)>) {
+        self.replay_view = view;
+    }
+
+    /// Returns the result of the last screenshot export, if it hasn't been dismissed.
+    pub fn screenshot_message(&self) -> Option<&str> {
+        self.screenshot_message.as_deref()
+    }
+
+    /// Sets the result of the last screenshot export.
+    pub fn set_screenshot_message(&mut self, message: Option<String>) {
+        self.screenshot_message = message;
+    }
+
+    /// Returns a reference to the recent frame times (read-only).
+    pub fn frame_times(&self) -> &VecDeque<f32> {
+        &self.frame_times
+    }
+
+    /// Returns a mutable reference to the recent frame times (for modification).
+    pub fn frame_times_mut(&mut self) -> &mut VecDeque<f32> {
+        &mut self.frame_times
+    }
+
+    /// Returns whether the pressure heatmap overlay is shown.
+    pub fn show_pressure_overlay(&self) -> bool {
+        self.show_pressure_overlay
+    }
+
+    /// Sets whether the pressure heatmap overlay is shown.
+    pub fn set_show_pressure_overlay(&mut self, show: bool) {
+        self.show_pressure_overlay = show;
+    }
+
+    /// Returns whether the mine probability overlay is shown.
+    pub fn show_probability_overlay(&self) -> bool {
+        self.show_probability_overlay
+    }
+
+    /// Sets whether the mine probability overlay is shown (and refreshes its estimates).
+    pub fn set_show_probability_overlay(&mut self, show: bool) {
+        self.show_probability_overlay = show;
+        self.update_mine_probabilities();
+    }
+
+    /// Returns whether satisfied numbers are dimmed.
+    pub fn dim_satisfied_numbers(&self) -> bool {
+        self.dim_satisfied_numbers
+    }
+
+    /// Sets whether satisfied numbers are dimmed.
+    pub fn set_dim_satisfied_numbers(&mut self, value: bool) {
+        self.dim_satisfied_numbers = value;
+    }
+
+    /// Returns the thickness of the cell borders (0.0 means no borders).
+    pub fn grid_line_width(&self) -> f32 {
+        self.grid_line_width
+    }
+
+    /// Sets the thickness of the cell borders; 0.0 (or less) turns them off for a flat look.
+    /// Cells stay distinguishable without borders thanks to the checkerboard fill.
+    pub fn set_grid_line_width(&mut self, width: f32) {
+        self.grid_line_width = width.max(0.0);
+    }
+
+    /// Returns whether the celebratory win wave is enabled.
+    pub fn win_wave(&self) -> bool {
+        self.win_wave
+    }
+
+    /// Sets whether the celebratory win wave is enabled.
+    pub fn set_win_wave(&mut self, value: bool) {
+        self.win_wave = value;
+    }
+
+    /// Returns whether hitting a mine flashes the window red.
+    pub fn loss_flash_enabled(&self) -> bool {
+        self.loss_flash_enabled
+    }
+
+    /// Sets whether hitting a mine flashes the window red.
+    pub fn set_loss_flash_enabled(&mut self, value: bool) {
+        self.loss_flash_enabled = value;
+    }
+
+    /// Returns whether the flag limit is on (no more flags than mines).
+    pub fn flag_limit(&self) -> bool {
+        self.flag_limit
+    }
+
+    /// Sets whether the flag limit is on: when on, flagging a covered cell is refused once
+    /// there are as many flags as mines (flags can still be turned into maybe marks).
+    pub fn set_flag_limit(&mut self, value: bool) {
+        self.flag_limit = value;
+    }
+
+    /// Returns whether zen mode is on (no timer, and mines can't be lost to).
+    pub fn zen_mode(&self) -> bool {
+        self.zen_mode
+    }
+
+    /// Sets whether zen mode is on: the timer is hidden, time attack is paused, and a mine
+    /// that is hit gets flagged instead of ending the game (see `absorb_mine`).
+    pub fn set_zen_mode(&mut self, value: bool) {
+        self.zen_mode = value;
+    }
+
+    /// Returns how many mine hits each game forgives (0 = the handicap is off).
+    pub fn safe_budget_size(&self) -> usize {
+        self.safe_budget_size
+    }
+
+    /// Sets how many mine hits each game forgives; takes effect from the next game.
+    pub fn set_safe_budget_size(&mut self, size: usize) {
+        self.safe_budget_size = size;
+    }
+
+    /// Returns how many mine hits this game can still forgive (lives left).
+    pub fn safe_budget(&self) -> usize {
+        self.safe_budget
+    }
+
+    /// Sets how many mine hits this game can still forgive.
+    pub fn set_safe_budget(&mut self, lives: usize) {
+        self.safe_budget = lives;
+    }
+
+    /// Returns the cell shaking after a refused flag and the seconds since, if any.
+    pub fn flag_refusal(&self) -> Option<(usize, usize, f32)> {
+        self.flag_refusal
+    }
+
+    /// Sets the cell shaking after a refused flag (None stops the shake).
+    pub fn set_flag_refusal(&mut self, value: Option<(usize, usize, f32)>) {
+        self.flag_refusal = value;
+    }
+
+    /// Returns whether the last revealed cell gets a brief pulsing outline.
+    pub fn reveal_pulse(&self) -> bool {
+        self.reveal_pulse
+    }
+
+    /// Sets whether the last revealed cell gets a brief pulsing outline (off clears it).
+    pub fn set_reveal_pulse(&mut self, value: bool) {
+        self.reveal_pulse = value;
+        if !value {
+            self.last_revealed = None;
+        }
+    }
+
+    /// Returns the cell with the reveal pulse and the seconds since it started, if any.
+    pub fn last_revealed(&self) -> Option<(usize, usize, f32)> {
+        self.last_revealed
+    }
+
+    /// Sets the cell with the reveal pulse and the seconds since it started (None clears it).
+    pub fn set_last_revealed(&mut self, value: Option<(usize, usize, f32)>) {
+        self.last_revealed = value;
+    }
+
+    /// Returns a mutable reference to the play times of recent effect sounds (for ducking).
+    pub fn recent_sound_times_mut(&mut self) -> &mut VecDeque<f64> {
+        &mut self.recent_sound_times
+    }
+
+    /// Returns the seconds since the loss flash started, if one is showing.
+    pub fn loss_flash(&self) -> Option<f32> {
+        self.loss_flash
+    }
+
+    /// Sets the seconds since the loss flash started (None hides it).
+    pub fn set_loss_flash(&mut self, value: Option<f32>) {
+        self.loss_flash = value;
+    }
+
+    /// Returns the result of the last editor layout save or load, if not dismissed.
+    pub fn editor_message(&self) -> Option<&str> {
+        self.editor_message.as_deref()
+    }
+
+    /// Sets the result of the last editor layout save or load (None hides it).
+    pub fn set_editor_message(&mut self, message: Option<String>) {
+        self.editor_message = message;
+    }
+
+    /// Returns whether flagging mode is on (left-clicks place flags).
+    pub fn flag_mode(&self) -> bool {
+        self.flag_mode
+    }
+
+    /// Sets whether flagging mode is on (left-clicks place flags).
+    pub fn set_flag_mode(&mut self, value: bool) {
+        self.flag_mode = value;
+    }
+
+    /// Returns the palette used to draw cell numbers.
+    pub fn number_palette(&self) -> NumberPalette {
+        self.number_palette
+    }
+
+    /// Sets the palette used to draw cell numbers.
+    pub fn set_number_palette(&mut self, palette: NumberPalette) {
+        self.number_palette = palette;
+    }
+
+    /// Returns whether the number color legend is shown.
+    pub fn show_number_legend(&self) -> bool {
+        self.show_number_legend
+    }
+
+    /// Sets whether the number color legend is shown.
+    pub fn set_show_number_legend(&mut self, show: bool) {
+        self.show_number_legend = show;
+    }
+
+    /// Returns whether accessibility announcements are printed.
+    pub fn screen_reader(&self) -> bool {
+        self.screen_reader
+    }
+
+    /// Sets whether accessibility announcements are printed.
+    pub fn set_screen_reader(&mut self, enabled: bool) {
+        self.screen_reader = enabled;
+    }
+
+    /// Returns the names of all available skins (read-only).
+    pub fn skins(&self) -> &Vec<String> {
+        &self.skins
+    }
+
+    /// Sets the names of all available skins.
+    pub fn set_skins(&mut self, skins: Vec<String>) {
+        self.skins = skins;
+    }
+
+    /// Returns the name of the selected skin.
+    pub fn skin_name(&self) -> &str {
+        &self.skin_name
+    }
+
+    /// Selects a skin by name (loaded by the game loop on the next frame).
+    pub fn set_skin_name(&mut self, name: &str) {
+        self.skin_name = name.to_string();
+    }
+
     /// Helper function to create a new MinesweeperApp with all fields initialized.
     /// Used by both `new` and `reset_game` to avoid code duplication.
     fn make_empty(
@@ -225,8 +1280,9 @@ impl MinesweeperApp {
         mines: usize,
         show_size_popup: bool,
         sound: bool,
+        reveal_order: RevealOrder,
     ) -> Self {
-        Self {
+        let mut app = Self {
             // --- Board and game state ---
             board: Board::new(width, height, mines),
 
@@ -236,134 +1292,811 @@ impl MinesweeperApp {
             ignore_next_size_popup_click: false,
 
             cell_size: BoardSize::board_size_from_params(width, height, mines).cell_size(),
+            dpi_scale: 1.0,
             sound: sound, // Whether sound is muted
+            skins: vec![DEFAULT_SKIN_NAME.to_string()],
+            skin_name: DEFAULT_SKIN_NAME.to_string(),
+            reveal_order,
+            wave_order: WaveOrder::Radial,
+            show_pressure_overlay: false,
+            show_probability_overlay: false,
+            mine_probabilities: HashMap::new(),
+            dim_satisfied_numbers: false,
+            grid_line_width: DEFAULT_GRID_LINE_WIDTH,
+            win_wave: false,
+            loss_flash_enabled: true,
+            flag_limit: false,
+            zen_mode: false,
+            safe_budget_size: 0,
+            safe_budget: 0,
+            flag_mode: false,
+            number_palette: NumberPalette::Classic,
+            show_number_legend: false,
+            screen_reader: false,
 
             // --- Booleans (game state flags) ---
             // --- Game state ---
             state: GameState::NotStarted,
+            editor_mode: false,
+            forced_guess: false,
+            hint: None,
+            last_reveal_cell: None,
+            safe_reveals_used: 0,
+            wins_with_help: 0,
+
+            seed: None,
+            replay: None,
+            auto_open: false,
+            auto_open_cell: None,
+            opening_assist: false,
+            preferred_openings: HashMap::new(),
+            first_click_grace: false,
+            first_reveal_pending: false,
+            retry_same_board: false,
+            confirm_new_game: true,
+            pending_new_game: None,
+            countdown_enabled: false,
+            countdown: None,
+
+            // --- Marathon mode state ---
+            marathon: false,
+            marathon_count: 0,
+            marathon_total_time: 0.0,
+            win_streak: 0,
+            streak_banner: None,
+            fanfare_pending: false,
 
             // --- Timers and time tracking ---
             start_time: 0.0,
+            move_time_limit: None,
+            last_move_time: 0.0,
+            move_clock_paused_at: None,
             end_time: None,
+            timer_precision: TimerPrecision::Seconds,
+            counter_mode: CounterMode::Flags,
+            timer_text: String::new(),
+            timer_prev_text: String::new(),
+            timer_roll: 0.0,
+            displayed_flags: mines as f32,
+            flags_tween_speed: 0.0,
 
             // --- Animation and effect state ---
-            pop_timers: vec![vec![None; width]; height],
-            wave_timers: vec![vec![None; width]; height],
+            pop_timers: Vec::new(), // Sized by clear_timers below
+            wave_timers: Vec::new(),
+            flag_timers: Vec::new(),
             particles: Vec::new(),
             shockwaves: Vec::new(),
+            loss_flash: None,
+            flag_refusal: None,
+            reveal_pulse: true,
+            last_revealed: None,
+            recent_sound_times: VecDeque::new(),
+            instant_numbers: false,
 
             // --- Reveal and flag state ---
             mine_reveal_queue: Vec::new(),
+            mine_reveal_delay: 0.0,
+            skip_mine_reveal: false,
             wrong_flags: Vec::new(),
+
+            // --- Input state ---
+            click_queue: VecDeque::new(),
+            double_click_chord: false,
+            double_click_window: DEFAULT_DOUBLE_CLICK_WINDOW,
+            last_click: None,
+            custom_dialog: None,
+
+            // --- Debug overlay state ---
+            show_debug_overlay: false,
+            frame_times: VecDeque::new(),
+
+            // --- Asset state ---
+            asset_error: None,
+            screenshot_pending: false,
+            safe_peek: None,
+            replay_view: None,
+            screenshot_message: None,
+            editor_message: None,
+        };
+        app.clear_timers();
+        app
+    }
+
+    /// Clears every per-cell animation timer (pop, wave, flag). The grids are cleared in place
+    /// when they already match the board's dimensions and only reallocated when the size
+    /// changed, so frequent restarts (e.g. in marathon mode) don't churn allocations.
+    pub fn clear_timers(&mut self) {
+        let (width, height) = (self.board.width(), self.board.height());
+        for grid in [&mut self.pop_timers, &mut self.wave_timers, &mut self.flag_timers] {
+            let same_size = grid.len() == height && grid.iter().all(|row| row.len() == width);
+            if same_size {
+                grid.iter_mut().for_each(|row| row.fill(None));
+            } else {
+                *grid = vec![vec![None; width]; height];
+            }
         }
     }
 
     /// Creates a new MinesweeperApp instance with the given board size and mine count.
     /// This is the main constructor, called at program start.
     pub fn new(width: usize, height: usize, mines: usize) -> Self {
-        Self::make_empty(width, height, mines, false, true)
+        Self::make_empty(width, height, mines, false, true, RevealOrder::Random)
     }
 
     /// Resets the current game to its initial state, keeping the same board size and mine count.
     /// Called when the player clicks "New Game" or restarts.
     pub fn reset_game(&mut self) {
         let (width, height, mines) = self.board_size.params();
-        *self = Self::make_empty(width, height, mines, self.show_size_popup, self.sound);
+        let mut fresh = Self::make_empty(
+            width,
+            height,
+            mines,
+            self.show_size_popup,
+            self.sound,
+            self.reveal_order,
+        );
+        fresh.board_size = self.board_size; // Custom sizes don't map back from their params
+        // Keep the debug overlay open across games while diagnosing
+        fresh.show_debug_overlay = self.show_debug_overlay;
+        fresh.dpi_scale = self.dpi_scale;
+        fresh.asset_error = self.asset_error.take();
+        fresh.auto_open = self.auto_open;
+        fresh.auto_open_cell = self.auto_open_cell;
+        fresh.opening_assist = self.opening_assist;
+        fresh.preferred_openings = std::mem::take(&mut self.preferred_openings);
+        fresh.first_click_grace = self.first_click_grace;
+        fresh.first_reveal_pending = true;
+        fresh.retry_same_board = self.retry_same_board;
+        fresh.confirm_new_game = self.confirm_new_game;
+        fresh.countdown_enabled = self.countdown_enabled;
+        fresh.countdown = self.countdown_enabled.then_some(COUNTDOWN_SECONDS);
+        fresh.show_pressure_overlay = self.show_pressure_overlay;
+        fresh.show_probability_overlay = self.show_probability_overlay;
+        fresh.dim_satisfied_numbers = self.dim_satisfied_numbers;
+        fresh.grid_line_width = self.grid_line_width;
+        fresh.win_wave = self.win_wave;
+        fresh.loss_flash_enabled = self.loss_flash_enabled;
+        fresh.flag_limit = self.flag_limit;
+        fresh.zen_mode = self.zen_mode;
+        fresh.safe_budget_size = self.safe_budget_size;
+        fresh.safe_budget = self.safe_budget_size;
+        fresh.flag_mode = self.flag_mode;
+        fresh.skip_mine_reveal = self.skip_mine_reveal;
+        fresh.instant_numbers = self.instant_numbers;
+        fresh.reveal_pulse = self.reveal_pulse;
+        fresh.number_palette = self.number_palette;
+        fresh.show_number_legend = self.show_number_legend;
+        fresh.screen_reader = self.screen_reader;
+        fresh.timer_precision = self.timer_precision;
+        fresh.counter_mode = self.counter_mode;
+        fresh.wave_order = self.wave_order;
+        fresh.board.set_topology(self.board.topology());
+        fresh.skins = std::mem::take(&mut self.skins);
+        fresh.skin_name = std::mem::take(&mut self.skin_name);
+        fresh.move_time_limit = self.move_time_limit;
+        fresh.marathon = self.marathon;
+        fresh.marathon_count = self.marathon_count;
+        fresh.marathon_total_time = self.marathon_total_time;
+        fresh.win_streak = self.win_streak;
+        fresh.streak_banner = self.streak_banner;
+        fresh.fanfare_pending = self.fanfare_pending;
+        fresh.wins_with_help = self.wins_with_help;
+        fresh.double_click_chord = self.double_click_chord;
+        fresh.double_click_window = self.double_click_window;
+        // Reuse the timer grids instead of allocating new ones for every game
+        fresh.pop_timers = std::mem::take(&mut self.pop_timers);
+        fresh.wave_timers = std::mem::take(&mut self.wave_timers);
+        fresh.flag_timers = std::mem::take(&mut self.flag_timers);
+        fresh.clear_timers();
+        *self = fresh;
+        if self.auto_open {
+            self.auto_open_start();
+        }
+        self.update_forced_guess();
+        self.update_mine_probabilities();
+    }
+
+    /// Returns whether "New Game" asks for confirmation while a game is in progress.
+    pub fn confirm_new_game(&self) -> bool {
+        self.confirm_new_game
+    }
+
+    /// Sets whether "New Game" asks for confirmation while a game is in progress.
+    pub fn set_confirm_new_game(&mut self, value: bool) {
+        self.confirm_new_game = value;
+    }
+
+    /// Returns whether the "start a new game?" confirmation popup is showing.
+    pub fn new_game_confirm_pending(&self) -> bool {
+        self.pending_new_game.is_some()
+    }
+
+    /// Returns the request waiting for confirmation, if the popup is showing.
+    pub fn pending_new_game(&self) -> Option<NewGameRequest> {
+        self.pending_new_game
+    }
+
+    /// Shows the "start a new game?" confirmation popup for a request, or hides it (None).
+    pub fn set_pending_new_game(&mut self, request: Option<NewGameRequest>) {
+        self.pending_new_game = request;
+    }
+
+    /// Returns whether a modal overlay is open: the custom board dialog, the "start a new
+    /// game?" confirmation, or the safe-cell peek. Board-changing keys and clicks are ignored
+    /// while one is.
+    pub fn modal_active(&self) -> bool {
+        self.custom_dialog.is_some() || self.new_game_confirm_pending() || self.safe_peek_active()
+    }
+
+    /// Handles a request that abandons the current game (the "New Game" button, G, a size
+    /// change, or the daily puzzle). If the current game is running and some cells are
+    /// uncovered (and confirmation is on), the confirmation popup is shown instead; otherwise
+    /// the requested game starts right away. Returns whether it started.
+    /// Asking for the size already selected does nothing.
+    pub fn request_new_game(&mut self, request: NewGameRequest) -> bool {
+        if request == NewGameRequest::Size(self.board_size) {
+            return false;
+        }
+        let in_progress = self.state == GameState::Running
+            && self.board.states().iter().flatten().any(|&s| s == CellState::Uncovered);
+        if self.confirm_new_game && in_progress {
+            self.pending_new_game = Some(request);
+            return false;
+        }
+        self.start_requested_game(request);
+        true
+    }
+
+    /// Starts the game a `NewGameRequest` asks for, without asking first.
+    /// The window is resized separately by `resize_window_to_board`, which needs a window.
+    pub fn start_requested_game(&mut self, request: NewGameRequest) {
+        match request {
+            NewGameRequest::Next => self.new_game(),
+            NewGameRequest::Random => self.reset_game(),
+            NewGameRequest::Size(size) => {
+                self.change_board_size(size);
+            }
+            NewGameRequest::Daily => self.start_daily(&today_utc()),
+        }
+    }
+
+    /// Starts the next game, as the "New Game" button and the endgame popups do.
+    /// With `retry_same_board` on, the current layout is replayed from its seed (see
+    /// `retry_board`); otherwise, or if the layout has no seed, a new random game starts.
+    pub fn new_game(&mut self) {
+        if !(self.retry_same_board && self.retry_board()) {
+            self.reset_game();
+        }
+    }
+
+    /// Starts the level editor on an empty board of the current size.
+    /// Left-clicks toggle mines until the layout is played with `play_edited_layout`.
+    pub fn start_editor(&mut self) {
+        self.reset_game();
+        let (width, height, _) = self.board_size.params();
+        let topology = self.board.topology();
+        self.board = Board::new(width, height, 0);
+        self.board.set_topology(topology);
+        self.editor_mode = true;
+        self.countdown = None; // The editor has no clock to sync
+    }
+
+    /// Saves the layout being edited to a `.mine` file (see `Board::to_mine_file`).
+    pub fn save_editor_layout(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.board.to_mine_file())
+            .map_err(|err| format!("Could not save {}: {}", path, err))
+    }
+
+    /// Loads a `.mine` layout into the editor. The layout must match the current board's size;
+    /// on an error the board is left as it was.
+    pub fn load_editor_layout(&mut self, path: &str) -> Result<(), String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {}", path, err))?;
+        let mut board =
+            Board::from_mine_file(&text).map_err(|err| format!("{}: {}", path, err))?;
+        let (width, height) = (self.board.width(), self.board.height());
+        if (board.width(), board.height()) != (width, height) {
+            return Err(format!(
+                "{} is {}x{}, but the board is {}x{}",
+                path,
+                board.width(),
+                board.height(),
+                width,
+                height
+            ));
+        }
+        board.set_topology(self.board.topology());
+        self.board = board;
+        Ok(())
+    }
+
+    /// Locks the edited layout and starts playing it.
+    /// Mines are already placed, so the game goes straight to `Running`.
+    pub fn play_edited_layout(&mut self) {
+        self.editor_mode = false;
+        self.first_reveal_pending = false; // Keep the hand-made layout as it is
+        self.start_time = get_time();
+        self.state = GameState::Running;
     }
 
     /// Main game loop. Handles drawing, input, and game logic.
     /// This version is broken into smaller helper functions for clarity.
     pub async fn run(&mut self) {
-        // Load textures and audio using constants for file paths and audio  paths
-        let flag_texture = load_texture(FLAG_TEXTURE_PATH).await.unwrap();
-        let mine_texture = load_texture(MINE_TEXTURE_PATH).await.unwrap();
-        let clock_texture = load_texture(CLOCK_TEXTURE_PATH).await.unwrap();
-        let mute_texture = load_texture(MUTE_TEXTURE_PATH).await.unwrap(); // Mute/sound icon
-        let synchronize_texture = load_texture(SYNCHRONIZE_TEXTURE_PATH).await.unwrap(); // New game/restart icon
-        let volume_texture = load_texture(VOLUME_TEXTURE_PATH).await.unwrap();
-        let flag_sound: Sound = load_sound(FLAG_SOUND_PATH).await.unwrap();
-        let bomb_sound: Sound = load_sound(BOMB_SOUND_PATH).await.unwrap();
-        let remove_flag_sound: Sound = load_sound(REMOVE_FLAG_SOUND_PATH).await.unwrap();
-        let flip_sound: Sound = load_sound(FLIP_SOUND_PATH).await.unwrap();
-        let wave_sound: Sound = load_sound(WAVE_SOUND_PATH).await.unwrap();
-        let mistake_sound: Sound = load_sound(MISTAKE_SOUND_PATH).await.unwrap();
-        let game_over_sound: Sound = load_sound(GAME_OVER_SOUND_PATH).await.unwrap();
-        let win_sound: Sound = load_sound(WIN_SOUND_PATH).await.unwrap();
+        // Load the default skin (icon textures) and sounds; missing files get placeholders
+        // and are listed on screen instead of crashing the game
+        let assets = match Assets::load().await {
+            Ok(assets) => assets,
+            Err(err) => {
+                self.asset_error = Some(err.to_string());
+                err.assets
+            }
+        };
+        let default_skin = assets.skin;
+        let sounds = assets.sounds;
+
+        // Find custom skins and load the selected one
+        self.skins = available_skins(SKINS_DIR);
+        let mut skin = Skin::load(&self.skin_name, &default_skin).await;
 
         let mut mine_reveal_timer = 0.0;
+        let mut auto_solve_timer = 0.0; // Seconds until the held S key plays the next solver move
 
         loop {
             // 1. Clear the screen to a light gray background
             clear_background(LIGHTGRAY);
+            self.set_dpi_scale(screen_dpi_scale());
+            // Fit the cells to the window (the player may have resized or maximized it)
+            self.cell_size = self.compute_cell_size((screen_width(), screen_height()));
+
+            // Auto-opened games start Running without a timer (reset_game can't read the clock),
+            // so start it on the first frame
+            let clock_waiting = self.start_time == 0.0 && self.countdown.is_none();
+            if self.state == GameState::Running && clock_waiting {
+                self.start_time = get_time();
+            }
+
+            // Run the start countdown; the clock starts when it reaches zero
+            self.tick_countdown(get_frame_time(), get_time());
+
+            // 2. Load a newly selected skin (falls back to the default icons for missing files)
+            if skin.name != self.skin_name {
+                skin = Skin::load(&self.skin_name, &default_skin).await;
+            }
 
-            // 2. Draw the top bar UI (flags, timer, new game button, sound)
+            // 3. Draw the top bar UI (flags, timer, new game button, sound)
             self.draw_top_bar(
                 self.cell_size,
-                &flag_texture,
-                &clock_texture,
-                &synchronize_texture,
-                &mute_texture,
-                &volume_texture,
+                &skin.flag,
+                &skin.clock,
+                &skin.synchronize,
+                &skin.mute,
+                &skin.volume,
             );
 
-            // 3. Draw the Minesweeper board (cells)
-            self.draw_board(self.cell_size, &flag_texture, &mine_texture, &win_sound);
+            // 4. Draw the Minesweeper board (cells)
+            self.draw_board(self.cell_size, &skin.flag, &skin.mine, &sounds.win);
+
+            // 5. Draw the start countdown over the board (if counting down)
+            self.draw_countdown(self.cell_size);
 
-            // 4. Draw the dropdown menu LAST, so it appears on top of the cells
+            // 6. Draw the dropdown menu LAST, so it appears on top of the cells
             if self.show_size_popup {
-                self.draw_top_bar_dropdown_menu(&flag_texture, &clock_texture);
+                self.draw_top_bar_dropdown_menu(&skin.flag, &skin.clock);
             }
 
-            // 5. Update and draw all particle effects (confetti, explosions, etc.)
+            // 7. Update and draw all particle effects (confetti, explosions, etc.)
             update_and_draw_particles(&mut self.particles);
 
-            // 6. Update and draw all shockwave effects
+            // 8. Update and draw all shockwave effects
             self.update_and_draw_shockwaves();
 
-            // 7. Reveal mines with animation
+            // 9. Draw the red loss flash over the whole window (fades out on its own)
+            self.draw_loss_flash(get_frame_time());
+
+            // 10. Reveal mines with animation
             self.reveal_mines_with_animation(
                 self.cell_size,
                 &mut mine_reveal_timer,
-                &bomb_sound,
-                &mistake_sound,
+                &sounds.bomb,
+                &sounds.mistake,
             );
 
-            // 8. Show game over popup if ready (after all animations)
-            self.show_game_over_popup_if_ready(&game_over_sound);
-
-            // 9. Handle left mouse click (main game logic)
-            if !self.show_size_popup {
-                if is_mouse_button_pressed(MouseButton::Left)
-                    && (self.state == GameState::NotStarted || self.state == GameState::Running)
-                {
-                    if let Some((row, col)) = self.mouse_to_cell(self.cell_size) {
-                        if self.board.cell_state(row, col) == Some(CellState::Covered) {
-                            self.handle_left_click(
-                                row,
-                                col,
-                                self.cell_size,
-                                &mut mine_reveal_timer,
-                                &bomb_sound,
-                                &flip_sound,
-                                &wave_sound,
-                                &win_sound,
-                            );
+            // 11. Show game over popup if ready (after all animations)
+            self.show_game_over_popup_if_ready(&sounds.game_over);
+
+            // Hotkeys that change the board are ignored while a dialog, popup, or peek is open
+            let modal_active = self.modal_active();
+
+            // 12. Toggle the level editor with the E key (only before the game starts)
+            if is_key_pressed(KeyCode::E) && self.state == GameState::NotStarted && !modal_active {
+                if self.editor_mode {
+                    self.reset_game();
+                } else {
+                    self.start_editor();
+                }
+            }
+
+            // 13. Save (F8) or load (F9) the layout being edited as a .mine file
+            if self.editor_mode && is_key_pressed(KeyCode::F8) {
+                let message = match self.save_editor_layout(EDITOR_LAYOUT_PATH) {
+                    Ok(()) => format!("Saved {}", EDITOR_LAYOUT_PATH),
+                    Err(err) => err,
+                };
+                self.editor_message = Some(message);
+            }
+            if self.editor_mode && is_key_pressed(KeyCode::F9) && !modal_active {
+                let message = match self.load_editor_layout(EDITOR_LAYOUT_PATH) {
+                    Ok(()) => format!("Loaded {}", EDITOR_LAYOUT_PATH),
+                    Err(err) => err,
+                };
+                self.editor_message = Some(message);
+            }
+
+            // 14. Copy the current seed to the clipboard with the C key
+            if is_key_pressed(KeyCode::C) {
+                self.copy_seed_to_clipboard();
+            }
+
+            // 15. Toggle the safe auto-open with the O key (takes effect from the next game)
+            if is_key_pressed(KeyCode::O) {
+                self.auto_open = !self.auto_open;
+            }
+
+            // 16. Toggle the pressure heatmap overlay with the H key
+            if is_key_pressed(KeyCode::H) {
+                self.show_pressure_overlay = !self.show_pressure_overlay;
+            }
+
+            // 17. Toggle the beginner first-click grace with the B key
+            if is_key_pressed(KeyCode::B) {
+                self.first_click_grace = !self.first_click_grace;
+            }
+
+            // 18. Show a hint (the most informative safe cell) with the N key
+            if is_key_pressed(KeyCode::N) && !modal_active {
+                self.request_hint();
+            }
+
+            // 19. Toggle the mine probability overlay with the R key
+            if is_key_pressed(KeyCode::R) {
+                self.set_show_probability_overlay(!self.show_probability_overlay);
+            }
+
+            // 20. Toggle satisfied-number dimming with the D key
+            if is_key_pressed(KeyCode::D) {
+                self.dim_satisfied_numbers = !self.dim_satisfied_numbers;
+            }
+
+            // 21. Toggle the "3-2-1" start countdown with the I key (applies from the next game)
+            if is_key_pressed(KeyCode::I) {
+                self.countdown_enabled = !self.countdown_enabled;
+            }
+
+            // 22. Cycle the grid line thickness (2px, 1px, no borders) with the V key
+            if is_key_pressed(KeyCode::V) {
+                let current = GRID_LINE_WIDTHS.iter().position(|&w| w == self.grid_line_width);
+                let next = current.map_or(0, |i| (i + 1) % GRID_LINE_WIDTHS.len());
+                self.grid_line_width = GRID_LINE_WIDTHS[next];
+            }
+
+            // 23. Toggle the celebratory win wave with the W key
+            if is_key_pressed(KeyCode::W) {
+                self.win_wave = !self.win_wave;
+            }
+
+            // 24. Toggle the red loss flash with the J key
+            if is_key_pressed(KeyCode::J) {
+                self.loss_flash_enabled = !self.loss_flash_enabled;
+            }
+
+            // 25. Toggle the flag limit (no more flags than mines) with F5
+            if is_key_pressed(KeyCode::F5) {
+                self.flag_limit = !self.flag_limit;
+            }
+
+            // 26. Toggle zen mode (no timer, mines get flagged instead of lost to) with F7
+            if is_key_pressed(KeyCode::F7) {
+                self.zen_mode = !self.zen_mode;
+            }
+
+            // 27. Cycle the safe-reveal budget (lives per game: off, 1, 3, 5) with F11
+            if is_key_pressed(KeyCode::F11) {
+                let next = SAFE_BUDGET_CHOICES
+                    .iter()
+                    .position(|&lives| lives == self.safe_budget_size)
+                    .map_or(0, |i| (i + 1) % SAFE_BUDGET_CHOICES.len());
+                self.safe_budget_size = SAFE_BUDGET_CHOICES[next];
+                if self.state == GameState::NotStarted {
+                    self.safe_budget = self.safe_budget_size;
+                }
+            }
+
+            // 28. Toggle double-click chording with the Q key
+            if is_key_pressed(KeyCode::Q) {
+                self.set_double_click_chord(!self.double_click_chord);
+            }
+
+            // 29. Switch the number palette (classic / colorblind-safe) with the P key
+            if is_key_pressed(KeyCode::P) {
+                self.number_palette = match self.number_palette {
+                    NumberPalette::Classic => NumberPalette::OkabeIto,
+                    NumberPalette::OkabeIto => NumberPalette::Classic,
+                };
+            }
+
+            // 30. Toggle the number color legend with the L key
+            if is_key_pressed(KeyCode::L) {
+                self.show_number_legend = !self.show_number_legend;
+            }
+
+            // 31. Toggle the timer precision (whole seconds / tenths) with the T key
+            if is_key_pressed(KeyCode::T) {
+                self.timer_precision = match self.timer_precision {
+                    TimerPrecision::Seconds => TimerPrecision::Tenths,
+                    TimerPrecision::Tenths => TimerPrecision::Seconds,
+                };
+            }
+
+            // 32. Cycle the top-bar counter (flags left, cells left, both) with the F3 key
+            if is_key_pressed(KeyCode::F3) {
+                self.counter_mode = self.counter_mode.next();
+            }
+
+            // 33. Cycle the flood-fill wave order (radial, spiral, left to right) with F6
+            if is_key_pressed(KeyCode::F6) {
+                self.wave_order = self.wave_order.next();
+            }
+
+            // 34. Switch between 8 and 4 neighbors per cell with F10 (only before the game starts)
+            if is_key_pressed(KeyCode::F10)
+                && self.state == GameState::NotStarted
+                && !modal_active
+            {
+                let topology = match self.board.topology() {
+                    Topology::Square => Topology::VonNeumann,
+                    Topology::VonNeumann => Topology::Square,
+                };
+                self.board.set_topology(topology);
+            }
+
+            // 35. Toggle the opening assist (first click opens the preferred cell) with F4
+            if is_key_pressed(KeyCode::F4) {
+                self.opening_assist = !self.opening_assist;
+            }
+
+            // 36. Toggle marathon mode with the M key (only between games)
+            if is_key_pressed(KeyCode::M) && self.state == GameState::NotStarted && !modal_active {
+                self.set_marathon(!self.marathon);
+            }
+
+            // 37. Toggle time attack with the A key (only between games)
+            if is_key_pressed(KeyCode::A) && self.state == GameState::NotStarted && !modal_active {
+                self.move_time_limit = match self.move_time_limit {
+                    Some(_) => None,
+                    None => Some(DEFAULT_MOVE_TIME_LIMIT),
+                };
+            }
+
+            // 38. Toggle replaying the same layout on "New Game" with the Y key
+            if is_key_pressed(KeyCode::Y) {
+                self.retry_same_board = !self.retry_same_board;
+            }
+
+            // 39. Start a new random layout with the G key, even when retrying the same board
+            if is_key_pressed(KeyCode::G) && !modal_active {
+                self.request_new_game(NewGameRequest::Random);
+            }
+
+            // 40. Reveal the safe cell nearest the last click with F1 or by clicking the
+            // "Guess required" note (an emergency button for stuck beginners)
+            let help_requested = !modal_active
+                && (is_key_pressed(KeyCode::F1) || self.guess_indicator_clicked());
+            if help_requested && self.reveal_one_safe().is_some() {
+                if self.sound {
+                    play_sound(
+                        &sounds.flip,
+                        PlaySoundParams {
+                            looped: false,
+                            volume: 0.5,
+                        },
+                    );
+                }
+                self.record_move(get_time());
+                self.check_win(self.cell_size, &sounds.win);
+            }
+
+            // 41. Toggle screen-reader output with Shift+Z; Z announces the game state and
+            // the cell under the mouse
+            if is_key_pressed(KeyCode::Z) {
+                if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+                    self.screen_reader = !self.screen_reader;
+                    self.announce("Screen reader output on");
+                } else {
+                    let cursor = self.mouse_to_cell(self.cell_size);
+                    self.announce(&self.describe_state(cursor));
+                }
+            }
+
+            // 42. Toggle the mid-game "New Game" confirmation with the U key
+            if is_key_pressed(KeyCode::U) {
+                self.confirm_new_game = !self.confirm_new_game;
+            }
+
+            // 43. Cycle the board size (Small -> Medium -> Large) with the Tab key
+            if is_key_pressed(KeyCode::Tab)
+                && !modal_active
+                && self.request_new_game(NewGameRequest::Size(self.board_size.next()))
+            {
+                self.resize_window_to_board();
+            }
+
+            // 44. Toggle skipping the game-over mine reveal cascade with the K key
+            if is_key_pressed(KeyCode::K) {
+                self.skip_mine_reveal = !self.skip_mine_reveal;
+            }
+
+            // 45. Toggle showing numbers during the reveal pop (instead of after it) with F12
+            if is_key_pressed(KeyCode::F12) {
+                self.instant_numbers = !self.instant_numbers;
+            }
+
+            // 46. Toggle the pulsing outline on the last revealed cell with the period key
+            if is_key_pressed(KeyCode::Period) {
+                self.set_reveal_pulse(!self.reveal_pulse);
+            }
+
+            // 47. Toggle flagging mode with the F key (the flag icon in the top bar does the same)
+            if is_key_pressed(KeyCode::F) {
+                self.flag_mode = !self.flag_mode;
+            }
+
+            // 48. Record board clicks in the click queue (or toggle mines in the editor)
+            if !self.show_size_popup && !self.modal_active() {
+                if self.editor_mode {
+                    if is_mouse_button_pressed(MouseButton::Left) {
+                        if let Some((row, col)) = self.mouse_to_cell(self.cell_size) {
+                            self.board.toggle_mine(row, col);
+                        }
+                    }
+                } else {
+                    let shift_held =
+                        is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+                    for button in [MouseButton::Left, MouseButton::Right] {
+                        if is_mouse_button_pressed(button) {
+                            if let Some((row, col)) = self.mouse_to_cell(self.cell_size) {
+                                let button = self.effective_button(button, shift_held);
+                                self.queue_click(row, col, button);
+                            }
                         }
                     }
                 }
+            }
+
+            // 49. Process one queued click per frame (left = uncover or chord, right = flag/unflag)
+            if let Some((row, col, button)) = self.next_queued_click() {
+                let states_before = self.board.states().to_vec();
+                if button == MouseButton::Left {
+                    self.handle_left_click(
+                        row,
+                        col,
+                        self.cell_size,
+                        &mut mine_reveal_timer,
+                        &sounds,
+                    );
+                } else {
+                    self.handle_right_click(
+                        row,
+                        col,
+                        &sounds.flag,
+                        &sounds.remove_flag,
+                        &sounds.mistake,
+                    );
+                }
+                // Only clicks that changed the board restart the time attack limit
+                if self.board.states() != states_before.as_slice() {
+                    self.record_move(get_time());
+                }
+                self.update_forced_guess();
+                self.update_mine_probabilities();
+            }
 
-                // 10. Handle right mouse click (flag/unflag)
-                if is_mouse_button_pressed(MouseButton::Right) && self.state == GameState::Running {
-                    if let Some((row, col)) = self.mouse_to_cell(self.cell_size) {
-                        self.handle_right_click(row, col, &flag_sound, &remove_flag_sound);
+            // 50. Auto-solver: S plays one certain move; holding S keeps playing them
+            if is_key_down(KeyCode::S) && self.state == GameState::Running && !modal_active {
+                auto_solve_timer -= get_frame_time();
+                if is_key_pressed(KeyCode::S) || auto_solve_timer <= 0.0 {
+                    auto_solve_timer = if is_key_pressed(KeyCode::S) {
+                        AUTO_SOLVE_HOLD_DELAY
+                    } else {
+                        AUTO_SOLVE_REPEAT_INTERVAL
+                    };
+                    if self.auto_solve_step() {
+                        if self.sound {
+                            play_sound(
+                                &sounds.flip,
+                                PlaySoundParams {
+                                    looped: false,
+                                    volume: 0.5,
+                                },
+                            );
+                        }
+                        self.record_move(get_time());
+                        self.check_win(self.cell_size, &sounds.win);
                     }
                 }
             }
 
-            // 11. Handle endgame popups (win/game over)
+            // 51. Time attack: lose the game if the per-move limit ran out
+            if self.check_move_time_limit(get_time()) && self.sound {
+                play_sound(
+                    &sounds.game_over,
+                    PlaySoundParams {
+                        looped: false,
+                        volume: 0.8,
+                    },
+                );
+            }
+
+            // 52. Handle endgame popups (win/game over)
             self.handle_endgame_popups(self.cell_size);
 
-            // 12. Wait for the next frame (yields to the event loop)
+            // 53. Ask before abandoning a game in progress (Enter confirms, Esc cancels)
+            self.handle_new_game_confirmation(self.cell_size);
+
+            // 54. Show the custom board dialog (keyboard entry of width, height, and mines)
+            self.handle_custom_dialog(self.cell_size);
+
+            // 55. Play the fanfare and show the banner for win-streak milestones
+            if self.fanfare_pending {
+                self.fanfare_pending = false;
+                if self.sound {
+                    play_sound(
+                        &sounds.fanfare,
+                        PlaySoundParams {
+                            looped: false,
+                            volume: 0.8,
+                        },
+                    );
+                }
+            }
+            self.draw_streak_banner(get_time());
+
+            // 56. Draw the number color legend (if toggled on)
+            self.draw_number_legend();
+
+            // 57. Peek at the board with every safe cell shown (mines stay hidden) with the / key
+            // (it can always be closed, but not opened over the dialog or confirmation popup)
+            if is_key_pressed(KeyCode::Slash) && (self.safe_peek_active() || !self.modal_active()) {
+                self.toggle_safe_peek();
+            }
+
+            // 58. Step through the replay of a finished game with the Left and Right arrows
+            if !self.modal_active() {
+                if is_key_pressed(KeyCode::Left) {
+                    self.step_replay_view(-1);
+                }
+                if is_key_pressed(KeyCode::Right) {
+                    self.step_replay_view(1);
+                }
+            }
+
+            // 59. Save the finished (or peeked) board as a PNG with the X key (before the overlays)
+            if is_key_pressed(KeyCode::X) {
+                self.request_screenshot();
+            }
+            self.take_pending_screenshot();
+
+            // 60. Toggle and draw the debug overlay (F2), on top of everything else
+            if is_key_pressed(KeyCode::F2) {
+                self.show_debug_overlay = !self.show_debug_overlay;
+            }
+            self.update_and_draw_debug_overlay();
+
+            // 61. Draw the missing-assets panel and any screenshot or editor message
+            self.draw_asset_error_panel();
+            self.draw_screenshot_message();
+            self.draw_editor_message();
+
+            // 62. Wait for the next frame (yields to the event loop)
             next_frame().await;
         }
     }
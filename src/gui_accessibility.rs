@@ -0,0 +1,74 @@
+//! Accessibility helpers for Minesweeper.
+//!
+//! This module builds a plain-text description of the game (board size, game state, mines
+//! left, elapsed time, and the cell under the cursor) that a screen reader can read out.
+//! Shift+Z turns screen-reader output on and Z then announces the description; for now
+//! announcements go to stdout, and `announce` is the place where a text-to-speech backend
+//! can be hooked in later.
+
+use super::MinesweeperApp;
+use crate::board::{Cell, CellState};
+use crate::gui::GameState;
+
+impl MinesweeperApp {
+    /// Returns a human-readable summary of the game, e.g.
+    /// `Medium board, 16 by 16. Game in progress. 38 mines left. Time 00:42.`
+    /// If `cursor` is a (row, col) on the board, the cell there is described as well
+    /// (rows and columns are counted from 1).
+    pub fn describe_state(&self, cursor: Option<(usize, usize)>) -> String {
+        let board = self.board();
+        let state = match self.state() {
+            GameState::NotStarted => "Not started",
+            GameState::Running => "Game in progress",
+            GameState::GameOver | GameState::Lost => "Game lost",
+            GameState::Won => "Game won",
+        };
+        let time = if self.timer_text().is_empty() {
+            self.format_elapsed(0.0)
+        } else {
+            self.timer_text().to_string()
+        };
+        let mut text = format!(
+            "{} board, {} by {}. {}. {} mines left. Time {}.",
+            self.board_size().label(),
+            board.width(),
+            board.height(),
+            state,
+            board.remaining_mines(),
+            time
+        );
+        if let Some((row, col)) = cursor {
+            if let Some(cell) = self.describe_cell(row, col) {
+                text.push_str(&format!(" Row {}, column {}: {}.", row + 1, col + 1, cell));
+            }
+        }
+        text
+    }
+
+    /// Describes what the player can see of a cell ("covered", "flagged", "3", ...).
+    /// Returns None if the cell is off the board.
+    pub fn describe_cell(&self, row: usize, col: usize) -> Option<String> {
+        let description = match self.board().cell_state(row, col)? {
+            CellState::Covered => "covered".to_string(),
+            CellState::Flagged => "flagged".to_string(),
+            CellState::Maybe => "marked maybe".to_string(),
+            CellState::Uncovered => match self.board().cell(row, col)? {
+                Cell::Mine => "mine".to_string(),
+                Cell::Number(n) => n.to_string(),
+                Cell::Empty => "empty".to_string(),
+            },
+        };
+        Some(description)
+    }
+
+    /// Announces text to the player if screen-reader output is on, returning whether it was
+    /// announced. For now it is printed to stdout (where screen readers watching the terminal
+    /// pick it up); a text-to-speech backend can be called from here.
+    pub fn announce(&self, text: &str) -> bool {
+        if !self.screen_reader() {
+            return false;
+        }
+        println!("{}", text);
+        true
+    }
+}
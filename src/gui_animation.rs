@@ -6,8 +6,11 @@
 
 use super::MinesweeperApp;
 use crate::board::*;
-use crate::gui::GameState;
+use crate::gui::{GameState, RevealOrder};
 use crate::particle::*;
+use ::rand::rngs::StdRng;
+use ::rand::seq::SliceRandom;
+use ::rand::SeedableRng;
 use macroquad::audio::*;
 use macroquad::prelude::*;
 
@@ -19,15 +22,29 @@ const TOP_BAR_HEIGHT: f32 = 60.0;
 const POP_GROW_PHASE: f32 = 0.2; // First 20% of animation: grow
 const POP_GROW_AMOUNT: f32 = 1.5; // How much to grow
 const POP_SHRINK_START: f32 = 1.3; // Max scale before shrinking
-const POP_LINE_WIDTH: f32 = 2.0; // Border thickness
 const POP_ANIMATION_DURATION: f32 = 0.5; // Duration for pop animation
+const FLAG_ANIMATION_DURATION: f32 = 0.25; // Duration for flag-planting animation
+const FLAG_GROW_PHASE: f32 = 0.6; // First 60% of the flag animation: grow past full size
+const FLAG_OVERSHOOT: f32 = 1.25; // Peak scale before settling back to full size
 
 // --- Shockwave effect constants ---
 const SHOCKWAVE_START_RADIUS: f32 = 30.0;
 const SHOCKWAVE_GROWTH: f32 = 200.0;
 const SHOCKWAVE_LINE_WIDTH: f32 = 6.0;
 const SHOCKWAVE_COLOR: Color = Color::from_rgba(255, 0, 0, 180);
-const REVEAL_DELAY: f32 = 0.37; // Delay between revealing mines (seconds)
+const FLAG_REFUSAL_DURATION: f32 = 0.25; // Seconds a cell shakes after a refused flag
+const FLAG_REFUSAL_AMPLITUDE: f32 = 3.0; // Widest sideways offset of the shake (pixels)
+const FLAG_REFUSAL_SHAKES: f32 = 3.0; // Back-and-forth swings during the shake
+const LOSS_FLASH_DURATION: f32 = 0.3; // Seconds for the red loss flash to fade out
+const LOSS_FLASH_ALPHA: f32 = 0.45; // Opacity of the loss flash when it starts
+const REVEAL_PULSE_DURATION: f32 = 1.2; // Seconds the last revealed cell's outline pulses
+const REVEAL_PULSE_COUNT: f32 = 3.0; // Pulses of the outline before it fades out
+const REVEAL_PULSE_WIDTH: f32 = 3.0;
+const REVEAL_PULSE_COLOR: Color = Color::from_rgba(255, 200, 0, 255);
+const SOUND_DUCKING_WINDOW: f64 = 0.1; // Seconds a played sound counts toward ducking
+const REVEAL_DELAY: f32 = 0.37; // Longest delay between revealing mines (seconds)
+const REVEAL_TOTAL_TIME: f32 = 6.0; // Longer reveal queues speed up to finish in about this long
+const WIN_WAVE_DELAY: f32 = 0.05; // Win wave delay per cell of distance from the board center (seconds)
 
 impl MinesweeperApp {
     /// Handles the wave/flood-fill animation for a cell.
@@ -45,7 +62,7 @@ impl MinesweeperApp {
                 return true; // Animation is still running, skip further drawing for this cell
             } else {
                 self.wave_timers_mut()[row][col] = None;
-                self.board_mut().uncover_cell(row, col);
+                self.board_mut().apply_move(Move::Reveal(row, col));
                 self.pop_timers_mut()[row][col] = Some(0.0);
                 spawn_particles(
                     &mut self.particles_mut(),
@@ -62,6 +79,12 @@ impl MinesweeperApp {
         false
     }
 
+    /// Returns whether a revealed number is drawn at progress `t` (0.0 to 1.0) of its pop
+    /// animation: only at the end by default, or throughout with instant numbers on.
+    pub fn pop_number_visible(&self, t: f32) -> bool {
+        self.instant_numbers() || t >= 1.0
+    }
+
     pub fn handle_pop_animation(
         &mut self,
         row: usize,
@@ -95,19 +118,24 @@ impl MinesweeperApp {
                     size,
                     uncovered_color,
                 );
-                draw_rectangle_lines(
-                    cx - size / 2.0,
-                    cy - size / 2.0,
-                    size,
-                    size,
-                    POP_LINE_WIDTH,
-                    DARKGRAY,
-                );
+                // Same border as the static cells (none when grid lines are off)
+                if self.grid_line_width() > 0.0 {
+                    draw_rectangle_lines(
+                        cx - size / 2.0,
+                        cy - size / 2.0,
+                        size,
+                        size,
+                        self.grid_line_width(),
+                        DARKGRAY,
+                    );
+                }
 
-                // Draw the number if the animation is finished
+                // Draw the number once the animation is finished (or all along, see
+                // `pop_number_visible`)
                 if let Cell::Number(n) = cell {
-                    if t >= 1.0 {
-                        self.draw_cell_number(n, cx, cy, cell_size);
+                    if self.pop_number_visible(t) {
+                        let dimmed = self.is_number_satisfied(row, col);
+                        self.draw_cell_number(n, cx, cy, cell_size, dimmed);
                     }
                 }
 
@@ -123,6 +151,129 @@ impl MinesweeperApp {
         false
     }
 
+    /// Returns the current scale (0.0 to `FLAG_OVERSHOOT`) of a flag being planted,
+    /// or 1.0 if the cell has no flag animation running.
+    /// The flag grows past full size, then settles back, like the pop animation.
+    pub fn flag_pop_scale(&self, row: usize, col: usize) -> f32 {
+        match self.flag_timers()[row][col] {
+            Some(timer) => {
+                let t = (timer / FLAG_ANIMATION_DURATION).min(1.0);
+                if t < FLAG_GROW_PHASE {
+                    FLAG_OVERSHOOT * t / FLAG_GROW_PHASE
+                } else {
+                    FLAG_OVERSHOOT
+                        - (FLAG_OVERSHOOT - 1.0) * ((t - FLAG_GROW_PHASE) / (1.0 - FLAG_GROW_PHASE))
+                }
+            }
+            None => 1.0,
+        }
+    }
+
+    /// Starts the celebratory win wave: every safe cell gets a wave timer proportional to its
+    /// distance from the board center, so the board ripples outward. When a cell's timer runs
+    /// out it is uncovered (if still covered) and pops, reusing the flood-fill wave animation.
+    /// Mines and cells already animating are left alone.
+    pub fn start_win_wave(&mut self) {
+        let (height, width) = (self.board().height(), self.board().width());
+        let center_row = (height as f32 - 1.0) / 2.0;
+        let center_col = (width as f32 - 1.0) / 2.0;
+        for row in 0..height {
+            for col in 0..width {
+                if self.board().cell(row, col) == Some(Cell::Mine)
+                    || self.wave_timers()[row][col].is_some()
+                {
+                    continue;
+                }
+                let distance =
+                    ((row as f32 - center_row).powi(2) + (col as f32 - center_col).powi(2)).sqrt();
+                self.wave_timers_mut()[row][col] = Some(distance * WIN_WAVE_DELAY);
+            }
+        }
+    }
+
+    /// Advances the flag-planting animation of a cell by `dt` seconds, clearing it when finished.
+    pub fn advance_flag_timer(&mut self, row: usize, col: usize, dt: f32) {
+        if let Some(timer) = self.flag_timers()[row][col] {
+            let timer = timer + dt;
+            self.flag_timers_mut()[row][col] = if timer >= FLAG_ANIMATION_DURATION {
+                None
+            } else {
+                Some(timer)
+            };
+        }
+    }
+
+    /// Starts the sideways shake of a cell whose flag was refused by the flag limit.
+    pub fn start_flag_refusal(&mut self, row: usize, col: usize) {
+        self.set_flag_refusal(Some((row, col, 0.0)));
+    }
+
+    /// Returns the sideways offset (in pixels) of the given cell from the refused-flag shake;
+    /// 0 for every other cell. The shake swings back and forth and dies down as it ends.
+    pub fn flag_refusal_offset(&self, row: usize, col: usize) -> f32 {
+        match self.flag_refusal() {
+            Some((r, c, elapsed)) if (r, c) == (row, col) => {
+                let progress = (elapsed / FLAG_REFUSAL_DURATION).min(1.0);
+                let swing = (progress * FLAG_REFUSAL_SHAKES * std::f32::consts::TAU).sin();
+                swing * FLAG_REFUSAL_AMPLITUDE * (1.0 - progress)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Advances the refused-flag shake by `dt` seconds, clearing it when finished.
+    pub fn advance_flag_refusal(&mut self, dt: f32) {
+        if let Some((row, col, elapsed)) = self.flag_refusal() {
+            let elapsed = elapsed + dt;
+            let refusal = (elapsed < FLAG_REFUSAL_DURATION).then_some((row, col, elapsed));
+            self.set_flag_refusal(refusal);
+        }
+    }
+
+    /// Starts the pulsing outline on a cell the player just revealed (if the pulse is on),
+    /// replacing the previous one.
+    pub fn start_reveal_pulse(&mut self, row: usize, col: usize) {
+        if self.reveal_pulse() {
+            self.set_last_revealed(Some((row, col, 0.0)));
+        }
+    }
+
+    /// Returns the opacity (0.0 to 1.0) of the reveal pulse outline: it throbs
+    /// `REVEAL_PULSE_COUNT` times while fading out. None if no cell is pulsing.
+    pub fn reveal_pulse_alpha(&self) -> Option<f32> {
+        let (_, _, elapsed) = self.last_revealed()?;
+        let progress = (elapsed / REVEAL_PULSE_DURATION).min(1.0);
+        let throb = 0.5 + 0.5 * (progress * REVEAL_PULSE_COUNT * std::f32::consts::TAU).cos();
+        Some(throb * (1.0 - progress))
+    }
+
+    /// Advances the reveal pulse by `dt` seconds, clearing it when finished or once the game
+    /// is no longer running.
+    pub fn advance_reveal_pulse(&mut self, dt: f32) {
+        if let Some((row, col, elapsed)) = self.last_revealed() {
+            let elapsed = elapsed + dt;
+            let running = self.state() == GameState::Running;
+            let pulse = (running && elapsed < REVEAL_PULSE_DURATION).then_some((row, col, elapsed));
+            self.set_last_revealed(pulse);
+        }
+    }
+
+    /// Draws the reveal pulse outline over the last revealed cell. It is drawn after the
+    /// cells, so cells still animating their reveal don't hide it.
+    pub fn draw_reveal_pulse(&self, cell_size: f32) {
+        let (Some((row, col, _)), Some(alpha)) = (self.last_revealed(), self.reveal_pulse_alpha())
+        else {
+            return;
+        };
+        let x = col as f32 * cell_size;
+        let y = row as f32 * cell_size + TOP_BAR_HEIGHT;
+        let color = Color {
+            a: alpha,
+            ..REVEAL_PULSE_COLOR
+        };
+        draw_rectangle_lines(x, y, cell_size, cell_size, REVEAL_PULSE_WIDTH, color);
+    }
+
     pub fn spawn_shockwave(&mut self, row: usize, col: usize, cell_size: f32) {
         // Create a shockwave animation effect centered on the given cell.
         let x = col as f32 * cell_size + cell_size / 2.0;
@@ -156,7 +307,147 @@ impl MinesweeperApp {
         });
     }
 
-    /// Reveals mines one by one with animation after game over.
+    /// Starts the red full-window flash shown when a mine is hit (if enabled).
+    pub fn start_loss_flash(&mut self) {
+        if self.loss_flash_enabled() {
+            self.set_loss_flash(Some(0.0));
+        }
+    }
+
+    /// Advances the loss flash by `dt` seconds and returns its opacity, or None once it has
+    /// faded out.
+    pub fn update_loss_flash(&mut self, dt: f32) -> Option<f32> {
+        let elapsed = self.loss_flash()? + dt;
+        if elapsed >= LOSS_FLASH_DURATION {
+            self.set_loss_flash(None);
+            return None;
+        }
+        self.set_loss_flash(Some(elapsed));
+        Some(LOSS_FLASH_ALPHA * (1.0 - elapsed / LOSS_FLASH_DURATION))
+    }
+
+    /// Draws the loss flash as a translucent red rectangle over the whole window.
+    pub fn draw_loss_flash(&mut self, dt: f32) {
+        if let Some(alpha) = self.update_loss_flash(dt) {
+            let color = Color::new(1.0, 0.0, 0.0, alpha);
+            draw_rectangle(0.0, 0.0, screen_width(), screen_height(), color);
+        }
+    }
+
+    /// Fills the mine reveal queue after the mine at (row, col) was clicked.
+    /// The queue holds all other unflagged mines (`true`) and all wrongly flagged cells (`false`),
+    /// in the order they will be revealed, as selected by `order`.
+    pub fn build_reveal_queue(&mut self, row: usize, col: usize, order: RevealOrder) {
+        // Collect all mines except flagged ones and the one just clicked.
+        // We use a temporary variable to avoid borrowing self.mine_reveal_queue and self.board at the same time,
+        // which would cause a Rust borrow checker error.
+        let mut new_queue: Vec<(usize, usize, bool)> = self
+            .board()
+            .mine_positions()
+            .iter()
+            .cloned()
+            .filter(|&(r2, c2)| {
+                self.board().cell_state(r2, c2) != Some(CellState::Flagged)
+                    && !(r2 == row && c2 == col)
+            })
+            .map(|(r, c)| (r, c, true))
+            .collect();
+
+        // Add wrongly flagged cells to the queue.
+        let wrong_flags: Vec<_> = (0..self.board().height())
+            .flat_map(|r| (0..self.board().width()).map(move |c| (r, c)))
+            .filter(|&(r, c)| {
+                self.board().cell_state(r, c) == Some(CellState::Flagged)
+                    && self.board().cell(r, c) != Some(Cell::Mine)
+            })
+            .map(|(r, c)| (r, c, false))
+            .collect();
+        new_queue.extend(wrong_flags);
+
+        let distance = |&(r, c, _): &(usize, usize, bool)| r.abs_diff(row) + c.abs_diff(col);
+        match order {
+            RevealOrder::Random => {
+                // Shuffle uniformly. Seeded games shuffle from their seed, so a replayed game
+                // also replays the same reveal order.
+                // Mine positions come from a HashSet, so sort first to make the shuffle input stable.
+                new_queue.sort();
+                match self.seed() {
+                    Some(seed) => new_queue.shuffle(&mut StdRng::seed_from_u64(seed)),
+                    None => new_queue.shuffle(&mut ::rand::thread_rng()),
+                }
+            }
+            RevealOrder::Nearest => new_queue.sort_by_key(distance),
+            RevealOrder::Farthest => {
+                new_queue.sort_by_key(|cell| std::cmp::Reverse(distance(cell)))
+            }
+            RevealOrder::RowMajor => new_queue.sort_by_key(|&(r, c, _)| (r, c)),
+        }
+
+        // Large boards have many more mines to show, so speed up to keep the wait bounded
+        let delay = (REVEAL_TOTAL_TIME / new_queue.len().max(1) as f32).min(REVEAL_DELAY);
+        self.set_mine_reveal_delay(delay);
+        *self.mine_reveal_queue_mut() = new_queue;
+    }
+
+    /// Advances the game-over mine reveal by `dt` seconds and returns the entries that are due,
+    /// removed from the queue: one entry every `mine_reveal_delay` seconds, or the whole queue
+    /// at once when `skip_mine_reveal` is on. Returns nothing unless the game state is `GameOver`.
+    pub fn mine_reveal_step(
+        &mut self,
+        mine_reveal_timer: &mut f32,
+        dt: f32,
+    ) -> Vec<(usize, usize, bool)> {
+        if self.state() != GameState::GameOver || self.mine_reveal_queue().is_empty() {
+            *mine_reveal_timer = 0.0;
+            return Vec::new();
+        }
+        if self.skip_mine_reveal() {
+            return std::mem::take(self.mine_reveal_queue_mut());
+        }
+        *mine_reveal_timer += dt;
+        let delay = self.mine_reveal_delay();
+        let mut due = Vec::new();
+        while *mine_reveal_timer >= delay && !self.mine_reveal_queue().is_empty() {
+            *mine_reveal_timer -= delay;
+            due.push(self.mine_reveal_queue_mut().remove(0));
+        }
+        due
+    }
+
+    /// Returns the volume for an effect sound played at `now` with volume `base`, and records
+    /// the play. Each sound played in the last `SOUND_DUCKING_WINDOW` seconds lowers it
+    /// (`base / (1 + recent)`), so bursts like the game-over cascade don't clip.
+    pub fn ducked_volume(&mut self, base: f32, now: f64) -> f32 {
+        let times = self.recent_sound_times_mut();
+        while times
+            .front()
+            .is_some_and(|&t| now - t > SOUND_DUCKING_WINDOW)
+        {
+            times.pop_front();
+        }
+        let volume = base / (1 + times.len()) as f32;
+        times.push_back(now);
+        volume
+    }
+
+    /// Plays an effect sound at volume `base`, ducked by the sounds played just before it.
+    /// Does nothing while the sound is off.
+    pub fn play_ducked_sound(&mut self, sound: &Sound, base: f32) {
+        if !self.sound() {
+            return;
+        }
+        let volume = self.ducked_volume(base, get_time());
+        play_sound(
+            sound,
+            PlaySoundParams {
+                looped: false,
+                volume,
+            },
+        );
+    }
+
+    /// Reveals mines one by one with animation after game over (see `mine_reveal_step`).
+    /// Each sound plays at most once per frame, so skipping the cascade doesn't stack sounds.
     pub fn reveal_mines_with_animation(
         &mut self,
         cell_size: f32,
@@ -164,49 +455,34 @@ impl MinesweeperApp {
         bomb_sound: &Sound,
         mistake_sound: &Sound,
     ) {
-        if self.state() == GameState::GameOver && !self.mine_reveal_queue().is_empty() {
-            *mine_reveal_timer += get_frame_time();
-            if *mine_reveal_timer >= REVEAL_DELAY {
-                *mine_reveal_timer = 0.0;
-                if let Some((r, c, is_mine)) = self.mine_reveal_queue_mut().pop() {
-                    if is_mine {
-                        if self.sound() {
-                            play_sound(
-                                bomb_sound,
-                                PlaySoundParams {
-                                    looped: false,
-                                    volume: 0.7,
-                                },
-                            );
-                        }
-                        self.board_mut().uncover_cell(r, c);
-                        spawn_particles(
-                            &mut self.particles_mut(),
-                            r,
-                            c,
-                            cell_size,
-                            true,
-                            None,
-                            TOP_BAR_HEIGHT,
-                        );
-                        self.spawn_shockwave(r, c, cell_size);
-                    } else {
-                        if self.sound() {
-                            play_sound(
-                                mistake_sound,
-                                PlaySoundParams {
-                                    looped: false,
-                                    volume: 0.7,
-                                },
-                            );
-                        }
-                        // Do NOT uncover, just mark for red X
-                        self.wrong_flags_mut().push((r, c));
-                    }
+        let due = self.mine_reveal_step(mine_reveal_timer, get_frame_time());
+        let mut played_bomb = false;
+        let mut played_mistake = false;
+        for (r, c, is_mine) in due {
+            if is_mine {
+                if !played_bomb {
+                    self.play_ducked_sound(bomb_sound, 0.7);
+                    played_bomb = true;
+                }
+                self.board_mut().apply_move(Move::Reveal(r, c));
+                spawn_particles(
+                    &mut self.particles_mut(),
+                    r,
+                    c,
+                    cell_size,
+                    true,
+                    None,
+                    TOP_BAR_HEIGHT,
+                );
+                self.spawn_shockwave(r, c, cell_size);
+            } else {
+                if !played_mistake {
+                    self.play_ducked_sound(mistake_sound, 0.7);
+                    played_mistake = true;
                 }
+                // Do NOT uncover, just mark for red X
+                self.wrong_flags_mut().push((r, c));
             }
-        } else {
-            *mine_reveal_timer = 0.0;
         }
     }
 }
@@ -0,0 +1,214 @@
+//! Asset loading for Minesweeper.
+//!
+//! This module bundles every texture and sound the game needs into an `Assets` struct and loads
+//! them without panicking. Each asset that fails to load is replaced by a placeholder (a simple
+//! drawn icon for textures, silence for sounds) and its path is recorded in an `AssetError`, so
+//! the game stays playable and can show the player which files are missing.
+
+use super::MinesweeperApp;
+use crate::gui_skin::Skin;
+use macroquad::audio::*;
+use macroquad::prelude::*;
+use std::fmt;
+
+// --- Sound file paths ---
+const FLAG_SOUND_PATH: &str = "assets/flag.wav";
+const BOMB_SOUND_PATH: &str = "assets/bomb.wav";
+const REMOVE_FLAG_SOUND_PATH: &str = "assets/remove_flag.wav";
+const FLIP_SOUND_PATH: &str = "assets/flip.wav";
+const WAVE_SOUND_PATH: &str = "assets/wave.wav";
+const MISTAKE_SOUND_PATH: &str = "assets/mistake.wav";
+const GAME_OVER_SOUND_PATH: &str = "assets/game_over.wav";
+const WIN_SOUND_PATH: &str = "assets/win.wav";
+const CHORD_SOUND_PATH: &str = "assets/chord.wav";
+const FANFARE_SOUND_PATH: &str = "assets/fanfare.wav";
+
+// --- Placeholder constants ---
+const PLACEHOLDER_ICON_SIZE: u16 = 64; // Size (pixels) of generated placeholder icons
+const SILENT_SAMPLE_RATE: u32 = 8000; // Sample rate of the generated silent sound
+const SILENT_SAMPLES: u32 = 16; // Length of the generated silent sound
+
+// --- Notice panel constants (missing assets, screenshot results) ---
+const PANEL_MARGIN: f32 = 8.0; // Distance from the bottom/left of the window
+const PANEL_PADDING: f32 = 6.0;
+const PANEL_LINE_HEIGHT: f32 = 18.0;
+const PANEL_FONT_SIZE: f32 = 18.0;
+const PANEL_BG_COLOR: Color = Color::from_rgba(120, 0, 0, 200);
+const PANEL_TEXT_COLOR: Color = WHITE;
+
+/// Every sound effect used by the game.
+pub struct Sounds {
+    pub flag: Sound,
+    pub bomb: Sound,
+    pub remove_flag: Sound,
+    pub flip: Sound,
+    pub wave: Sound,
+    pub mistake: Sound,
+    pub game_over: Sound,
+    pub win: Sound,
+    pub chord: Sound,
+    pub fanfare: Sound, // Win-streak milestones
+}
+
+/// All textures (the default skin) and sounds used by the game.
+pub struct Assets {
+    pub skin: Skin,
+    pub sounds: Sounds,
+}
+
+/// Returned when one or more assets failed to load.
+/// Holds the paths that failed, plus a complete asset bundle in which every failed
+/// asset was replaced by a placeholder, so the game can keep running.
+pub struct AssetError {
+    pub missing: Vec<String>,
+    pub assets: Assets,
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Missing assets: {}", self.missing.join(", "))
+    }
+}
+
+impl Assets {
+    /// Loads all textures and sounds.
+    /// Returns `Err` with the list of failed paths (and a placeholder-filled bundle) if any load failed.
+    pub async fn load() -> Result<Assets, AssetError> {
+        let mut missing = Vec::new();
+        let skin = Skin::load_default(&mut missing).await;
+        let sounds = Sounds {
+            flag: load_sound_or_silence(FLAG_SOUND_PATH, &mut missing).await,
+            bomb: load_sound_or_silence(BOMB_SOUND_PATH, &mut missing).await,
+            remove_flag: load_sound_or_silence(REMOVE_FLAG_SOUND_PATH, &mut missing).await,
+            flip: load_sound_or_silence(FLIP_SOUND_PATH, &mut missing).await,
+            wave: load_sound_or_silence(WAVE_SOUND_PATH, &mut missing).await,
+            mistake: load_sound_or_silence(MISTAKE_SOUND_PATH, &mut missing).await,
+            game_over: load_sound_or_silence(GAME_OVER_SOUND_PATH, &mut missing).await,
+            win: load_sound_or_silence(WIN_SOUND_PATH, &mut missing).await,
+            chord: load_sound_or_silence(CHORD_SOUND_PATH, &mut missing).await,
+            fanfare: load_sound_or_silence(FANFARE_SOUND_PATH, &mut missing).await,
+        };
+        let assets = Assets { skin, sounds };
+        if missing.is_empty() {
+            Ok(assets)
+        } else {
+            Err(AssetError { missing, assets })
+        }
+    }
+}
+
+/// Loads a texture, or records the path as missing and returns a drawn placeholder icon.
+pub async fn load_texture_or_placeholder(path: &str, missing: &mut Vec<String>) -> Texture2D {
+    match load_texture(path).await {
+        Ok(texture) => texture,
+        Err(_) => {
+            missing.push(path.to_string());
+            placeholder_icon(path)
+        }
+    }
+}
+
+/// Loads a sound, or records the path as missing and returns a silent sound.
+async fn load_sound_or_silence(path: &str, missing: &mut Vec<String>) -> Sound {
+    match load_sound(path).await {
+        Ok(sound) => sound,
+        Err(_) => {
+            missing.push(path.to_string());
+            load_sound_from_bytes(&silent_wav())
+                .await
+                .expect("generated silent WAV should always load")
+        }
+    }
+}
+
+/// Draws a simple placeholder icon for a missing texture.
+/// Flags get a red triangle on a pole, mines a black disc, and other icons a dark ring.
+fn placeholder_icon(path: &str) -> Texture2D {
+    let size = PLACEHOLDER_ICON_SIZE as u32;
+    let mut image = Image::gen_image_color(PLACEHOLDER_ICON_SIZE, PLACEHOLDER_ICON_SIZE, BLANK);
+    let center = size as f32 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let (fx, fy) = (x as f32, y as f32);
+            let dist = ((fx - center).powi(2) + (fy - center).powi(2)).sqrt();
+            let color = if path.ends_with("flag.png") {
+                let on_pole = (14..20).contains(&x) && (8..58).contains(&y);
+                // Triangle pointing right from the pole, between y = 8 and y = 36
+                let in_flag = x >= 20
+                    && (8..=36).contains(&y)
+                    && (x - 20) as f32 <= 36.0 * (1.0 - (fy - 22.0).abs() / 14.0);
+                if in_flag {
+                    Some(RED)
+                } else if on_pole {
+                    Some(DARKGRAY)
+                } else {
+                    None
+                }
+            } else if path.ends_with("blast.png") {
+                (dist <= center * 0.7).then_some(BLACK)
+            } else {
+                (dist <= center * 0.8 && dist >= center * 0.6).then_some(DARKGRAY)
+            };
+            if let Some(color) = color {
+                image.set_pixel(x, y, color);
+            }
+        }
+    }
+    Texture2D::from_image(&image)
+}
+
+/// Builds a tiny silent mono 16-bit PCM WAV file in memory.
+fn silent_wav() -> Vec<u8> {
+    let data_len = SILENT_SAMPLES * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    wav.extend_from_slice(&SILENT_SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SILENT_SAMPLE_RATE * 2).to_le_bytes()); // Byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // Block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 0);
+    wav
+}
+
+impl MinesweeperApp {
+    /// Draws a panel at the bottom of the window listing the assets that failed to load.
+    /// Pressing Escape dismisses it.
+    pub fn draw_asset_error_panel(&mut self) {
+        if is_key_pressed(KeyCode::Escape) {
+            self.set_asset_error(None);
+        }
+        if let Some(message) = self.asset_error() {
+            let hint = "Placeholders are used instead. Press Esc to dismiss.";
+            self.draw_notice_panel(&[message, hint]);
+        }
+    }
+
+    /// Draws a panel of text lines at the bottom-left of the window, for notices the player
+    /// should see (missing assets, screenshot results).
+    pub fn draw_notice_panel(&self, lines: &[&str]) {
+        let width = lines
+            .iter()
+            .map(|line| measure_text(line, None, PANEL_FONT_SIZE as u16, 1.0).width)
+            .fold(0.0, f32::max)
+            + 2.0 * PANEL_PADDING;
+        let height = lines.len() as f32 * PANEL_LINE_HEIGHT + 2.0 * PANEL_PADDING;
+        let y = screen_height() - height - PANEL_MARGIN;
+        draw_rectangle(PANEL_MARGIN, y, width, height, PANEL_BG_COLOR);
+        for (i, line) in lines.iter().enumerate() {
+            self.draw_text_sharp(
+                line,
+                PANEL_MARGIN + PANEL_PADDING,
+                y + PANEL_PADDING + (i + 1) as f32 * PANEL_LINE_HEIGHT - 4.0,
+                PANEL_FONT_SIZE,
+                PANEL_TEXT_COLOR,
+            );
+        }
+    }
+}
@@ -6,10 +6,14 @@
 
 use super::MinesweeperApp;
 use crate::board::*;
-use crate::gui::GameState;
+use crate::gui::{GameState, NumberPalette, WaveOrder};
+use crate::gui_assets::Sounds;
 use crate::particle::*;
+use crate::replay::Replay;
+use crate::solver;
 use macroquad::audio::*;
 use macroquad::prelude::*;
+use std::collections::HashSet;
 
 // use crate::gui_animation::*;
 
@@ -28,7 +32,65 @@ const NUMBER_TEXT_Y_OFFSET: f32 = -4.0; // Vertical adjustment for centering tex
 const FLAG_ICON_SCALE: f32 = 0.7;
 const FLAG_XY_OFFSET: f32 = 6.0;
 const FLAG_LINE_WIDTH: f32 = 4.0;
+const MAYBE_FLAG_ALPHA: f32 = 0.45; // Opacity of the flag drawn on maybe-mine cells
+const MAYBE_DASH_LENGTH: f32 = 4.0;
+const MAYBE_DASH_WIDTH: f32 = 2.0;
+const MAYBE_DASH_COLOR: Color = Color::from_rgba(90, 60, 20, 255);
+const WIN_STREAK_MILESTONES: [u32; 3] = [5, 10, 25]; // Streaks celebrated with a fanfare
+const CORRECT_FLAG_COLOR: Color = Color::from_rgba(0, 170, 0, 255); // Check mark over correct flags
 const MINE_ICON_SCALE: f32 = 0.7;
+const CHORD_SWEEP_DELAY: f32 = 0.04; // Chord reveal delay per cell of distance from the number
+const MAX_WAVE_DISTANCE: usize = 12; // Flood-fill cells farther than this animate together
+const WAVE_STEP_DELAY: f32 = 0.05; // Flood-fill reveal delay per ring or column (seconds)
+const MAX_QUEUED_CLICKS: usize = 8; // Clicks beyond this are dropped, so a stuck game can't pile up input
+const SATISFIED_NUMBER_ALPHA: f32 = 0.35; // Opacity of numbers whose mines are all flagged
+const PRESSURE_FULL: f32 = 12.0; // Visible pressure at which the heatmap tint is strongest
+const PRESSURE_MAX_ALPHA: f32 = 0.55; // Opacity of the strongest heatmap tint
+const PROBABILITY_MAX_ALPHA: f32 = 0.5; // Opacity of the probability tint for a certain mine
+const PROBABILITY_FONT_SCALE: f32 = 0.35; // Proportion of cell size for the percentage text
+const PROBABILITY_MIN_CELL_SIZE: f32 = 24.0; // Smaller cells show only the tint, no percentage
+const HINT_OUTLINE_WIDTH: f32 = 4.0;
+const HINT_COLOR: Color = Color::from_rgba(0, 120, 255, 255); // Outline of the hinted cell
+const PROBABILITY_TEXT_COLOR: Color = Color::from_rgba(40, 40, 40, 255);
+
+// --- Number palettes ---
+// Okabe-Ito colors are distinguishable with the common forms of color blindness.
+const CLASSIC_NUMBER_COLORS: [Color; 8] =
+    [BLUE, GREEN, RED, DARKBLUE, MAROON, DARKGREEN, BLACK, GRAY];
+const OKABE_ITO_NUMBER_COLORS: [Color; 8] = [
+    Color::new(0.0, 0.447, 0.698, 1.0),   // Blue
+    Color::new(0.0, 0.620, 0.451, 1.0),   // Bluish green
+    Color::new(0.835, 0.369, 0.0, 1.0),   // Vermillion
+    Color::new(0.800, 0.475, 0.655, 1.0), // Reddish purple
+    Color::new(0.902, 0.624, 0.0, 1.0),   // Orange
+    Color::new(0.337, 0.706, 0.914, 1.0), // Sky blue
+    Color::new(0.0, 0.0, 0.0, 1.0),       // Black
+    Color::new(0.4, 0.4, 0.4, 1.0),       // Gray
+];
+
+// --- Number legend constants ---
+const LEGEND_MARGIN: f32 = 8.0; // Distance from the right edge of the board and the top bar
+const LEGEND_PADDING: f32 = 6.0;
+const LEGEND_LINE_HEIGHT: f32 = 20.0;
+const LEGEND_SWATCH_SIZE: f32 = 14.0;
+const LEGEND_FONT_SIZE: f32 = 20.0;
+const LEGEND_WIDTH: f32 = 56.0;
+const LEGEND_BG_COLOR: Color = Color::from_rgba(255, 255, 255, 220);
+
+impl NumberPalette {
+    /// Returns the color of the given cell number (1-8) in this palette.
+    /// Out-of-range numbers are drawn black.
+    pub fn number_color(self, n: u8) -> Color {
+        let colors = match self {
+            NumberPalette::Classic => &CLASSIC_NUMBER_COLORS,
+            NumberPalette::OkabeIto => &OKABE_ITO_NUMBER_COLORS,
+        };
+        match n {
+            1..=8 => colors[n as usize - 1],
+            _ => BLACK,
+        }
+    }
+}
 
 // All these are methods for MinesweeperApp
 impl MinesweeperApp {
@@ -40,9 +102,32 @@ impl MinesweeperApp {
         mine_texture: &Texture2D,
         win_sound: &Sound,
     ) {
+        // The peek is drawn from its own copy, so the real board and its animations are left
+        // alone underneath; it ends by itself if the game ends while it shows
+        if self.safe_peek_active() && self.state() != GameState::Running {
+            self.set_safe_peek(None);
+        }
+        if let Some(peek) = self.safe_peek() {
+            self.draw_plain_board(peek, cell_size, flag_texture, mine_texture);
+            return;
+        }
+        // Likewise for a replay step, which ends when the next game starts
+        if self.replay_view().is_some() && !self.game_finished() {
+            self.set_replay_view(None);
+        }
+        if let Some((_, board)) = self.replay_view() {
+            self.draw_plain_board(board, cell_size, flag_texture, mine_texture);
+            return;
+        }
+        // Only frontier cells have visible pressure, so the heatmap skips everything else
+        let frontier: HashSet<(usize, usize)> = if self.show_pressure_overlay() {
+            self.board().frontier_cells().into_iter().collect()
+        } else {
+            HashSet::new()
+        };
         for row in 0..self.board().height() {
             for col in 0..self.board().width() {
-                let x = col as f32 * cell_size;
+                let x = col as f32 * cell_size + self.flag_refusal_offset(row, col);
                 let y = row as f32 * cell_size + TOP_BAR_HEIGHT;
                 let is_even = (row + col) % 2 == 0;
                 let covered_color = if is_even {
@@ -55,11 +140,9 @@ impl MinesweeperApp {
                 } else {
                     UNCOVERED_COLOR_ODD
                 };
-                let cell_state = self
-                    .board()
-                    .cell_state(row, col)
-                    .unwrap_or(CellState::Covered);
-                let cell = self.board().cell(row, col).unwrap_or(Cell::Empty);
+                // In bounds by construction, so index the rows directly
+                let cell_state = self.board().states()[row][col];
+                let cell = self.board().cells()[row][col];
 
                 // Handle wave/flood-fill animation for this cell
                 if self.handle_wave_animation(row, col, cell_size, win_sound) {
@@ -72,11 +155,25 @@ impl MinesweeperApp {
 
                 // Draw cell background and border
                 let bg_color = match cell_state {
-                    CellState::Covered | CellState::Flagged => covered_color,
+                    CellState::Covered | CellState::Flagged | CellState::Maybe => covered_color,
                     CellState::Uncovered => uncovered_color,
                 };
                 draw_rectangle(x, y, cell_size, cell_size, bg_color);
-                draw_rectangle_lines(x, y, cell_size, cell_size, 2.0, DARKGRAY);
+                if frontier.contains(&(row, col)) {
+                    self.draw_pressure_tint(row, col, x, y, cell_size);
+                }
+                if self.show_probability_overlay() && cell_state == CellState::Covered {
+                    self.draw_probability(row, col, x, y, cell_size);
+                }
+                if self.grid_line_width() > 0.0 {
+                    let width = self.grid_line_width();
+                    draw_rectangle_lines(x, y, cell_size, cell_size, width, DARKGRAY);
+                }
+                // Outline the hinted cell until it is uncovered
+                if self.hint() == Some((row, col)) && cell_state == CellState::Covered {
+                    let width = HINT_OUTLINE_WIDTH;
+                    draw_rectangle_lines(x, y, cell_size, cell_size, width, HINT_COLOR);
+                }
 
                 // Draw the cell content (flag, mine, number, or nothing)
                 self.draw_cell_content(
@@ -90,10 +187,89 @@ impl MinesweeperApp {
                     flag_texture,
                     mine_texture,
                 );
+                self.advance_flag_timer(row, col, get_frame_time());
+            }
+        }
+        self.advance_flag_refusal(get_frame_time());
+        self.draw_reveal_pulse(cell_size);
+        self.advance_reveal_pulse(get_frame_time());
+    }
+
+    /// Draws another board (the safe-cell peek or a replay step) in place of the real one:
+    /// plain cells without overlays or animations (wave and pop timers stay paused meanwhile).
+    fn draw_plain_board(
+        &self,
+        board: &Board,
+        cell_size: f32,
+        flag_texture: &Texture2D,
+        mine_texture: &Texture2D,
+    ) {
+        for row in 0..board.height() {
+            for col in 0..board.width() {
+                let x = col as f32 * cell_size;
+                let y = row as f32 * cell_size + TOP_BAR_HEIGHT;
+                let cell_state = board.states()[row][col];
+                let cell = board.cells()[row][col];
+                let is_even = (row + col) % 2 == 0;
+                let bg_color = match (cell_state, is_even) {
+                    (CellState::Uncovered, true) => UNCOVERED_COLOR_EVEN,
+                    (CellState::Uncovered, false) => UNCOVERED_COLOR_ODD,
+                    (_, true) => COVERED_COLOR_EVEN,
+                    (_, false) => COVERED_COLOR_ODD,
+                };
+                draw_rectangle(x, y, cell_size, cell_size, bg_color);
+                if self.grid_line_width() > 0.0 {
+                    let width = self.grid_line_width();
+                    draw_rectangle_lines(x, y, cell_size, cell_size, width, DARKGRAY);
+                }
+                self.draw_cell_content(
+                    cell_state,
+                    cell,
+                    row,
+                    col,
+                    x,
+                    y,
+                    cell_size,
+                    flag_texture,
+                    mine_texture,
+                );
             }
         }
     }
 
+    /// Tints a covered cell red in proportion to its visible pressure (heatmap overlay).
+    fn draw_pressure_tint(&self, row: usize, col: usize, x: f32, y: f32, cell_size: f32) {
+        let pressure = self.board().visible_pressure(row, col);
+        if pressure <= 0.0 {
+            return;
+        }
+        let alpha = (pressure / PRESSURE_FULL).min(1.0) * PRESSURE_MAX_ALPHA;
+        draw_rectangle(x, y, cell_size, cell_size, Color::new(1.0, 0.0, 0.0, alpha));
+    }
+
+    /// Draws a covered cell's estimated mine probability: a green (safe) to red (mine) tint
+    /// and, when the cell is large enough, the percentage.
+    fn draw_probability(&self, row: usize, col: usize, x: f32, y: f32, cell_size: f32) {
+        let Some(&probability) = self.mine_probabilities().get(&(row, col)) else {
+            return;
+        };
+        let tint = Color::new(probability, 1.0 - probability, 0.0, PROBABILITY_MAX_ALPHA);
+        draw_rectangle(x, y, cell_size, cell_size, tint);
+        if cell_size < PROBABILITY_MIN_CELL_SIZE {
+            return;
+        }
+        let text = format!("{:.0}%", probability * 100.0);
+        let font_size = cell_size * PROBABILITY_FONT_SCALE;
+        let size = measure_text(&text, None, font_size as u16, 1.0);
+        self.draw_text_sharp(
+            &text,
+            x + (cell_size - size.width) / 2.0,
+            y + (cell_size + size.height) / 2.0,
+            font_size,
+            PROBABILITY_TEXT_COLOR,
+        );
+    }
+
     /// Draws the content inside a cell based on its state and value.
     fn draw_cell_content(
         &self,
@@ -109,34 +285,70 @@ impl MinesweeperApp {
     ) {
         match cell_state {
             CellState::Covered => {
-                // Covered cell: nothing to draw inside
+                // Covered cell: nothing to draw inside, except mines while editing a layout
+                if self.editor_mode() && cell == Cell::Mine {
+                    draw_texture_ex(
+                        mine_texture,
+                        x + (cell_size - cell_size * MINE_ICON_SCALE) / 2.0,
+                        y + (cell_size - cell_size * MINE_ICON_SCALE) / 2.0,
+                        WHITE,
+                        DrawTextureParams {
+                            dest_size: Some(Vec2::new(
+                                cell_size * MINE_ICON_SCALE,
+                                cell_size * MINE_ICON_SCALE,
+                            )),
+                            ..Default::default()
+                        },
+                    );
+                }
             }
             CellState::Flagged => {
-                // Draw the flag icon centered in the cell
+                // Draw the flag icon centered in the cell (scaled while it is being planted)
+                let flag_size = cell_size * FLAG_ICON_SCALE * self.flag_pop_scale(row, col);
                 draw_texture_ex(
                     flag_texture,
-                    x + (cell_size - cell_size * FLAG_ICON_SCALE) / 2.0,
-                    y + (cell_size - cell_size * FLAG_ICON_SCALE) / 2.0,
+                    x + (cell_size - flag_size) / 2.0,
+                    y + (cell_size - flag_size) / 2.0,
                     WHITE,
                     DrawTextureParams {
-                        dest_size: Some(Vec2::new(
-                            cell_size * FLAG_ICON_SCALE,
-                            cell_size * FLAG_ICON_SCALE,
-                        )),
+                        dest_size: Some(Vec2::new(flag_size, flag_size)),
                         ..Default::default()
                     },
                 );
+                let game_over =
+                    self.state() == GameState::GameOver || self.state() == GameState::Lost;
+                let x1 = x + FLAG_XY_OFFSET;
+                let y1 = y + FLAG_XY_OFFSET;
+                let x2 = x + cell_size - FLAG_XY_OFFSET;
+                let y2 = y + cell_size - FLAG_XY_OFFSET;
                 // If the game is over and this is a wrong flag, draw a red X over the flag
-                if (self.state() == GameState::GameOver || self.state() == GameState::Lost)
-                    && self.wrong_flags().contains(&(row, col))
-                {
-                    let x1 = x + FLAG_XY_OFFSET;
-                    let y1 = y + FLAG_XY_OFFSET;
-                    let x2 = x + cell_size - FLAG_XY_OFFSET;
-                    let y2 = y + cell_size - FLAG_XY_OFFSET;
+                if game_over && self.wrong_flags().contains(&(row, col)) {
                     draw_line(x1, y1, x2, y2, FLAG_LINE_WIDTH, RED);
                     draw_line(x1, y2, x2, y1, FLAG_LINE_WIDTH, RED);
                 }
+                // A flag on a mine (see `Board::flags_on_mines`) gets a green check mark
+                if game_over && cell == Cell::Mine {
+                    let (mid_x, mid_y) = (x1 + (x2 - x1) * 0.4, y2);
+                    let color = CORRECT_FLAG_COLOR;
+                    draw_line(x1, (y1 + y2) / 2.0, mid_x, mid_y, FLAG_LINE_WIDTH, color);
+                    draw_line(mid_x, mid_y, x2, y1, FLAG_LINE_WIDTH, color);
+                }
+            }
+            CellState::Maybe => {
+                // A faded flag with a dashed outline: a guess, not a committed flag
+                let flag_size = cell_size * FLAG_ICON_SCALE;
+                draw_texture_ex(
+                    flag_texture,
+                    x + (cell_size - flag_size) / 2.0,
+                    y + (cell_size - flag_size) / 2.0,
+                    Color::new(1.0, 1.0, 1.0, MAYBE_FLAG_ALPHA),
+                    DrawTextureParams {
+                        dest_size: Some(Vec2::new(flag_size, flag_size)),
+                        ..Default::default()
+                    },
+                );
+                let inset = cell_size - 2.0 * FLAG_XY_OFFSET;
+                draw_dashed_square(x + FLAG_XY_OFFSET, y + FLAG_XY_OFFSET, inset);
             }
             CellState::Uncovered => {
                 match cell {
@@ -163,6 +375,7 @@ impl MinesweeperApp {
                             x + cell_size / 2.0,
                             y + cell_size / 2.0,
                             cell_size,
+                            self.is_number_satisfied(row, col),
                         );
                     }
                     Cell::Empty => {
@@ -173,23 +386,36 @@ impl MinesweeperApp {
         }
     }
 
-    /// Draws a cell number with classic Minesweeper color and proper centering.
-    pub fn draw_cell_number(&self, n: u8, cx: f32, cy: f32, cell_size: f32) {
+    /// Returns true if satisfied-number dimming is on and the cell is a number
+    /// with exactly that many flagged neighbors (the player considers it "done").
+    pub fn is_number_satisfied(&self, row: usize, col: usize) -> bool {
+        if !self.dim_satisfied_numbers() {
+            return false;
+        }
+        match self.board().cell(row, col) {
+            Some(Cell::Number(n)) => self.board().tally_neighbors(row, col).flagged == n as usize,
+            _ => false,
+        }
+    }
+
+    /// Draws a cell number in the active palette's color with proper centering.
+    /// Dimmed numbers are drawn faded so unresolved numbers stand out.
+    pub fn draw_cell_number(&self, n: u8, cx: f32, cy: f32, cell_size: f32, dimmed: bool) {
         let label = n.to_string();
-        let text_color = match n {
-            1 => BLUE,
-            2 => GREEN,
-            3 => RED,
-            4 => DARKBLUE,
-            5 => MAROON,
-            6 => DARKGREEN,
-            7 => BLACK,
-            8 => GRAY,
-            _ => BLACK,
+        let text_color = self.number_palette().number_color(n);
+        let text_color = if dimmed {
+            Color::new(
+                text_color.r,
+                text_color.g,
+                text_color.b,
+                SATISFIED_NUMBER_ALPHA,
+            )
+        } else {
+            text_color
         };
         let font_size = cell_size * NUMBER_FONT_SCALE;
         let text_dim = measure_text(&label, None, font_size as u16, 1.0);
-        draw_text(
+        self.draw_text_sharp(
             &label,
             cx - text_dim.width / 2.0,
             cy + text_dim.height / 2.0 + NUMBER_TEXT_Y_OFFSET,
@@ -198,66 +424,418 @@ impl MinesweeperApp {
         );
     }
 
+    /// Draws a legend in the top-right corner of the board mapping each number to its
+    /// color swatch in the active palette (only when the legend is toggled on).
+    pub fn draw_number_legend(&self) {
+        if !self.show_number_legend() {
+            return;
+        }
+        let palette = self.number_palette();
+        let x = self.board().width() as f32 * self.cell_size() - LEGEND_WIDTH - LEGEND_MARGIN;
+        let y = TOP_BAR_HEIGHT + LEGEND_MARGIN;
+        let height = 8.0 * LEGEND_LINE_HEIGHT + 2.0 * LEGEND_PADDING;
+        draw_rectangle(x, y, LEGEND_WIDTH, height, LEGEND_BG_COLOR);
+        draw_rectangle_lines(x, y, LEGEND_WIDTH, height, 2.0, DARKGRAY);
+        for n in 1..=8u8 {
+            let line_y = y + LEGEND_PADDING + (n - 1) as f32 * LEGEND_LINE_HEIGHT;
+            let color = palette.number_color(n);
+            self.draw_text_sharp(
+                &n.to_string(),
+                x + LEGEND_PADDING,
+                line_y + LEGEND_LINE_HEIGHT - 5.0,
+                LEGEND_FONT_SIZE,
+                color,
+            );
+            draw_rectangle(
+                x + LEGEND_WIDTH - LEGEND_PADDING - LEGEND_SWATCH_SIZE,
+                line_y + (LEGEND_LINE_HEIGHT - LEGEND_SWATCH_SIZE) / 2.0,
+                LEGEND_SWATCH_SIZE,
+                LEGEND_SWATCH_SIZE,
+                color,
+            );
+        }
+    }
+
     /// Converts mouse position to (row, col) if within the board, else returns None.
     pub fn mouse_to_cell(&self, cell_size: f32) -> Option<(usize, usize)> {
         let (mx, my) = mouse_position();
-        if my < TOP_BAR_HEIGHT {
+        self.point_to_cell(mx, my, cell_size)
+    }
+
+    /// Converts a point in logical window coordinates (as returned by `mouse_position`,
+    /// which already divides out the DPI scale) to a cell, or None outside the board.
+    pub fn point_to_cell(&self, x: f32, y: f32, cell_size: f32) -> Option<(usize, usize)> {
+        if x < 0.0 || y < TOP_BAR_HEIGHT {
             return None;
         }
-        let col = (mx / cell_size) as usize;
-        let row = ((my - TOP_BAR_HEIGHT) / cell_size) as usize;
-        if row < self.board().height() && col < self.board().width() {
-            Some((row, col))
+        let col = (x / cell_size) as usize;
+        let row = ((y - TOP_BAR_HEIGHT) / cell_size) as usize;
+        self.board().in_bounds(row, col).then_some((row, col))
+    }
+
+    /// Returns the button a board click should act as.
+    /// In flagging mode a left-click flags (acts as a right-click), unless Shift is held,
+    /// which uncovers as usual. Right-clicks always flag.
+    pub fn effective_button(&self, button: MouseButton, shift_held: bool) -> MouseButton {
+        if self.flag_mode() && button == MouseButton::Left && !shift_held {
+            MouseButton::Right
         } else {
-            None
+            button
         }
     }
 
+    /// Records a board click so it can be processed on a later frame.
+    /// Clicks are buffered so rapid input isn't lost while animations are running.
+    pub fn queue_click(&mut self, row: usize, col: usize, button: MouseButton) {
+        if self.click_queue().len() < MAX_QUEUED_CLICKS {
+            self.click_queue_mut().push_back((row, col, button));
+        }
+    }
+
+    /// Pops the next queued click that still makes sense in the current game state.
+    /// Clicks on cells that were uncovered in the meantime (or that arrive after the game
+    /// ended) are skipped. Left clicks need a covered cell before or during the game;
+    /// right clicks need a covered or flagged cell while the game is running.
+    pub fn next_queued_click(&mut self) -> Option<(usize, usize, MouseButton)> {
+        while let Some((row, col, button)) = self.click_queue_mut().pop_front() {
+            let cell_state = self.board().cell_state(row, col);
+            let valid = match button {
+                MouseButton::Left => {
+                    let uncover = (self.state() == GameState::NotStarted
+                        || self.state() == GameState::Running)
+                        && cell_state == Some(CellState::Covered);
+                    // Left-clicking an uncovered number chords it
+                    let chord = self.state() == GameState::Running
+                        && cell_state == Some(CellState::Uncovered)
+                        && matches!(self.board().cell(row, col), Some(Cell::Number(_)));
+                    uncover || chord
+                }
+                MouseButton::Right => {
+                    self.state() == GameState::Running
+                        && matches!(
+                            cell_state,
+                            Some(CellState::Covered | CellState::Flagged | CellState::Maybe)
+                        )
+                }
+                _ => false,
+            };
+            if valid {
+                return Some((row, col, button));
+            }
+        }
+        None
+    }
+
     /// Handles all logic for a left mouse click on the board.
     /// This includes starting the timer, placing mines on first click,
-    /// handling mine clicks, empty cell clicks (flood fill), and number cell clicks.
+    /// handling mine clicks, empty cell clicks (flood fill), number cell clicks,
+    /// and chording on already uncovered numbers.
     pub fn handle_left_click(
         &mut self,
         row: usize,
         col: usize,
         cell_size: f32,
         mine_reveal_timer: &mut f32,
-        bomb_sound: &Sound,
-        flip_sound: &Sound,
-        wave_sound: &Sound,
-        win_sound: &Sound,
+        sounds: &Sounds,
     ) {
-        // On the first click, start the timer, place mines, and set the game state to running
-        if self.state() == GameState::NotStarted {
+        if self.countdown().is_some() {
+            return; // The board isn't clickable until the start countdown ends
+        }
+        if self.board().cell_state(row, col) == Some(CellState::Uncovered) {
+            if !self.register_chord_click(row, col, get_time()) {
+                return; // Waiting for the second click of a double-click
+            }
+            self.handle_chord_click(
+                row,
+                col,
+                mine_reveal_timer,
+                &sounds.bomb,
+                &sounds.chord,
+                &sounds.win,
+            );
+            return;
+        }
+        // With the opening assist on, the first click opens the preferred cell wherever it lands
+        let (row, col) = if self.state() == GameState::NotStarted && self.opening_assist() {
+            self.preferred_opening()
+        } else {
+            (row, col)
+        };
+        // A reveal never counts as the first half of a double-click, so a quick second click
+        // on the number it uncovered doesn't chord straight away
+        self.set_last_click(None);
+        self.set_last_reveal_cell(Some((row, col)));
+        self.start_reveal_pulse(row, col);
+        // On the first click, start the timer (unless the countdown already started it),
+        // place mines, and set the game state to running
+        if self.state() == GameState::NotStarted && self.start_time() == 0.0 {
             self.set_start_time(get_time());
-            self.board_mut().place_mines_avoiding(row, col);
-            self.board_mut().calculate_numbers();
-            self.set_state(GameState::Running);
         }
-        // Handle what was clicked
-        match self.board().cell(row, col) {
-            Some(Cell::Mine) => {
-                self.handle_mine_click(row, col, cell_size, mine_reveal_timer, bomb_sound)
+        self.prepare_reveal(row, col);
+        if self.absorb_mine(row, col) {
+            return;
+        }
+        // Handle what the reveal found
+        match self.play_reveal(row, col) {
+            RevealOutcome::Mine => {
+                self.handle_mine_click(row, col, cell_size, mine_reveal_timer, &sounds.bomb)
+            }
+            RevealOutcome::Empty { flooded } => {
+                self.handle_empty_click(&flooded, cell_size, &sounds.wave, &sounds.win)
             }
-            Some(Cell::Empty) => {
-                self.handle_empty_click(row, col, cell_size, wave_sound, win_sound)
+            RevealOutcome::Number(_) => {
+                self.handle_number_click(row, col, cell_size, &sounds.flip, &sounds.win)
+            }
+            RevealOutcome::AlreadyRevealed | RevealOutcome::Flagged => {}
+        }
+    }
+
+    /// Returns whether hitting `count` mines at once would be forgiven: always in zen mode,
+    /// otherwise only if the safe-reveal budget has that many lives left.
+    pub fn can_absorb_mines(&self, count: usize) -> bool {
+        self.zen_mode() || self.safe_budget() >= count
+    }
+
+    /// In zen mode, or while the safe-reveal budget lasts, undoes the fatal reveal of the mine
+    /// at (row, col) and flags it instead, so the game keeps running. Outside zen mode this
+    /// costs one life of the budget. Covers mines about to be clicked as well as mines a chord
+    /// has just uncovered. Returns whether a mine was absorbed.
+    pub fn absorb_mine(&mut self, row: usize, col: usize) -> bool {
+        if self.board().cell(row, col) != Some(Cell::Mine) || !self.can_absorb_mines(1) {
+            return false;
+        }
+        if !self.zen_mode() {
+            self.set_safe_budget(self.safe_budget() - 1);
+        }
+        if self.board().cell_state(row, col) == Some(CellState::Uncovered) {
+            self.play_move(Move::Absorb(row, col));
+        } else {
+            self.play_move(Move::Flag(row, col));
+        }
+        self.flag_timers_mut()[row][col] = Some(0.0);
+        true
+    }
+
+    /// Records a left-click on the uncovered cell (row, col) at time `now` and returns whether
+    /// it should chord. With double-click chording off every click chords; with it on, only the
+    /// second of two clicks on the same cell within the double-click window does.
+    pub fn register_chord_click(&mut self, row: usize, col: usize, now: f64) -> bool {
+        if !self.double_click_chord() {
+            return true;
+        }
+        let is_double = matches!(
+            self.last_click(),
+            Some((time, r, c)) if (r, c) == (row, col) && now - time <= self.double_click_window()
+        );
+        self.set_last_click(if is_double {
+            None
+        } else {
+            Some((now, row, col))
+        });
+        is_double
+    }
+
+    /// Emergency help for stuck beginners: reveals the covered safe cell nearest the player's
+    /// last reveal click (or the board center), with the usual pop or wave animation, and
+    /// counts the help for this game. Uses the real mine layout, not deduction.
+    /// Returns the revealed cell; the caller checks for a win afterwards.
+    pub fn reveal_one_safe(&mut self) -> Option<(usize, usize)> {
+        if self.state() != GameState::Running || self.countdown().is_some() {
+            return None;
+        }
+        let center = (self.board().height() / 2, self.board().width() / 2);
+        let (from_row, from_col) = self.last_reveal_cell().unwrap_or(center);
+        let (row, col) = self.board().nearest_safe_covered(from_row, from_col)?;
+        let result = self.play_move(Move::Reveal(row, col));
+        if result.revealed.len() == 1 {
+            self.pop_timers_mut()[row][col] = Some(0.0);
+        } else {
+            self.assign_wave_delays(&result.revealed, self.wave_order());
+        }
+        self.set_safe_reveals_used(self.safe_reveals_used() + 1);
+        self.update_forced_guess();
+        self.update_mine_probabilities();
+        Some((row, col))
+    }
+
+    /// Makes sure mines are placed before a reveal at the given cell.
+    /// On the first click, mines are placed around it from a fresh seed and the game starts.
+    /// With the first-click grace on, the first reveal after a reset also moves any mines that
+    /// were placed earlier (e.g. by the auto-open) out of the way, so it opens an empty region.
+    pub fn prepare_reveal(&mut self, row: usize, col: usize) {
+        if self.state() == GameState::NotStarted {
+            // Capture a fresh seed so the game can be shared and replayed
+            let seed: u64 = ::rand::random();
+            self.place_seeded_mines(row, col, seed);
+            self.set_state(GameState::Running);
+        } else if self.first_reveal_pending()
+            && self.first_click_grace()
+            && self.board_mut().clear_opening(row, col) > 0
+        {
+            // The seed no longer reproduces this layout
+            self.set_seed(None);
+            self.set_replay(None);
+        }
+        self.set_first_reveal_pending(false);
+    }
+
+    /// Plays one auto-solver move (see `solver::solve_step`) with the usual animations:
+    /// removes a wrong flag, flags a proven mine, or reveals a proven-safe cell.
+    /// Returns whether a move was made. When no certain move exists, nothing happens and
+    /// the "Guess required" indicator is refreshed; the caller checks for a win afterwards.
+    pub fn auto_solve_step(&mut self) -> bool {
+        if self.state() != GameState::Running || self.countdown().is_some() {
+            return false;
+        }
+        let Some(mv) = solver::solve_step(self.board()) else {
+            self.update_forced_guess();
+            return false;
+        };
+        let result = self.play_move(mv);
+        match mv {
+            Move::Reveal(row, col) if result.revealed.len() == 1 => {
+                self.pop_timers_mut()[row][col] = Some(0.0);
             }
-            _ => self.handle_number_click(row, col, cell_size, flip_sound, win_sound),
+            Move::Reveal(..) => self.assign_wave_delays(&result.revealed, self.wave_order()),
+            Move::Flag(row, col) => self.flag_timers_mut()[row][col] = Some(0.0),
+            _ => {}
+        }
+        self.update_forced_guess();
+        self.update_mine_probabilities();
+        true
+    }
+
+    /// Returns the cell the opening assist opens on the current board size: the cell set with
+    /// `set_preferred_opening` for this size if it is on the board, otherwise the board center.
+    pub fn preferred_opening(&self) -> (usize, usize) {
+        let (height, width) = (self.board().height(), self.board().width());
+        match self.preferred_openings().get(&self.board_size()) {
+            Some(&(row, col)) if row < height && col < width => (row, col),
+            _ => (height / 2, width / 2),
+        }
+    }
+
+    /// Performs a safe opening reveal without waiting for the player's first click.
+    /// Mines are placed around the auto-open cell (the chosen cell, or the board center),
+    /// which is always empty, and its region is flood-filled with the usual wave animation.
+    /// The game is left `Running`; the timer starts on the next frame.
+    pub fn auto_open_start(&mut self) {
+        let (height, width) = (self.board().height(), self.board().width());
+        if height == 0 || width == 0 {
+            return;
+        }
+        let (row, col) = match self.auto_open_cell() {
+            Some((r, c)) if r < height && c < width => (r, c),
+            _ => (height / 2, width / 2),
+        };
+        let seed: u64 = ::rand::random();
+        self.place_seeded_mines(row, col, seed);
+        self.reveal_opening(row, col);
+    }
+
+    /// Returns whether the game has ended (lost, with or without its reveal still running, or
+    /// won).
+    fn game_finished(&self) -> bool {
+        matches!(
+            self.state(),
+            GameState::GameOver | GameState::Won | GameState::Lost
+        )
+    }
+
+    /// Steps the replay view of a finished game by `delta` moves and returns the step now
+    /// shown (see `Replay::board_at`). The first step back starts from the last move; stepping
+    /// forward past the last move goes back to the real board and returns None. Does nothing
+    /// (and returns None) while the game is running or if it has no replay.
+    pub fn step_replay_view(&mut self, delta: isize) -> Option<usize> {
+        let replay = self.replay().filter(|_| self.game_finished())?;
+        let current = self.replay_view().map_or(replay.len(), |(step, _)| *step);
+        let step = current.saturating_add_signed(delta);
+        if step >= replay.len() {
+            self.set_replay_view(None);
+            return None;
         }
+        let board = replay.board_at(step);
+        self.set_replay_view(Some((step, board)));
+        Some(step)
+    }
+
+    /// Places the mines around the given cell from a seed, calculates numbers, and starts
+    /// a replay of the game.
+    fn place_seeded_mines(&mut self, row: usize, col: usize, seed: u64) {
+        self.set_seed(Some(seed));
+        self.board_mut().place_mines_avoiding_seeded(row, col, seed);
+        self.board_mut().calculate_numbers();
+        let board = self.board();
+        let (width, height, mines) = (board.width(), board.height(), board.mines());
+        let mut replay = Replay::new(width, height, mines, seed, (row, col));
+        replay.set_topology(board.topology());
+        self.set_replay(Some(replay));
+    }
+
+    /// Starts the daily puzzle for the current board size and the given date
+    /// (see `Board::daily`). Its opening at the board center is revealed right away,
+    /// and the game is left `Running`; the timer starts on the next frame.
+    pub fn start_daily(&mut self, date: &str) {
+        let size = self.board_size();
+        let (width, height, _) = size.params();
+        // Same placement as `Board::daily`, so the game also gets a replay
+        self.start_seeded_game(height / 2, width / 2, Board::daily_seed(size, date));
+    }
+
+    /// Restarts the current layout: the mines are placed again from the game's seed and its
+    /// opening is revealed, so the board is exactly the one just played.
+    /// Returns false (and changes nothing) if the layout has no seed to replay, e.g. a
+    /// hand-made layout or one changed by the first-click grace.
+    pub fn retry_board(&mut self) -> bool {
+        let Some(replay) = self.replay() else {
+            return false;
+        };
+        let (seed, (row, col)) = (replay.seed(), replay.opening());
+        self.start_seeded_game(row, col, seed);
+        true
+    }
+
+    /// Resets the game and starts it on the layout placed from `seed` around (row, col),
+    /// with that opening already revealed.
+    fn start_seeded_game(&mut self, row: usize, col: usize, seed: u64) {
+        self.reset_game();
+        let (width, height, mines) = self.board_size().params();
+        let topology = self.board().topology();
+        *self.board_mut() = Board::new(width, height, mines);
+        self.board_mut().set_topology(topology);
+        self.clear_timers(); // Drop any auto-open wave
+        self.place_seeded_mines(row, col, seed);
+        self.set_first_reveal_pending(false); // Keep the seeded layout as it is
+        self.reveal_opening(row, col);
+        self.update_forced_guess();
+        self.update_mine_probabilities();
+    }
+
+    /// Starts the game by revealing the opening at the given cell (mines must already be
+    /// placed around it), with the usual wave animation.
+    fn reveal_opening(&mut self, row: usize, col: usize) {
+        self.set_state(GameState::Running);
+        let result = self.play_move(Move::Reveal(row, col));
+        self.assign_wave_delays(&result.revealed, self.wave_order());
     }
 
-    /// Handles all logic for a right mouse click on the board (flag/unflag).
+    /// Handles all logic for a right mouse click on the board.
+    /// Right-clicks cycle a cell through covered, flagged, and maybe-mine (`CellState::Maybe`).
     pub fn handle_right_click(
         &mut self,
         row: usize,
         col: usize,
         flag_sound: &Sound,
         remove_flag_sound: &Sound,
+        mistake_sound: &Sound,
     ) {
-        match self.board().cell_state(row, col) {
-            Some(CellState::Covered) => {
-                self.board_mut().flag_cell(row, col);
-                // Play flag sound when flag is placed
+        if self.countdown().is_some() {
+            return;
+        }
+        match self.play_flag_toggle(row, col) {
+            Some(FlagChange::Flagged) => {
+                self.flag_timers_mut()[row][col] = Some(0.0); // Start the planting animation
+                                                              // Play flag sound when flag is placed
                 if self.sound() {
                     play_sound(
                         flag_sound,
@@ -268,8 +846,8 @@ impl MinesweeperApp {
                     );
                 }
             }
-            Some(CellState::Flagged) => {
-                self.board_mut().unflag_cell(row, col);
+            Some(FlagChange::Unflagged) => {
+                self.flag_timers_mut()[row][col] = None; // Unflagging cancels any running animation
                 if self.sound() {
                     play_sound(
                         remove_flag_sound,
@@ -280,15 +858,28 @@ impl MinesweeperApp {
                     );
                 }
             }
-            _ => {}
+            None => {
+                // The flag limit is reached: shake the cell and play a quiet error sound
+                self.start_flag_refusal(row, col);
+                if self.sound() {
+                    play_sound(
+                        mistake_sound,
+                        PlaySoundParams {
+                            looped: false,
+                            volume: 0.3,
+                        },
+                    );
+                }
+            }
+            Some(FlagChange::Unmarked | FlagChange::NoChange) => {}
         }
     }
 
-    /// Handles logic for clicking an empty cell (starts flood fill animation).
+    /// Handles logic for clicking an empty cell (starts the flood fill animation for the
+    /// cells it uncovered).
     fn handle_empty_click(
         &mut self,
-        row: usize,
-        col: usize,
+        flooded: &[(usize, usize, usize)],
         cell_size: f32,
         wave_sound: &Sound,
         win_sound: &Sound,
@@ -302,15 +893,43 @@ impl MinesweeperApp {
                 },
             );
         }
-        let revealed = self.board_mut().flood_fill_wave(row, col);
-        for &(r, c, dist) in &revealed {
-            let delay = dist as f32 * 0.05;
-            self.wave_timers_mut()[r][c] = Some(delay);
-        }
+        self.assign_wave_delays(flooded, self.wave_order());
         self.check_win(cell_size, win_sound);
     }
 
-    /// Handles logic for clicking a number cell (uncover and pop animation).
+    /// Starts the wave animation for the cells a flood fill revealed, as `(row, col, distance)`
+    /// from `Board::apply_move` (the clicked cell has distance 0). Each cell's delay depends on
+    /// `order`: its ring around the click (Radial), its ring plus its angle around the click so
+    /// each ring is swept in turn (Spiral), or its column from the leftmost revealed one
+    /// (LeftToRight). Steps beyond `MAX_WAVE_DISTANCE` animate together.
+    pub fn assign_wave_delays(&mut self, revealed: &[(usize, usize, usize)], order: WaveOrder) {
+        let (origin_row, origin_col) = revealed
+            .iter()
+            .find(|&&(_, _, dist)| dist == 0)
+            .map_or((0, 0), |&(r, c, _)| (r, c));
+        let left = revealed.iter().map(|&(_, c, _)| c).min().unwrap_or(0);
+        for &(r, c, dist) in revealed {
+            let steps = match order {
+                WaveOrder::Radial => dist.min(MAX_WAVE_DISTANCE) as f32,
+                WaveOrder::Spiral => {
+                    let ring = r.abs_diff(origin_row).max(c.abs_diff(origin_col));
+                    let dy = r as f32 - origin_row as f32;
+                    let dx = c as f32 - origin_col as f32;
+                    let turn = dy.atan2(dx).rem_euclid(std::f32::consts::TAU);
+                    let turn = if ring == 0 {
+                        0.0
+                    } else {
+                        turn / std::f32::consts::TAU
+                    };
+                    ring.min(MAX_WAVE_DISTANCE) as f32 + turn
+                }
+                WaveOrder::LeftToRight => (c - left).min(MAX_WAVE_DISTANCE) as f32,
+            };
+            self.wave_timers_mut()[r][c] = Some(steps * WAVE_STEP_DELAY);
+        }
+    }
+
+    /// Handles logic for clicking a number cell (pop animation for the uncovered number).
     fn handle_number_click(
         &mut self,
         row: usize,
@@ -328,30 +947,72 @@ impl MinesweeperApp {
                 },
             );
         }
-        self.board_mut().uncover_cell(row, col);
         self.pop_timers_mut()[row][col] = Some(0.0);
         self.check_win(cell_size, win_sound);
     }
 
-    /// Handles logic for clicking a mine cell.
-    fn handle_mine_click(
+    /// Handles a chord on an uncovered number: uncovers its remaining neighbors once its flags
+    /// are all placed. Plays one sweep sound for the whole chord (not a flip per cell), and the
+    /// revealed cells pop in a quick sweep outward from the number.
+    /// If a wrong flag makes the chord hit a mine, the bomb sound plays and the game is lost.
+    fn handle_chord_click(
         &mut self,
         row: usize,
         col: usize,
-        cell_size: f32,
         mine_reveal_timer: &mut f32,
         bomb_sound: &Sound,
+        chord_sound: &Sound,
+        win_sound: &Sound,
     ) {
+        let result = self.play_move(Move::Chord(row, col));
+        if result.revealed.is_empty() {
+            return;
+        }
+        let cell_size = self.cell_size();
+        // In zen mode, or with enough lives left, the mines a wrong flag let through are
+        // flagged below instead
+        let mines_hit = result
+            .revealed
+            .iter()
+            .filter(|&&(r, c, _)| self.board().cell(r, c) == Some(Cell::Mine))
+            .count();
+        if let Some((mine_row, mine_col)) = result
+            .mine_hit
+            .filter(|_| !self.can_absorb_mines(mines_hit))
+        {
+            self.handle_mine_click(mine_row, mine_col, cell_size, mine_reveal_timer, bomb_sound);
+            return;
+        }
         if self.sound() {
             play_sound(
-                bomb_sound,
+                chord_sound,
                 PlaySoundParams {
                     looped: false,
-                    volume: 0.7,
+                    volume: 0.6,
                 },
-            ); // Play bomb sound
+            );
         }
-        self.board_mut().uncover_cell(row, col);
+        for (r, c, dist) in result.revealed {
+            if !self.absorb_mine(r, c) {
+                self.wave_timers_mut()[r][c] = Some(dist as f32 * CHORD_SWEEP_DELAY);
+            }
+        }
+        self.check_win(cell_size, win_sound);
+    }
+
+    /// Handles logic for clicking a mine cell.
+    fn handle_mine_click(
+        &mut self,
+        row: usize,
+        col: usize,
+        cell_size: f32,
+        mine_reveal_timer: &mut f32,
+        bomb_sound: &Sound,
+    ) {
+        self.play_ducked_sound(bomb_sound, 0.7); // Play bomb sound
+                                                 // Already uncovered when the mine was hit by a click or a chord; revealing again
+                                                 // changes nothing (and records nothing)
+        self.play_move(Move::Reveal(row, col));
         spawn_particles(
             &mut self.particles_mut(),
             row,
@@ -362,69 +1023,38 @@ impl MinesweeperApp {
             TOP_BAR_HEIGHT,
         );
         self.spawn_shockwave(row, col, cell_size);
+        *mine_reveal_timer = 0.0;
+        self.lose_game(row, col, get_time());
+    }
 
-        // Build a new queue of mines to reveal (excluding flagged and the one just clicked).
-        // We use a temporary variable to avoid borrowing self.mine_reveal_queue and self.board at the same time,
-        // which would cause a Rust borrow checker error.
-        let mut new_queue: Vec<(usize, usize, bool)> = self
-            .board()
-            .mine_positions()
-            .iter()
-            .cloned()
-            .filter(|&(r2, c2)| {
-                self.board().cell_state(r2, c2) != Some(CellState::Flagged)
-                    && !(r2 == row && c2 == col)
-            })
-            .map(|(r, c)| (r, c, true))
-            .collect();
-
-        // Add wrongly flagged cells to the queue.
-        // Again, we collect into a temporary variable to avoid borrow checker issues.
-        let wrong_flags: Vec<_> = (0..self.board().height())
-            .flat_map(|r| (0..self.board().width()).map(move |c| (r, c)))
-            .filter(|&(r, c)| {
-                self.board().cell_state(r, c) == Some(CellState::Flagged)
-                    && self.board().cell(r, c) != Some(Cell::Mine)
-            })
-            .map(|(r, c)| (r, c, false))
-            .collect();
-
-        // Extend the new_queue with wrong flags, then assign it to mine_reveal_queue.
-        // This ensures all borrows are finished before mutably borrowing self.mine_reveal_queue.
-        new_queue.extend(wrong_flags);
-        let queue = self.mine_reveal_queue_mut();
-        *queue = new_queue;
-
-        // Shuffle the mine_reveal_queue in a pseudo-random order using a hash of the cell coordinates and the current time.
-        // This gives a different reveal order each game over, without needing an external random crate.
-        let now = get_time();
-        self.mine_reveal_queue_mut().sort_by(|a, b| {
-            let hash_a = ((a.0 as f64 * 13.37 + a.1 as f64 * 42.42 + now) * 1000.0) as i64;
-            let hash_b = ((b.0 as f64 * 13.37 + b.1 as f64 * 42.42 + now) * 1000.0) as i64;
-            hash_a.cmp(&hash_b)
-        });
+    /// Ends the game as lost at `now`, the way hitting the mine at (row, col) does: starts
+    /// the loss flash, stops the clock, and queues all other unflagged mines and wrong flags
+    /// for the reveal cascade (ordered from (row, col) by the configured reveal order).
+    pub fn lose_game(&mut self, row: usize, col: usize, now: f64) {
+        self.start_loss_flash();
 
-        *mine_reveal_timer = 0.0;
-        self.set_end_time(Some(get_time()));
-        self.set_state(GameState::GameOver); // Fill the queue with all other mines to reveal (except flagged and the one just clicked)
-                                             // self.wrong_flags.clear();
+        // Build the queue of mines (and wrong flags) to reveal, in the configured order.
+        let order = self.reveal_order();
+        self.build_reveal_queue(row, col, order);
+
+        self.set_end_time(Some(now));
+        self.set_win_streak(0);
+        self.set_state(GameState::GameOver);
     }
 
-    pub fn check_win(&mut self, cell_size: f32, win_sound: &Sound) {
-        // Checks if the player has won the game by uncovering all non-mine cells.
-        for row in 0..self.board().height() {
-            for col in 0..self.board().width() {
-                if self.board().cell(row, col) != Some(Cell::Mine)
-                    && self.board().cell_state(row, col) != Some(CellState::Uncovered)
-                {
-                    return; // Not won yet, exit early
-                }
-            }
+    /// Counts a win toward the streak. Reaching a milestone (5, 10, or 25 wins in a row)
+    /// shows the streak banner from `now` and queues the fanfare for the run loop.
+    pub fn record_win_streak(&mut self, now: f64) {
+        let streak = self.win_streak() + 1;
+        self.set_win_streak(streak);
+        if WIN_STREAK_MILESTONES.contains(&streak) {
+            self.set_streak_banner(Some((streak, now)));
+            self.set_fanfare_pending(true);
         }
-        // If we get here, all non-mine cells are uncovered
-        self.set_end_time(Some(get_time()));
-        self.set_state(GameState::Won);
-        if self.sound() {
+    }
+
+    pub fn check_win(&mut self, cell_size: f32, win_sound: &Sound) {
+        if self.check_win_at(get_time(), cell_size) && self.sound() {
             play_sound(
                 win_sound,
                 PlaySoundParams {
@@ -433,10 +1063,80 @@ impl MinesweeperApp {
                 },
             );
         }
+    }
+
+    /// Checks if the player has won the game by uncovering all non-mine cells, at time `now`.
+    /// On a win, records the end time, spawns confetti, and starts the marathon's next board
+    /// or the win wave. Returns true if the game was just won. A board without mines is won
+    /// as soon as its first click flood-fills it.
+    /// A game that is already won is not won again (wave animations call this as they finish).
+    pub fn check_win_at(&mut self, now: f64, cell_size: f32) -> bool {
+        if self.state() == GameState::Won {
+            return false;
+        }
+        // Won once as many non-mine cells are uncovered as the board has safe cells
+        let board = self.board();
+        let uncovered_safe = (0..board.height())
+            .flat_map(|row| (0..board.width()).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                board.cell(row, col) != Some(Cell::Mine)
+                    && board.cell_state(row, col) == Some(CellState::Uncovered)
+            })
+            .count();
+        if uncovered_safe < board.safe_cell_count() {
+            return false; // Not won yet
+        }
+        // If we get here, all non-mine cells are uncovered
+        self.set_end_time(Some(now));
+        self.set_state(GameState::Won);
+        self.record_win_streak(now);
+        if self.safe_reveals_used() > 0 {
+            self.set_wins_with_help(self.wins_with_help() + 1);
+        }
         // Get the board width before mutably borrowing self for particles.
         // This avoids Rust's borrow checker error by ensuring the immutable borrow ends
         // before the mutable borrow of self.particles begins.
         let board_width = self.board().width();
         spawn_confetti(&mut self.particles_mut(), board_width, cell_size);
+
+        // In marathon mode, bank the win and go straight to a new board of the same size.
+        // The confetti is carried over so it rains on the next board.
+        if self.marathon() {
+            let elapsed = self.end_time().unwrap_or(0.0) - self.start_time();
+            self.record_marathon_win(elapsed);
+            let particles = std::mem::take(self.particles_mut());
+            self.reset_game();
+            *self.particles_mut() = particles;
+        } else if self.win_wave() {
+            self.start_win_wave();
+        }
+        true
+    }
+}
+
+/// Draws the outline of a square as dashes, used for maybe-mine cells.
+fn draw_dashed_square(x: f32, y: f32, size: f32) {
+    let mut offset = 0.0;
+    while offset < size {
+        let (a, b) = (offset, (offset + MAYBE_DASH_LENGTH).min(size));
+        draw_line(x + a, y, x + b, y, MAYBE_DASH_WIDTH, MAYBE_DASH_COLOR);
+        draw_line(
+            x + a,
+            y + size,
+            x + b,
+            y + size,
+            MAYBE_DASH_WIDTH,
+            MAYBE_DASH_COLOR,
+        );
+        draw_line(x, y + a, x, y + b, MAYBE_DASH_WIDTH, MAYBE_DASH_COLOR);
+        draw_line(
+            x + size,
+            y + a,
+            x + size,
+            y + b,
+            MAYBE_DASH_WIDTH,
+            MAYBE_DASH_COLOR,
+        );
+        offset += 2.0 * MAYBE_DASH_LENGTH;
     }
 }
@@ -0,0 +1,65 @@
+//! Debug overlay for Minesweeper.
+//!
+//! This module contains the performance overlay toggled with F2. It shows the current FPS,
+//! a rolling average frame time, and the live particle and shockwave counts, so frame drops
+//! can be correlated with effect load. The overlay is off by default and is never drawn
+//! during normal play unless toggled on.
+
+use super::MinesweeperApp;
+use macroquad::prelude::*;
+
+// --- Debug overlay constants ---
+const FRAME_TIME_WINDOW: usize = 60; // Number of frames in the rolling average
+const OVERLAY_X: f32 = 8.0;
+const OVERLAY_Y: f32 = 68.0; // Just below the top bar
+const OVERLAY_W: f32 = 170.0;
+const OVERLAY_LINE_HEIGHT: f32 = 18.0;
+const OVERLAY_PADDING: f32 = 6.0;
+const OVERLAY_FONT_SIZE: f32 = 18.0;
+const OVERLAY_BG_COLOR: Color = Color::from_rgba(0, 0, 0, 170);
+const OVERLAY_TEXT_COLOR: Color = Color::from_rgba(120, 255, 120, 255);
+
+impl MinesweeperApp {
+    /// Records the duration of the last frame, keeping only the most recent frames.
+    pub fn record_frame_time(&mut self, frame_time: f32) {
+        let frame_times = self.frame_times_mut();
+        if frame_times.len() == FRAME_TIME_WINDOW {
+            frame_times.pop_front();
+        }
+        frame_times.push_back(frame_time);
+    }
+
+    /// Returns the rolling average frame time in milliseconds (0.0 if no frames were recorded).
+    pub fn average_frame_time_ms(&self) -> f32 {
+        let frame_times = self.frame_times();
+        if frame_times.is_empty() {
+            return 0.0;
+        }
+        frame_times.iter().sum::<f32>() / frame_times.len() as f32 * 1000.0
+    }
+
+    /// Records this frame's time and, if enabled, draws the debug overlay in the top-left corner.
+    pub fn update_and_draw_debug_overlay(&mut self) {
+        self.record_frame_time(get_frame_time());
+        if !self.show_debug_overlay() {
+            return;
+        }
+        let lines = [
+            format!("FPS: {}", get_fps()),
+            format!("Frame: {:.2} ms", self.average_frame_time_ms()),
+            format!("Particles: {}", self.particles().len()),
+            format!("Shockwaves: {}", self.shockwaves().len()),
+        ];
+        let height = lines.len() as f32 * OVERLAY_LINE_HEIGHT + 2.0 * OVERLAY_PADDING;
+        draw_rectangle(OVERLAY_X, OVERLAY_Y, OVERLAY_W, height, OVERLAY_BG_COLOR);
+        for (i, line) in lines.iter().enumerate() {
+            self.draw_text_sharp(
+                line,
+                OVERLAY_X + OVERLAY_PADDING,
+                OVERLAY_Y + OVERLAY_PADDING + (i + 1) as f32 * OVERLAY_LINE_HEIGHT - 4.0,
+                OVERLAY_FONT_SIZE,
+                OVERLAY_TEXT_COLOR,
+            );
+        }
+    }
+}
@@ -5,7 +5,8 @@
 //! general UI drawing are handled in other modules.
 
 use super::MinesweeperApp;
-use crate::gui::GameState;
+use crate::board::{Board, BoardSize};
+use crate::gui::{GameState, NewGameRequest};
 use macroquad::audio::*;
 use macroquad::prelude::*;
 
@@ -26,10 +27,69 @@ const POPUP_BTN_Y_MARGIN: f32 = 16.0;
 const POPUP_BTN_LABEL_FONT_SIZE: u16 = 22;
 const POPUP_BTN_LABEL_Y_OFFSET: f32 = -4.0;
 const POPUP_BTN_LABEL: &str = "Play Again";
+const CONFIRM_MSG: &str = "Start a new game?";
+const CONFIRM_YES_LABEL: &str = "New Game";
+const CONFIRM_NO_LABEL: &str = "Cancel";
+const CONFIRM_BTN_GAP: f32 = 16.0; // Space between the two confirmation buttons
+const CONFIRM_BORDER_COLOR: Color = Color::from_rgba(255, 140, 0, 255);
+const CONFIRM_NO_BTN_COLOR: Color = Color::from_rgba(90, 90, 90, 255);
+const STREAK_BANNER_DURATION: f64 = 2.5; // Seconds the win-streak banner stays up
+const STREAK_BANNER_HEIGHT: f32 = 48.0;
+const STREAK_BANNER_FONT_SIZE: f32 = 30.0;
+const STREAK_BANNER_BG_COLOR: Color = Color::from_rgba(255, 200, 0, 230);
+const STREAK_BANNER_TEXT_COLOR: Color = Color::from_rgba(60, 30, 0, 255);
+const CUSTOM_TITLE: &str = "Custom board";
+const CUSTOM_OK_LABEL: &str = "OK";
+const CUSTOM_FIELD_NAMES: [&str; 3] = ["Width", "Height", "Mines"];
+const CUSTOM_FIELD_MAX_DIGITS: usize = 4;
+const CUSTOM_POPUP_HEIGHT: f32 = 250.0;
+const CUSTOM_TITLE_Y_OFFSET: f32 = 36.0;
+const CUSTOM_FIELD_Y_OFFSET: f32 = 64.0; // Top of the first field
+const CUSTOM_FIELD_ROW_HEIGHT: f32 = 36.0;
+const CUSTOM_FIELD_X_OFFSET: f32 = 150.0; // Left edge of the fields (labels sit to their left)
+const CUSTOM_LABEL_X_OFFSET: f32 = 40.0;
+const CUSTOM_FIELD_WIDTH: f32 = 120.0;
+const CUSTOM_FIELD_HEIGHT: f32 = 28.0;
+const CUSTOM_FIELD_FONT_SIZE: f32 = 22.0;
+const CUSTOM_ERROR_Y_OFFSET: f32 = 188.0;
+const CUSTOM_ERROR_FONT_SIZE: f32 = 18.0;
+const CUSTOM_FIELD_BG_COLOR: Color = Color::from_rgba(60, 60, 60, 255);
+const CUSTOM_ERROR_COLOR: Color = Color::from_rgba(255, 110, 110, 255);
+
+/// The text typed into the "Custom..." board dialog.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CustomBoardDialog {
+    pub fields: [String; 3],   // Width, height, and mines, as typed
+    pub focus: usize,          // Index of the field that receives typed digits
+    pub error: Option<String>, // Why the last confirm was refused (shown under the fields)
+}
+
+impl CustomBoardDialog {
+    /// Parses the fields into (width, height, mines), naming the first field that isn't a
+    /// number. Whether the board itself is valid is checked by `Board::try_new`.
+    pub fn values(&self) -> Result<(usize, usize, usize), String> {
+        let mut values = [0; 3];
+        let named_fields = self.fields.iter().zip(CUSTOM_FIELD_NAMES);
+        for (value, (text, name)) in values.iter_mut().zip(named_fields) {
+            *value = text
+                .parse()
+                .map_err(|_| format!("{} must be a number", name.to_lowercase()))?;
+        }
+        Ok((values[0], values[1], values[2]))
+    }
+}
 
 impl MinesweeperApp {
     /// Draws a centered popup with a message and a "Play Again" button.
     pub fn draw_popup(&mut self, cell_size: f32, border_color: Color, msg: &str) -> bool {
+        let (popup_x, popup_y) = self.draw_popup_frame(cell_size, border_color, msg);
+        let btn_x = popup_x + (POPUP_WIDTH - POPUP_BTN_WIDTH) / 2.0;
+        let btn_y = popup_y + POPUP_HEIGHT - POPUP_BTN_HEIGHT - POPUP_BTN_Y_MARGIN;
+        self.draw_popup_button(btn_x, btn_y, POPUP_BTN_LABEL, border_color)
+    }
+
+    /// Draws the centered popup box with its border and message, and returns its top-left corner.
+    fn draw_popup_frame(&mut self, cell_size: f32, border_color: Color, msg: &str) -> (f32, f32) {
         let popup_x = (self.board().width() as f32 * cell_size - POPUP_WIDTH) / 2.0;
         let popup_y =
             (self.board().height() as f32 * cell_size + TOP_BAR_HEIGHT - POPUP_HEIGHT) / 2.0;
@@ -45,27 +105,23 @@ impl MinesweeperApp {
         );
 
         let text_dim = measure_text(msg, None, POPUP_MSG_FONT_SIZE as u16, 1.0);
-        draw_text(
+        self.draw_text_sharp(
             msg,
             popup_x + (POPUP_WIDTH - text_dim.width) / 2.0,
             popup_y + POPUP_MSG_Y_OFFSET,
             POPUP_MSG_FONT_SIZE,
             WHITE,
         );
+        (popup_x, popup_y)
+    }
 
-        let btn_x = popup_x + (POPUP_WIDTH - POPUP_BTN_WIDTH) / 2.0;
-        let btn_y = popup_y + POPUP_HEIGHT - POPUP_BTN_HEIGHT - POPUP_BTN_Y_MARGIN;
-        draw_rectangle(
-            btn_x,
-            btn_y,
-            POPUP_BTN_WIDTH,
-            POPUP_BTN_HEIGHT,
-            border_color,
-        );
+    /// Draws a popup button with a centered label and returns whether it was clicked.
+    fn draw_popup_button(&mut self, btn_x: f32, btn_y: f32, label: &str, color: Color) -> bool {
+        draw_rectangle(btn_x, btn_y, POPUP_BTN_WIDTH, POPUP_BTN_HEIGHT, color);
 
-        let btn_label_dim = measure_text(POPUP_BTN_LABEL, None, POPUP_BTN_LABEL_FONT_SIZE, 1.0);
-        draw_text(
-            POPUP_BTN_LABEL,
+        let btn_label_dim = measure_text(label, None, POPUP_BTN_LABEL_FONT_SIZE, 1.0);
+        self.draw_text_sharp(
+            label,
             btn_x + (POPUP_BTN_WIDTH - btn_label_dim.width) / 2.0,
             btn_y + (POPUP_BTN_HEIGHT + btn_label_dim.height) / 2.0 + POPUP_BTN_LABEL_Y_OFFSET,
             POPUP_BTN_LABEL_FONT_SIZE as f32,
@@ -85,6 +141,252 @@ impl MinesweeperApp {
         false
     }
 
+    /// Shows the "start a new game?" popup while a mid-game new game request is pending.
+    /// "New Game" (or Enter) starts the requested game; "Cancel" (or Escape) closes the popup.
+    pub fn handle_new_game_confirmation(&mut self, cell_size: f32) {
+        let Some(request) = self.pending_new_game() else {
+            return;
+        };
+        let (popup_x, popup_y) =
+            self.draw_popup_frame(cell_size, CONFIRM_BORDER_COLOR, CONFIRM_MSG);
+        let btn_y = popup_y + POPUP_HEIGHT - POPUP_BTN_HEIGHT - POPUP_BTN_Y_MARGIN;
+        let yes_x = popup_x + POPUP_WIDTH / 2.0 - CONFIRM_BTN_GAP / 2.0 - POPUP_BTN_WIDTH;
+        let no_x = popup_x + POPUP_WIDTH / 2.0 + CONFIRM_BTN_GAP / 2.0;
+        let yes = self.draw_popup_button(yes_x, btn_y, CONFIRM_YES_LABEL, CONFIRM_BORDER_COLOR);
+        let no = self.draw_popup_button(no_x, btn_y, CONFIRM_NO_LABEL, CONFIRM_NO_BTN_COLOR);
+        if yes || is_key_pressed(KeyCode::Enter) {
+            self.set_pending_new_game(None);
+            self.start_requested_game(request);
+            if let NewGameRequest::Size(_) = request {
+                self.resize_window_to_board();
+            }
+        } else if no || is_key_pressed(KeyCode::Escape) {
+            self.set_pending_new_game(None);
+        }
+    }
+
+    /// Opens the "Custom..." board dialog, filled in with the current board's size.
+    pub fn open_custom_dialog(&mut self) {
+        let board = self.board();
+        let fields = [board.width(), board.height(), board.mines()].map(|n| n.to_string());
+        self.set_custom_dialog(Some(CustomBoardDialog {
+            fields,
+            ..Default::default()
+        }));
+    }
+
+    /// Types a character into the focused field of the custom board dialog.
+    /// Only digits are accepted, up to `CUSTOM_FIELD_MAX_DIGITS` per field.
+    pub fn custom_dialog_type(&mut self, ch: char) {
+        let Some(dialog) = self.custom_dialog_mut() else {
+            return;
+        };
+        let field = &mut dialog.fields[dialog.focus];
+        if ch.is_ascii_digit() && field.len() < CUSTOM_FIELD_MAX_DIGITS {
+            field.push(ch);
+        }
+    }
+
+    /// Deletes the last character of the focused field of the custom board dialog.
+    pub fn custom_dialog_backspace(&mut self) {
+        if let Some(dialog) = self.custom_dialog_mut() {
+            dialog.fields[dialog.focus].pop();
+        }
+    }
+
+    /// Moves the custom board dialog's focus to the next field, wrapping to the first.
+    pub fn custom_dialog_next_field(&mut self) {
+        if let Some(dialog) = self.custom_dialog_mut() {
+            dialog.focus = (dialog.focus + 1) % dialog.fields.len();
+        }
+    }
+
+    /// Confirms the custom board dialog. If the fields make a valid board (see
+    /// `Board::try_new`) the dialog closes and a game at that size is requested (see
+    /// `request_new_game`), returning whether it started; otherwise the reason is shown in
+    /// the dialog, which stays open.
+    /// Sizes that match a preset select the preset instead of a custom size.
+    pub fn confirm_custom_dialog(&mut self) -> bool {
+        let Some(dialog) = self.custom_dialog() else {
+            return false;
+        };
+        let checked = dialog.values().and_then(|(width, height, mines)| {
+            Board::try_new(width, height, mines).map(|_| (width, height, mines))
+        });
+        let (width, height, mines) = match checked {
+            Ok(values) => values,
+            Err(error) => {
+                if let Some(dialog) = self.custom_dialog_mut() {
+                    dialog.error = Some(error);
+                }
+                return false;
+            }
+        };
+        let preset = BoardSize::board_size_from_params(width, height, mines);
+        let size = if preset.params() == (width, height, mines) {
+            preset
+        } else {
+            BoardSize::Custom {
+                width,
+                height,
+                mines,
+            }
+        };
+        self.set_custom_dialog(None);
+        self.request_new_game(NewGameRequest::Size(size))
+    }
+
+    /// Shows the custom board dialog while it is open and feeds it keyboard input: digits
+    /// type, Backspace deletes, Tab (or a click on a field) moves between the fields, "OK"
+    /// (or Enter) confirms, and "Cancel" (or Escape) closes it.
+    pub fn handle_custom_dialog(&mut self, cell_size: f32) {
+        let Some(dialog) = self.custom_dialog().cloned() else {
+            return;
+        };
+        let popup_x = (self.board().width() as f32 * cell_size - POPUP_WIDTH) / 2.0;
+        let popup_y =
+            (self.board().height() as f32 * cell_size + TOP_BAR_HEIGHT - CUSTOM_POPUP_HEIGHT) / 2.0;
+        draw_rectangle(
+            popup_x,
+            popup_y,
+            POPUP_WIDTH,
+            CUSTOM_POPUP_HEIGHT,
+            POPUP_BG_COLOR,
+        );
+        draw_rectangle_lines(
+            popup_x,
+            popup_y,
+            POPUP_WIDTH,
+            CUSTOM_POPUP_HEIGHT,
+            POPUP_BORDER_WIDTH,
+            CONFIRM_BORDER_COLOR,
+        );
+        let title_dim = measure_text(CUSTOM_TITLE, None, POPUP_MSG_FONT_SIZE as u16, 1.0);
+        self.draw_text_sharp(
+            CUSTOM_TITLE,
+            popup_x + (POPUP_WIDTH - title_dim.width) / 2.0,
+            popup_y + CUSTOM_TITLE_Y_OFFSET,
+            POPUP_MSG_FONT_SIZE,
+            WHITE,
+        );
+
+        let mut clicked_field = None;
+        for (i, (text, name)) in dialog.fields.iter().zip(CUSTOM_FIELD_NAMES).enumerate() {
+            let field_x = popup_x + CUSTOM_FIELD_X_OFFSET;
+            let field_y = popup_y + CUSTOM_FIELD_Y_OFFSET + i as f32 * CUSTOM_FIELD_ROW_HEIGHT;
+            let text_y = field_y + CUSTOM_FIELD_HEIGHT * 0.75;
+            self.draw_text_sharp(
+                name,
+                popup_x + CUSTOM_LABEL_X_OFFSET,
+                text_y,
+                CUSTOM_FIELD_FONT_SIZE,
+                WHITE,
+            );
+            draw_rectangle(
+                field_x,
+                field_y,
+                CUSTOM_FIELD_WIDTH,
+                CUSTOM_FIELD_HEIGHT,
+                CUSTOM_FIELD_BG_COLOR,
+            );
+            // The focused field is outlined and shows a cursor after its text
+            let shown = if i == dialog.focus {
+                draw_rectangle_lines(
+                    field_x,
+                    field_y,
+                    CUSTOM_FIELD_WIDTH,
+                    CUSTOM_FIELD_HEIGHT,
+                    2.0,
+                    CONFIRM_BORDER_COLOR,
+                );
+                format!("{}_", text)
+            } else {
+                text.clone()
+            };
+            self.draw_text_sharp(&shown, field_x + 6.0, text_y, CUSTOM_FIELD_FONT_SIZE, WHITE);
+            if is_mouse_button_pressed(MouseButton::Left) {
+                let (mx, my) = mouse_position();
+                if (field_x..=field_x + CUSTOM_FIELD_WIDTH).contains(&mx)
+                    && (field_y..=field_y + CUSTOM_FIELD_HEIGHT).contains(&my)
+                {
+                    clicked_field = Some(i);
+                }
+            }
+        }
+        if let Some(error) = &dialog.error {
+            let error_dim = measure_text(error, None, CUSTOM_ERROR_FONT_SIZE as u16, 1.0);
+            self.draw_text_sharp(
+                error,
+                popup_x + (POPUP_WIDTH - error_dim.width) / 2.0,
+                popup_y + CUSTOM_ERROR_Y_OFFSET,
+                CUSTOM_ERROR_FONT_SIZE,
+                CUSTOM_ERROR_COLOR,
+            );
+        }
+
+        let btn_y = popup_y + CUSTOM_POPUP_HEIGHT - POPUP_BTN_HEIGHT - POPUP_BTN_Y_MARGIN;
+        let ok_x = popup_x + POPUP_WIDTH / 2.0 - CONFIRM_BTN_GAP / 2.0 - POPUP_BTN_WIDTH;
+        let cancel_x = popup_x + POPUP_WIDTH / 2.0 + CONFIRM_BTN_GAP / 2.0;
+        let ok = self.draw_popup_button(ok_x, btn_y, CUSTOM_OK_LABEL, CONFIRM_BORDER_COLOR);
+        let cancel =
+            self.draw_popup_button(cancel_x, btn_y, CONFIRM_NO_LABEL, CONFIRM_NO_BTN_COLOR);
+
+        if let (Some(focus), Some(dialog)) = (clicked_field, self.custom_dialog_mut()) {
+            dialog.focus = focus;
+        }
+        while let Some(ch) = get_char_pressed() {
+            self.custom_dialog_type(ch);
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.custom_dialog_backspace();
+        }
+        if is_key_pressed(KeyCode::Tab) {
+            self.custom_dialog_next_field();
+        }
+        if ok || is_key_pressed(KeyCode::Enter) {
+            if self.confirm_custom_dialog() {
+                self.resize_window_to_board();
+            }
+        } else if cancel || is_key_pressed(KeyCode::Escape) {
+            self.set_custom_dialog(None);
+        }
+    }
+
+    /// Returns the milestone streak whose banner should show at `now`, dismissing the banner
+    /// once it has been up for `STREAK_BANNER_DURATION` seconds.
+    pub fn update_streak_banner(&mut self, now: f64) -> Option<u32> {
+        let (streak, shown_at) = self.streak_banner()?;
+        if now - shown_at > STREAK_BANNER_DURATION {
+            self.set_streak_banner(None);
+            return None;
+        }
+        Some(streak)
+    }
+
+    /// Draws the win-streak milestone banner across the top of the board while it is up.
+    pub fn draw_streak_banner(&mut self, now: f64) {
+        let Some(streak) = self.update_streak_banner(now) else {
+            return;
+        };
+        let width = self.board().width() as f32 * self.cell_size();
+        draw_rectangle(
+            0.0,
+            TOP_BAR_HEIGHT,
+            width,
+            STREAK_BANNER_HEIGHT,
+            STREAK_BANNER_BG_COLOR,
+        );
+        let text = format!("{} wins in a row!", streak);
+        let dim = measure_text(&text, None, STREAK_BANNER_FONT_SIZE as u16, 1.0);
+        self.draw_text_sharp(
+            &text,
+            (width - dim.width) / 2.0,
+            TOP_BAR_HEIGHT + (STREAK_BANNER_HEIGHT + dim.height) / 2.0,
+            STREAK_BANNER_FONT_SIZE,
+            STREAK_BANNER_TEXT_COLOR,
+        );
+    }
+
     /// Checks if the game over popup should be shown and sets wrong flags.
     pub fn show_game_over_popup_if_ready(&mut self, game_over_sound: &Sound) {
         if self.state() == GameState::GameOver
@@ -111,18 +413,40 @@ impl MinesweeperApp {
         if self.state() == GameState::Won {
             if let Some(end_time) = self.end_time() {
                 if get_time() - end_time > 4.0 {
-                    let msg = &format!("You Win!  Time: {:.1}s", end_time - self.start_time());
+                    let elapsed = end_time - self.start_time();
+                    let msg = &if self.zen_mode() {
+                        "Board cleared".to_string()
+                    } else if self.safe_reveals_used() > 0 {
+                        format!("Won with help: {:.1}s", elapsed)
+                    } else {
+                        format!("You Win!  Time: {:.1}s", elapsed)
+                    };
                     if self.draw_popup(cell_size, GREEN, msg) {
-                        self.reset_game();
+                        self.new_game();
                     }
                 }
             }
         }
         // Show game over popup if lost
         else if self.state() == GameState::Lost {
-            let msg = "Game Over!";
-            if self.draw_popup(cell_size, RED, msg) {
-                self.reset_game();
+            // A loss ends the marathon, so show its final tally instead
+            let msg = if self.marathon() {
+                format!(
+                    "Over! {} boards, {:.0}s",
+                    self.marathon_count(),
+                    self.marathon_total_time()
+                )
+            } else {
+                format!(
+                    "Game Over!  Flags: {:.0}%",
+                    self.board().correct_flag_ratio() * 100.0
+                )
+            };
+            if self.draw_popup(cell_size, RED, &msg) {
+                if self.marathon() {
+                    self.set_marathon(true); // Start a fresh marathon
+                }
+                self.new_game();
             }
         }
     }
@@ -0,0 +1,97 @@
+//! End-of-game screenshot export for Minesweeper.
+//!
+//! After a win or loss, pressing X saves the rendered frame (board, top bar, and popup) as a PNG
+//! in the working directory, named after the difficulty and the time it was taken. Export
+//! failures (e.g. a read-only folder) are reported in an on-screen panel instead of crashing.
+//! During a game, the safe-cell peek (slash key) shows the board with every safe cell
+//! uncovered and the mines still hidden, so it can be saved as a spoiler-free picture too.
+
+use super::MinesweeperApp;
+use crate::board::BoardSize;
+use crate::gui::GameState;
+use crate::gui_ui::civil_date;
+use macroquad::prelude::*;
+use std::panic::{self, AssertUnwindSafe};
+
+const SCREENSHOT_PREFIX: &str = "result";
+
+/// Returns the file name for a screenshot of a game of the given size taken at `unix_secs`,
+/// e.g. `result-medium-20261016-142530.png` (UTC).
+pub fn screenshot_file_name(size: BoardSize, unix_secs: u64) -> String {
+    let (year, month, day) = civil_date(unix_secs);
+    let secs_of_day = unix_secs % 86_400;
+    format!(
+        "{}-{}-{:04}{:02}{:02}-{:02}{:02}{:02}.png",
+        SCREENSHOT_PREFIX,
+        size.label().to_lowercase(),
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+impl MinesweeperApp {
+    /// Asks for the final board to be saved at the end of this frame.
+    /// Only finished games (won or lost) and the safe-cell peek can be exported; returns
+    /// whether the request was taken.
+    pub fn request_screenshot(&mut self) -> bool {
+        let finished =
+            matches!(self.state(), GameState::Won | GameState::Lost) || self.safe_peek_active();
+        if finished {
+            self.set_screenshot_pending(true);
+        }
+        finished
+    }
+
+    /// Turns the safe-cell peek on or off and returns whether it is now showing. Turning it
+    /// on (only while a game is running) copies the board and uncovers every safe cell of the
+    /// copy (see `Board::reveal_all_safe`), which is drawn instead of the real board. The real
+    /// board is never touched, so the game continues exactly where it was. Board clicks are
+    /// ignored while it shows.
+    pub fn toggle_safe_peek(&mut self) -> bool {
+        if self.safe_peek_active() {
+            self.set_safe_peek(None);
+            return false;
+        }
+        if self.state() != GameState::Running {
+            return false;
+        }
+        let mut peek = self.board().clone();
+        peek.reveal_all_safe();
+        self.set_safe_peek(Some(peek));
+        true
+    }
+
+    /// Saves the rendered frame as a PNG if a screenshot was requested.
+    /// Call this after everything that belongs in the picture has been drawn.
+    pub fn take_pending_screenshot(&mut self) {
+        if !self.screenshot_pending() {
+            return;
+        }
+        self.set_screenshot_pending(false);
+        let unix_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let path = screenshot_file_name(self.board_size(), unix_secs);
+        // `Image::export_png` panics when the file can't be written
+        let saved = panic::catch_unwind(AssertUnwindSafe(|| get_screen_data().export_png(&path)));
+        let message = match saved {
+            Ok(()) => format!("Saved {}", path),
+            Err(_) => format!("Could not save {}", path),
+        };
+        self.set_screenshot_message(Some(message));
+    }
+
+    /// Draws the result of the last screenshot export. Pressing Escape dismisses it.
+    pub fn draw_screenshot_message(&mut self) {
+        if is_key_pressed(KeyCode::Escape) {
+            self.set_screenshot_message(None);
+        }
+        if let Some(message) = self.screenshot_message() {
+            self.draw_notice_panel(&[message, "Press Esc to dismiss."]);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Skin (icon set) loading for Minesweeper.
+//!
+//! This module defines the `Skin` struct, which holds all icon textures used by the board and
+//! top bar, and the logic to find and load skins. The default skin lives in `assets/`; custom
+//! skins are folders under `skins/<name>/` containing any of the same PNG file names
+//! (`flag.png`, `blast.png`, `clock.png`, `mute.png`, `synchronize.png`, `volume.png`).
+//! Any file a custom skin doesn't provide falls back to the default skin's texture.
+
+use crate::gui_assets::load_texture_or_placeholder;
+use macroquad::prelude::*;
+use std::path::Path;
+
+// --- Skin locations and icon file names ---
+pub const DEFAULT_SKIN_NAME: &str = "Default";
+const DEFAULT_SKIN_DIR: &str = "assets"; // Folder of the default skin
+pub const SKINS_DIR: &str = "skins"; // Folder containing one subfolder per custom skin
+const FLAG_FILE: &str = "flag.png"; // Flag icon
+const MINE_FILE: &str = "blast.png"; // Mine icon
+const CLOCK_FILE: &str = "clock.png"; // Clock icon
+const MUTE_FILE: &str = "mute.png"; // Mute/sound icon
+const SYNCHRONIZE_FILE: &str = "synchronize.png"; // New game/restart icon
+const VOLUME_FILE: &str = "volume.png"; // Volume/sound-on icon
+
+/// A set of icon textures used to draw the board and top bar.
+#[derive(Clone)]
+pub struct Skin {
+    pub name: String,
+    pub flag: Texture2D,
+    pub mine: Texture2D,
+    pub clock: Texture2D,
+    pub mute: Texture2D,
+    pub synchronize: Texture2D,
+    pub volume: Texture2D,
+}
+
+impl Skin {
+    /// Loads the default skin from the `assets/` folder.
+    /// Icons that fail to load are replaced by placeholders and their paths pushed onto `missing`.
+    pub async fn load_default(missing: &mut Vec<String>) -> Skin {
+        let dir = DEFAULT_SKIN_DIR;
+        Skin {
+            name: DEFAULT_SKIN_NAME.to_string(),
+            flag: load_texture_or_placeholder(&skin_file(dir, FLAG_FILE), missing).await,
+            mine: load_texture_or_placeholder(&skin_file(dir, MINE_FILE), missing).await,
+            clock: load_texture_or_placeholder(&skin_file(dir, CLOCK_FILE), missing).await,
+            mute: load_texture_or_placeholder(&skin_file(dir, MUTE_FILE), missing).await,
+            synchronize: load_texture_or_placeholder(&skin_file(dir, SYNCHRONIZE_FILE), missing)
+                .await,
+            volume: load_texture_or_placeholder(&skin_file(dir, VOLUME_FILE), missing).await,
+        }
+    }
+
+    /// Loads the skin with the given name from `skins/<name>/`.
+    /// Every icon the skin is missing (or fails to load) is taken from `default` instead,
+    /// and the default skin itself is returned for `DEFAULT_SKIN_NAME`.
+    pub async fn load(name: &str, default: &Skin) -> Skin {
+        if name == DEFAULT_SKIN_NAME {
+            return default.clone();
+        }
+        let dir = format!("{}/{}", SKINS_DIR, name);
+        Skin {
+            name: name.to_string(),
+            flag: load_or_fallback(&dir, FLAG_FILE, &default.flag).await,
+            mine: load_or_fallback(&dir, MINE_FILE, &default.mine).await,
+            clock: load_or_fallback(&dir, CLOCK_FILE, &default.clock).await,
+            mute: load_or_fallback(&dir, MUTE_FILE, &default.mute).await,
+            synchronize: load_or_fallback(&dir, SYNCHRONIZE_FILE, &default.synchronize).await,
+            volume: load_or_fallback(&dir, VOLUME_FILE, &default.volume).await,
+        }
+    }
+}
+
+/// Returns the path of an icon file inside a skin folder.
+fn skin_file(dir: &str, file: &str) -> String {
+    format!("{}/{}", dir, file)
+}
+
+/// Loads an icon from a skin folder, using the fallback texture if it is missing or invalid.
+async fn load_or_fallback(dir: &str, file: &str, fallback: &Texture2D) -> Texture2D {
+    let path = skin_file(dir, file);
+    if !Path::new(&path).is_file() {
+        return fallback.clone();
+    }
+    load_texture(&path)
+        .await
+        .unwrap_or_else(|_| fallback.clone())
+}
+
+/// Returns the names of all available skins: the default skin first,
+/// then every subfolder of `dir` in alphabetical order.
+/// A missing or unreadable folder simply yields only the default skin.
+pub fn available_skins(dir: &str) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .filter(|name| name != DEFAULT_SKIN_NAME)
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.insert(0, DEFAULT_SKIN_NAME.to_string());
+    names
+}
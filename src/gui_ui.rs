@@ -7,7 +7,7 @@
 
 use super::MinesweeperApp;
 use crate::board::*;
-use crate::gui::GameState;
+use crate::gui::{CounterMode, GameState, NewGameRequest, TimerPrecision};
 use macroquad::prelude::*;
 
 // === UI Layout and Style Constants ===
@@ -19,6 +19,19 @@ const FONT_SIZE: f32 = 20.0;
 const ICON_Y: f32 = 18.0;
 const ICON_TEXT_OFFSET: f32 = 0.8; // Multiplier for icon size to position text vertically
 const BTN_LABEL_SUFFIX: &str = " v";
+const DROPDOWN_PADDING: f32 = 12.0; // Horizontal padding around the widest dropdown label
+const PLAY_BTN_LABEL: &str = "Play";
+const DAILY_LABEL: &str = "Daily"; // Dropdown entry that starts today's puzzle
+const CUSTOM_LABEL: &str = "Custom..."; // Dropdown entry that opens the custom board dialog
+const STRIP_FONT_SIZE: f32 = 14.0;
+const STRIP_TEXT_Y: f32 = 13.0; // Baseline of small labels (seed, marathon), in the strip above the icons
+const STRIP_MARGIN: f32 = 6.0;
+const STRIP_ELLIPSIS: &str = "...";
+const TIMER_ROLL_DURATION: f32 = 0.08; // Seconds for a changed digit to roll into place
+const TIMER_ROLL_DISTANCE: f32 = 10.0; // Pixels a digit travels while rolling
+const MOVE_TIME_BAR_HEIGHT: f32 = 4.0; // Height of the time attack countdown bar
+const MOVE_TIME_WARNING_FRACTION: f32 = 0.3; // Countdown bar turns red below this fraction
+const FLAG_MODE_HIGHLIGHT_PADDING: f32 = 3.0; // Padding of the highlight behind the flag icon
 
 // Colors
 const COLOR_TOP_BAR: Color = Color::from_rgba(255, 140, 0, 255);
@@ -27,6 +40,40 @@ const COLOR_BTN_SELECTED: Color = Color::from_rgba(255, 220, 120, 255);
 const COLOR_BTN_UNSELECTED: Color = Color::from_rgba(220, 220, 220, 255);
 const COLOR_DROPDOWN_BG: Color = Color::from_rgba(245, 245, 245, 255);
 const COLOR_TEXT: Color = BLACK;
+const COLOR_OVER_FLAGGED: Color = Color::from_rgba(200, 0, 0, 255); // Negative flags-left count
+const COLOR_FLAG_MODE_BG: Color = Color::from_rgba(255, 255, 255, 255); // Flag icon in flagging mode
+const COLOR_MOVE_TIME_BAR: Color = Color::from_rgba(40, 120, 40, 255);
+const COLOR_MOVE_TIME_BAR_LOW: Color = Color::from_rgba(200, 30, 30, 255);
+const COLOR_MOVE_TIME_BAR_BG: Color = Color::from_rgba(120, 60, 0, 255);
+const MIN_TOP_BAR_SPACING: f32 = 4.0; // Tightest gap between top-bar items on crowded bars
+const ICON_TEXT_GAP: f32 = 4.0; // Gap between the flag/clock icons and their text
+const HOVER_SCALE: f32 = 1.08; // Hovered top-bar buttons grow by this factor around their center
+const COLOR_BTN_HOVER: Color = Color::from_rgba(255, 235, 170, 255); // Brighter button fill
+const COLOR_ICON_HOVER: Color = Color::from_rgba(255, 255, 255, 70); // Glow behind hovered icons
+const COUNTDOWN_FONT_SIZE: f32 = 140.0; // Size of the big "3-2-1" digits over the board
+const COLOR_COUNTDOWN: Color = Color::from_rgba(40, 40, 40, 220);
+const MIN_CELL_SIZE: f32 = 16.0; // Smallest cells, even in a window too small for the board
+const MAX_CELL_SIZE: f32 = 64.0; // Largest cells, so maximized windows don't get huge cells
+const GUESS_LABEL: &str = "Guess required (F1: help)"; // Clicking it reveals a safe cell
+const COLOR_GUESS_TEXT: Color = Color::from_rgba(150, 0, 0, 255); // "Guess required" indicator
+
+/// Horizontal positions of the top-bar items, from `MinesweeperApp::top_bar_layout`.
+/// Drawing and click hit-boxes both use these, so they stay in sync when the bar is crowded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TopBarLayout {
+    pub flags_x: f32,       // Flag icon and counter
+    pub timer_x: f32,       // Clock icon and timer
+    pub size_button_x: f32, // Size dropdown button (or the editor's "Play" button)
+    pub new_game_x: f32,    // New game icon
+    pub sound_x: f32,       // Sound icon
+    pub spacing: f32,       // Gap between items
+}
+
+/// What a top-bar button shows (see `draw_button_hoverable`).
+enum ButtonFace<'a> {
+    Icon(&'a Texture2D), // A square icon texture
+    Label(&'a str),      // Text on a filled rectangle
+}
 
 impl MinesweeperApp {
     /// Returns dynamic spacing for top bar elements based on board size.
@@ -35,9 +82,61 @@ impl MinesweeperApp {
             BoardSize::Small => 20.0,
             BoardSize::Medium => 48.0,
             BoardSize::Large => 64.0,
+            BoardSize::Custom { width, .. } => (width as f32 * 3.0).clamp(20.0, 64.0),
+        }
+    }
+
+    /// Lays out the top-bar items given the measured widths of the counter and timer text.
+    /// Items use the size's usual spacing when they fit; on crowded bars (e.g. the Small board
+    /// with a wide counter) the gaps shrink, down to `MIN_TOP_BAR_SPACING`, and then the left
+    /// margin shrinks, so the sound icon stays inside the bar.
+    pub fn top_bar_layout(&self, counter_width: f32, timer_width: f32) -> TopBarLayout {
+        let bar_width = self.board().width() as f32 * self.cell_size();
+        let widths = [
+            ICON_SIZE + ICON_TEXT_GAP + counter_width,
+            ICON_SIZE + ICON_TEXT_GAP + timer_width,
+            BTN_W,
+            ICON_SIZE,
+            ICON_SIZE,
+        ];
+        let content: f32 = widths.iter().sum();
+        let gaps = (widths.len() - 1) as f32;
+        let mut start = self.top_bar_start_x();
+        let room = bar_width - start - STRIP_MARGIN - content;
+        let spacing = (room / gaps).clamp(MIN_TOP_BAR_SPACING, self.top_bar_spacing());
+        let overflow = start + content + gaps * spacing + STRIP_MARGIN - bar_width;
+        if overflow > 0.0 {
+            start = (start - overflow).max(STRIP_MARGIN);
+        }
+        let mut xs = [0.0; 5];
+        let mut x = start;
+        for (slot, width) in xs.iter_mut().zip(widths) {
+            *slot = x;
+            x += width + spacing;
+        }
+        TopBarLayout {
+            flags_x: xs[0],
+            timer_x: xs[1],
+            size_button_x: xs[2],
+            new_game_x: xs[3],
+            sound_x: xs[4],
+            spacing,
         }
     }
 
+    /// Measures the counter and timer text and lays out the top bar (see `top_bar_layout`).
+    fn measure_top_bar_layout(&self) -> TopBarLayout {
+        // Laid out for the real count, so the bar doesn't shift while the counter counts
+        let counter_width = measure_text(&self.counter_text(), None, FONT_SIZE as u16, 1.0).width;
+        // The timer is drawn one character at a time, so measure it the same way
+        let timer_width = self
+            .timer_text()
+            .chars()
+            .map(|ch| measure_text(&ch.to_string(), None, FONT_SIZE as u16, 1.0).width)
+            .sum();
+        self.top_bar_layout(counter_width, timer_width)
+    }
+
     /// Draws the entire top bar, calling helper functions for each section.
     /// Note: The dropdown menu itself should be drawn after the board for proper layering!
     pub fn draw_top_bar(
@@ -53,23 +152,168 @@ impl MinesweeperApp {
         let bar_width = self.board().width() as f32 * cell_size;
         draw_rectangle(0.0, 0.0, bar_width, TOP_BAR_HEIGHT, COLOR_TOP_BAR);
 
-        let mut x = self.top_bar_start_x();
-        let spacing = self.top_bar_spacing();
+        // Update the rolling timer text, then lay out the items so they fit the bar
+        let timer_text = if self.zen_mode() {
+            "Zen".to_string() // No clock in zen mode
+        } else {
+            self.format_elapsed(self.elapsed_time())
+        };
+        self.update_timer_text(timer_text, get_frame_time());
+        self.advance_displayed_flags(get_frame_time());
+        let layout = self.measure_top_bar_layout();
+        let spacing = layout.spacing;
 
-        // Draw flags left section and update x
-        x = self.draw_flags_left_section(x, flag_texture, spacing);
+        // Draw flags left section (the flag icon toggles flagging mode)
+        self.handle_flag_mode_click(layout.flags_x);
+        self.draw_flags_left_section(layout.flags_x, flag_texture, spacing);
 
-        // Draw timer section and update x
-        x = self.draw_timer_section(x, clock_texture, spacing);
+        // Draw the timer section
+        self.draw_timer_section(layout.timer_x, clock_texture, spacing);
 
-        // Draw board size dropdown button (but NOT the dropdown menu itself)
-        x = self.draw_board_size_dropdown_button(x, spacing);
+        // Draw board size dropdown button (but NOT the dropdown menu itself),
+        // or the "Play" button while the level editor is active
+        if self.editor_mode() {
+            self.draw_play_layout_button(layout.size_button_x, spacing);
+        } else {
+            self.draw_board_size_dropdown_button(layout.size_button_x, spacing);
+        }
 
-        // Draw new game icon and update x
-        x = self.draw_new_game_icon(x, new_game_texture, spacing);
+        // Draw new game icon
+        self.draw_new_game_icon(layout.new_game_x, new_game_texture, spacing);
 
         // Draw sound icon (future)
-        self.draw_sound_icon(x, volume_texture,mute_texture);
+        self.draw_sound_icon(layout.sound_x, volume_texture,mute_texture);
+
+        // Draw the marathon tally, the guess indicator, and the current game's seed
+        // in the strip above the icons
+        self.draw_marathon_label();
+        self.draw_safe_budget_label();
+        self.draw_guess_indicator(bar_width);
+        self.draw_seed_label(bar_width);
+
+        // Draw the time attack countdown along the bottom edge of the bar
+        self.draw_move_time_bar(bar_width);
+    }
+
+    /// Draws a bar along the bottom of the top bar that shrinks as the time attack
+    /// per-move limit runs out (only while time attack is counting down).
+    fn draw_move_time_bar(&self, bar_width: f32) {
+        let (Some(remaining), Some(limit)) =
+            (self.move_time_remaining(get_time()), self.move_time_limit())
+        else {
+            return;
+        };
+        let fraction = (remaining / limit).clamp(0.0, 1.0) as f32;
+        let y = TOP_BAR_HEIGHT - MOVE_TIME_BAR_HEIGHT;
+        draw_rectangle(0.0, y, bar_width, MOVE_TIME_BAR_HEIGHT, COLOR_MOVE_TIME_BAR_BG);
+        let color = if fraction < MOVE_TIME_WARNING_FRACTION {
+            COLOR_MOVE_TIME_BAR_LOW
+        } else {
+            COLOR_MOVE_TIME_BAR
+        };
+        draw_rectangle(0.0, y, bar_width * fraction, MOVE_TIME_BAR_HEIGHT, color);
+    }
+
+    /// Draws a "Guess required" note centered at the top of the bar when no logical move is left.
+    fn draw_guess_indicator(&self, bar_width: f32) {
+        if !self.forced_guess() {
+            return;
+        }
+        let x = self.guess_indicator_rect(bar_width).x;
+        self.draw_text_sharp(GUESS_LABEL, x, STRIP_TEXT_Y, STRIP_FONT_SIZE, COLOR_GUESS_TEXT);
+    }
+
+    /// Returns the area of the "Guess required" note, centered in the strip above the icons.
+    fn guess_indicator_rect(&self, bar_width: f32) -> Rect {
+        let label_dim = measure_text(GUESS_LABEL, None, STRIP_FONT_SIZE as u16, 1.0);
+        let x = (bar_width - label_dim.width) / 2.0;
+        Rect::new(x, 0.0, label_dim.width, STRIP_TEXT_Y + STRIP_MARGIN)
+    }
+
+    /// Returns whether the "Guess required" note (which doubles as the emergency safe-reveal
+    /// button) was clicked on this frame.
+    pub fn guess_indicator_clicked(&self) -> bool {
+        if !self.forced_guess() || !is_mouse_button_pressed(MouseButton::Left) {
+            return false;
+        }
+        let bar_width = self.board().width() as f32 * self.cell_size();
+        let (mx, my) = mouse_position();
+        self.guess_indicator_rect(bar_width).contains(Vec2::new(mx, my))
+    }
+
+    /// Draws the running marathon tally left-aligned at the top of the bar (marathon mode only).
+    fn draw_marathon_label(&self) {
+        if !self.marathon() {
+            return;
+        }
+        let label = self.marathon_label();
+        self.draw_text_sharp(&label, STRIP_MARGIN, STRIP_TEXT_Y, STRIP_FONT_SIZE, COLOR_TEXT);
+    }
+
+    /// Returns the marathon tally shown at the top of the bar.
+    fn marathon_label(&self) -> String {
+        format!(
+            "Marathon: {} cleared, {:.1}s",
+            self.marathon_count(),
+            self.marathon_total_time()
+        )
+    }
+
+    /// Draws the lives left of the safe-reveal budget at the top left of the bar (after the
+    /// marathon tally, if shown). Nothing is drawn while the budget is off.
+    fn draw_safe_budget_label(&self) {
+        if self.safe_budget_size() == 0 {
+            return;
+        }
+        let x = if self.marathon() {
+            let marathon = measure_text(&self.marathon_label(), None, STRIP_FONT_SIZE as u16, 1.0);
+            STRIP_MARGIN * 3.0 + marathon.width
+        } else {
+            STRIP_MARGIN
+        };
+        let label = format!("Lives: {}", self.safe_budget());
+        let color = if self.safe_budget() == 0 { COLOR_OVER_FLAGGED } else { COLOR_TEXT };
+        self.draw_text_sharp(&label, x, STRIP_TEXT_Y, STRIP_FONT_SIZE, color);
+    }
+
+    /// Returns the seed of the current game formatted for display and sharing.
+    pub fn seed_string(&self) -> Option<String> {
+        self.seed().map(|seed| format!("{:016x}", seed))
+    }
+
+    /// Draws the seed right-aligned at the top of the bar, truncated with an
+    /// ellipsis if it does not fit the bar width. Clicking the label copies the seed.
+    fn draw_seed_label(&mut self, bar_width: f32) {
+        let Some(seed) = self.seed_string() else {
+            return;
+        };
+        let max_width = bar_width - 2.0 * STRIP_MARGIN;
+        let mut label = format!("Seed: {}", seed);
+        while measure_text(&label, None, STRIP_FONT_SIZE as u16, 1.0).width > max_width
+            && label.len() > STRIP_ELLIPSIS.len()
+        {
+            label.truncate(label.len() - STRIP_ELLIPSIS.len() - 1);
+            label.push_str(STRIP_ELLIPSIS);
+        }
+        let label_dim = measure_text(&label, None, STRIP_FONT_SIZE as u16, 1.0);
+        let x = bar_width - label_dim.width - STRIP_MARGIN;
+        self.draw_text_sharp(&label, x, STRIP_TEXT_Y, STRIP_FONT_SIZE, COLOR_TEXT);
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let (mx, my) = mouse_position();
+            if mx >= x && mx <= bar_width && my <= STRIP_TEXT_Y + STRIP_MARGIN {
+                self.copy_seed_to_clipboard();
+            }
+        }
+    }
+
+    /// Copies the current seed to the system clipboard.
+    /// Returns true on success; always false when built without the `clipboard` feature.
+    pub fn copy_seed_to_clipboard(&self) -> bool {
+        match self.seed_string() {
+            Some(seed) => copy_to_clipboard(&seed),
+            None => false,
+        }
     }
 
     /// Returns the recommended starting X position for the top bar,
@@ -79,7 +323,18 @@ impl MinesweeperApp {
         (bar_width * 0.08).max(12.0)
     }
 
+    /// Toggles flagging mode when the flag icon (at `x` in the top bar) is clicked.
+    fn handle_flag_mode_click(&mut self, x: f32) {
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let (mx, my) = mouse_position();
+            if (x..=x + ICON_SIZE).contains(&mx) && (ICON_Y..=ICON_Y + ICON_SIZE).contains(&my) {
+                self.set_flag_mode(!self.flag_mode());
+            }
+        }
+    }
+
     /// Draws the flag icon and flags left counter.
+    /// The flag icon is highlighted while flagging mode is on.
     /// Returns the new x position after this section.
     pub fn draw_flags_left_section(
         &self,
@@ -87,6 +342,12 @@ impl MinesweeperApp {
         flag_texture: &Texture2D,
         spacing: f32,
     ) -> f32 {
+        if self.flag_mode() {
+            let pad = FLAG_MODE_HIGHLIGHT_PADDING;
+            let size = ICON_SIZE + 2.0 * pad;
+            draw_rectangle(x - pad, ICON_Y - pad, size, size, COLOR_FLAG_MODE_BG);
+            draw_rectangle_lines(x - pad, ICON_Y - pad, size, size, 2.0, COLOR_TEXT);
+        }
         draw_texture_ex(
             flag_texture,
             x,
@@ -97,23 +358,82 @@ impl MinesweeperApp {
                 ..Default::default()
             },
         );
-        x += ICON_SIZE + 4.0;
-        let flags_placed = (0..self.board().height())
-            .flat_map(|row| (0..self.board().width()).map(move |col| (row, col)))
-            .filter(|&(row, col)| self.board().cell_state(row, col) == Some(CellState::Flagged))
-            .count();
-        let flags_left = self.board().mines() as isize - flags_placed as isize;
-        draw_text(
-            &flags_left.to_string(),
+        x += ICON_SIZE + ICON_TEXT_GAP;
+        // Over-flagging makes the flags left negative; it is shown as-is, in red, as a warning
+        let over_flagged =
+            self.counter_mode() != CounterMode::Cells && self.displayed_flags() < 0.0;
+        let text = self.displayed_counter_text();
+        self.draw_text_sharp(
+            &text,
             x,
             ICON_Y + ICON_SIZE * ICON_TEXT_OFFSET,
             FONT_SIZE,
-            COLOR_TEXT,
+            if over_flagged {
+                COLOR_OVER_FLAGGED
+            } else {
+                COLOR_TEXT
+            },
         );
-        x + measure_text(&flags_left.to_string(), None, FONT_SIZE as u16, 1.0).width + spacing
+        x + measure_text(&text, None, FONT_SIZE as u16, 1.0).width + spacing
+    }
+
+    /// Returns the text of the counter next to the flag icon for the current counter mode:
+    /// the flags left, the cells not uncovered yet, or both as "flags|cells".
+    pub fn counter_text(&self) -> String {
+        self.format_counter(self.board().remaining_mines())
+    }
+
+    /// Same as `counter_text`, but with the flags left the counter is showing while it counts
+    /// toward a new value (see `advance_displayed_flags`).
+    pub fn displayed_counter_text(&self) -> String {
+        self.format_counter(self.displayed_flags().round() as isize)
+    }
+
+    /// Formats the counter for the current counter mode with `flags_left` flags left.
+    fn format_counter(&self, flags_left: isize) -> String {
+        let board = self.board();
+        let uncovered = board.count_by_state(CellState::Uncovered);
+        let cells_left = board.total_cells() - uncovered;
+        match self.counter_mode() {
+            CounterMode::Flags => flags_left.to_string(),
+            CounterMode::Cells => cells_left.to_string(),
+            CounterMode::Both => format!("{}|{}", flags_left, cells_left),
+        }
+    }
+
+    /// Returns the elapsed game time in seconds (frozen once the game ends).
+    pub fn elapsed_time(&self) -> f64 {
+        if let Some(end_time) = self.end_time() {
+            end_time - self.start_time()
+        } else if self.state() == GameState::Running {
+            get_time() - self.start_time()
+        } else {
+            0.0
+        }
+    }
+
+    /// Formats an elapsed time for the timer using the current timer precision.
+    pub fn format_elapsed(&self, elapsed_time: f64) -> String {
+        match self.timer_precision() {
+            TimerPrecision::Seconds => {
+                let total_seconds = elapsed_time as u32;
+                format!("{:02}:{:02}", total_seconds / 60, total_seconds % 60)
+            }
+            TimerPrecision::Tenths => {
+                let total_tenths = (elapsed_time * 10.0) as u32;
+                let total_seconds = total_tenths / 10;
+                format!(
+                    "{:02}:{:02}.{}",
+                    total_seconds / 60,
+                    total_seconds % 60,
+                    total_tenths % 10
+                )
+            }
+        }
     }
 
     /// Draws the clock icon and timer.
+    /// Digits that just changed roll in from below while the old digits roll out upward.
     /// Returns the new x position after this section.
     pub fn draw_timer_section(&self, mut x: f32, clock_texture: &Texture2D, spacing: f32) -> f32 {
         draw_texture_ex(
@@ -126,53 +446,215 @@ impl MinesweeperApp {
                 ..Default::default()
             },
         );
-        x += ICON_SIZE + 4.0;
-        let elapsed_time = if let Some(end_time) = self.end_time() {
-            end_time - self.start_time()
-        } else if self.state() == GameState::Running {
-            get_time() - self.start_time()
-        } else {
-            0.0
+        x += ICON_SIZE + ICON_TEXT_GAP;
+        let time_str = self.timer_text();
+        let text_y = ICON_Y + ICON_SIZE * ICON_TEXT_OFFSET;
+        let progress = (self.timer_roll() / TIMER_ROLL_DURATION).min(1.0);
+        let mut prev_chars = self.timer_prev_text().chars();
+        let mut char_x = x;
+        for ch in time_str.chars() {
+            let label = ch.to_string();
+            let prev = prev_chars.next();
+            if progress < 1.0 && prev.is_some_and(|p| p != ch) {
+                // Roll the old digit up and out, and the new digit up into place
+                let old_label = prev.unwrap_or(ch).to_string();
+                let fade_out = Color::new(COLOR_TEXT.r, COLOR_TEXT.g, COLOR_TEXT.b, 1.0 - progress);
+                let fade_in = Color::new(COLOR_TEXT.r, COLOR_TEXT.g, COLOR_TEXT.b, progress);
+                self.draw_text_sharp(
+                    &old_label,
+                    char_x,
+                    text_y - progress * TIMER_ROLL_DISTANCE,
+                    FONT_SIZE,
+                    fade_out,
+                );
+                self.draw_text_sharp(
+                    &label,
+                    char_x,
+                    text_y + (1.0 - progress) * TIMER_ROLL_DISTANCE,
+                    FONT_SIZE,
+                    fade_in,
+                );
+            } else {
+                self.draw_text_sharp(&label, char_x, text_y, FONT_SIZE, COLOR_TEXT);
+            }
+            char_x += measure_text(&label, None, FONT_SIZE as u16, 1.0).width;
+        }
+        char_x + spacing
+    }
+
+    /// Advances the start countdown by `dt` seconds. When it runs out, the countdown ends and
+    /// the game clock starts at `now`, so every player's timer starts on "go" rather than on
+    /// their first click. Returns whether the countdown finished on this tick.
+    pub fn tick_countdown(&mut self, dt: f32, now: f64) -> bool {
+        let Some(remaining) = self.countdown() else {
+            return false;
         };
-        let total_seconds = elapsed_time as u32;
-        let time_str = format!("{:02}:{:02}", total_seconds / 60, total_seconds % 60);
-        draw_text(
-            &time_str,
-            x,
-            ICON_Y + ICON_SIZE * ICON_TEXT_OFFSET,
-            FONT_SIZE,
-            COLOR_TEXT,
+        let remaining = remaining - dt;
+        if remaining > 0.0 {
+            self.set_countdown(Some(remaining));
+            return false;
+        }
+        self.set_countdown(None);
+        self.set_start_time(now);
+        true
+    }
+
+    /// Draws the big centered countdown digit (3, 2, 1) over the board while counting down.
+    pub fn draw_countdown(&self, cell_size: f32) {
+        let Some(remaining) = self.countdown() else {
+            return;
+        };
+        let text = (remaining.ceil() as u32).to_string();
+        let dim = measure_text(&text, None, COUNTDOWN_FONT_SIZE as u16, 1.0);
+        let board_w = self.board().width() as f32 * cell_size;
+        let board_h = self.board().height() as f32 * cell_size;
+        self.draw_text_sharp(
+            &text,
+            (board_w - dim.width) / 2.0,
+            TOP_BAR_HEIGHT + (board_h + dim.height) / 2.0,
+            COUNTDOWN_FONT_SIZE,
+            COLOR_COUNTDOWN,
         );
-        x + measure_text(&time_str, None, FONT_SIZE as u16, 1.0).width + spacing
+    }
+
+    /// Draws a top-bar button (an icon or a labeled rectangle) and returns whether it was
+    /// clicked. While the mouse is over it, the button is drawn slightly larger around its
+    /// center and brighter. Hit-testing always uses the un-scaled `rect`, so the grown button
+    /// can't flicker in and out of hover at its edges.
+    fn draw_button_hoverable(&self, rect: Rect, face: ButtonFace) -> bool {
+        let (mx, my) = mouse_position();
+        let hovered = rect.contains(Vec2::new(mx, my));
+        let scale = if hovered { HOVER_SCALE } else { 1.0 };
+        let (w, h) = (rect.w * scale, rect.h * scale);
+        let (x, y) = (rect.x - (w - rect.w) / 2.0, rect.y - (h - rect.h) / 2.0);
+        match face {
+            ButtonFace::Icon(texture) => {
+                if hovered {
+                    draw_rectangle(x, y, w, h, COLOR_ICON_HOVER);
+                }
+                draw_texture_ex(
+                    texture,
+                    x,
+                    y,
+                    WHITE,
+                    DrawTextureParams {
+                        dest_size: Some(Vec2::new(w, h)),
+                        ..Default::default()
+                    },
+                );
+            }
+            ButtonFace::Label(label) => {
+                draw_rectangle(x, y, w, h, if hovered { COLOR_BTN_HOVER } else { COLOR_BTN });
+                let font_size = FONT_SIZE * scale;
+                let label_dim = measure_text(label, None, font_size as u16, 1.0);
+                self.draw_text_sharp(
+                    label,
+                    x + (w - label_dim.width) / 2.0,
+                    y + h * 0.7,
+                    font_size,
+                    COLOR_TEXT,
+                );
+            }
+        }
+        hovered && is_mouse_button_pressed(MouseButton::Left)
     }
 
     /// Draws the board size dropdown button (but NOT the dropdown menu itself).
     /// Returns the new x position after this section.
     fn draw_board_size_dropdown_button(&mut self, x: f32, spacing: f32) -> f32 {
-        let btn_label = format!("{}{}", self.board_size().label(), BTN_LABEL_SUFFIX);
+        // Boards that don't match their preset (e.g. a custom mine count) are labeled by density
+        let board = self.board();
+        let preset = !matches!(self.board_size(), BoardSize::Custom { .. })
+            && (board.width(), board.height(), board.mines()) == self.board_size().params();
+        let size_label = if preset {
+            self.board_size().label()
+        } else {
+            board.difficulty_label()
+        };
+        let btn_label = format!("{}{}", size_label, BTN_LABEL_SUFFIX);
+        let rect = Rect::new(x, ICON_Y, BTN_W, BTN_H);
+        let clicked = self.draw_button_hoverable(rect, ButtonFace::Label(&btn_label));
+        // Handle dropdown click
+        if is_mouse_button_pressed(MouseButton::Left) {
+            if self.ignore_next_size_popup_click() {
+                self.set_ignore_next_size_popup_click(false); // Reset the flag
+            } else if clicked {
+                self.set_show_size_popup(true);
+            }
+        }
+        x + BTN_W + spacing
+    }
+
+    /// Draws the "Play" button shown in the level editor and handles click.
+    /// Clicking it locks the edited layout and starts the game.
+    /// Returns the new x position after this section.
+    fn draw_play_layout_button(&mut self, x: f32, spacing: f32) -> f32 {
         draw_rectangle(x, ICON_Y, BTN_W, BTN_H, COLOR_BTN);
-        let label_dim = measure_text(&btn_label, None, FONT_SIZE as u16, 1.0);
-        draw_text(
-            &btn_label,
+        let label_dim = measure_text(PLAY_BTN_LABEL, None, FONT_SIZE as u16, 1.0);
+        self.draw_text_sharp(
+            PLAY_BTN_LABEL,
             x + (BTN_W - label_dim.width) / 2.0,
             ICON_Y + BTN_H * 0.7,
             FONT_SIZE,
             COLOR_TEXT,
         );
-        // Handle dropdown click
         if is_mouse_button_pressed(MouseButton::Left) {
-                if self.ignore_next_size_popup_click() {
-                    self.set_ignore_next_size_popup_click(false); // Reset the flag
-                } else {
-                    let (mx, my) = mouse_position();
-                    if mx >= x && mx <= x + BTN_W && my >= ICON_Y && my <= ICON_Y + BTN_H {
-                        self.set_show_size_popup(true);
-                    }
-                }
+            let (mx, my) = mouse_position();
+            if (x..=x + BTN_W).contains(&mx) && (ICON_Y..=ICON_Y + BTN_H).contains(&my) {
+                self.play_edited_layout();
+            }
         }
         x + BTN_W + spacing
     }
 
+    /// Draws the result of the last editor layout save or load. Pressing Escape dismisses it.
+    pub fn draw_editor_message(&mut self) {
+        if is_key_pressed(KeyCode::Escape) {
+            self.set_editor_message(None);
+        }
+        if let Some(message) = self.editor_message() {
+            self.draw_notice_panel(&[message, "Press Esc to dismiss."]);
+        }
+    }
+
+    /// Switches to a new board size and starts a fresh game on it.
+    /// Returns false (and changes nothing) if the size is already selected.
+    /// The window is resized separately by `resize_window_to_board`, which needs a window.
+    pub fn change_board_size(&mut self, size: BoardSize) -> bool {
+        if self.board_size() == size {
+            return false;
+        }
+        self.set_board_size(size);
+        self.reset_game();
+        true
+    }
+
+    /// Returns the largest whole-pixel cell size at which the board and the top bar fit in the
+    /// `available` (width, height) area, clamped between `MIN_CELL_SIZE` and `MAX_CELL_SIZE`.
+    pub fn compute_cell_size(&self, available: (f32, f32)) -> f32 {
+        let (width, height) = (self.board().width() as f32, self.board().height() as f32);
+        let fit_width = available.0 / width.max(1.0);
+        let fit_height = (available.1 - TOP_BAR_HEIGHT) / height.max(1.0);
+        fit_width
+            .min(fit_height)
+            .floor()
+            .clamp(MIN_CELL_SIZE, MAX_CELL_SIZE)
+    }
+
+    /// Resizes the window to fit the current board size. The cells keep the size they would
+    /// have in the current window, so a window the player enlarged stays large, but are never
+    /// smaller than the size's preset (so switching from a bigger board doesn't shrink them).
+    pub fn resize_window_to_board(&self) {
+        let fit = self.compute_cell_size((screen_width(), screen_height()));
+        let cell_size = fit.max(self.board_size().cell_size());
+        use macroquad::window::request_new_screen_size;
+        // Logical size: macroquad multiplies it by the DPI scale itself
+        request_new_screen_size(
+            self.board().width() as f32 * cell_size,
+            self.board().height() as f32 * cell_size + TOP_BAR_HEIGHT,
+        );
+    }
+
     /// Draws the dropdown menu for board size selection.
     /// Call this AFTER drawing the board, so it appears on top of the cells.
     pub fn draw_board_size_dropdown_menu(&mut self, x: f32) {
@@ -180,10 +662,17 @@ impl MinesweeperApp {
             return;
         }
         let sizes = [BoardSize::Small, BoardSize::Medium, BoardSize::Large];
+        let skins = self.skins().clone();
         let popup_x = x;
         let popup_y = ICON_Y + BTN_H;
-        let popup_w = BTN_W;
-        let popup_h = sizes.len() as f32 * BTN_H;
+        // Widen the menu if a skin name (or "Custom...") doesn't fit the button width
+        let popup_w = skins
+            .iter()
+            .map(String::as_str)
+            .chain([CUSTOM_LABEL])
+            .map(|name| measure_text(name, None, FONT_SIZE as u16, 1.0).width + DROPDOWN_PADDING)
+            .fold(BTN_W, f32::max);
+        let popup_h = (sizes.len() + 2 + skins.len()) as f32 * BTN_H;
         draw_rectangle(popup_x, popup_y, popup_w, popup_h, COLOR_DROPDOWN_BG);
         for (i, &size) in sizes.iter().enumerate() {
             let by = popup_y + i as f32 * BTN_H;
@@ -200,7 +689,7 @@ impl MinesweeperApp {
             );
             let label = size.label();
             let label_dim = measure_text(label, None, FONT_SIZE as u16, 1.0);
-            draw_text(
+            self.draw_text_sharp(
                 label,
                 popup_x + (popup_w - label_dim.width) / 2.0,
                 by + BTN_H * 0.7,
@@ -214,20 +703,102 @@ impl MinesweeperApp {
                     if self.board_size() == size {
                         return;
                     }
-                    self.set_board_size(size);
-                    let (w, h, _) = size.params();
-                    use macroquad::window::request_new_screen_size;
-                    request_new_screen_size(
-                        w as f32 * size.cell_size(),
-                        h as f32 * size.cell_size() + TOP_BAR_HEIGHT,
-                    );
-                    self.reset_game();
+                    if self.request_new_game(NewGameRequest::Size(size)) {
+                        self.resize_window_to_board();
+                    } else {
+                        self.set_show_size_popup(false); // Make way for the confirmation popup
+                    }
                     // self.set_show_size_popup(false); // Close the dropdown
                     self.set_ignore_next_size_popup_click(true);  // Ignore the next click to prevent immediate reopen
                     return;
                 }
             }
         }
+        // The daily puzzle (same layout for everyone today) is listed below the sizes
+        let by = popup_y + sizes.len() as f32 * BTN_H;
+        draw_rectangle(popup_x, by, popup_w, BTN_H, COLOR_BTN_UNSELECTED);
+        let label_dim = measure_text(DAILY_LABEL, None, FONT_SIZE as u16, 1.0);
+        self.draw_text_sharp(
+            DAILY_LABEL,
+            popup_x + (popup_w - label_dim.width) / 2.0,
+            by + BTN_H * 0.7,
+            FONT_SIZE,
+            COLOR_TEXT,
+        );
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let (mx, my) = mouse_position();
+            if mx >= popup_x && mx <= popup_x + popup_w && my >= by && my <= by + BTN_H {
+                self.request_new_game(NewGameRequest::Daily);
+                self.set_show_size_popup(false);
+                self.set_ignore_next_size_popup_click(true);
+                return;
+            }
+        }
+        // Below it, "Custom..." opens a dialog to type in any width, height, and mine count
+        let by = popup_y + (sizes.len() + 1) as f32 * BTN_H;
+        let selected = matches!(self.board_size(), BoardSize::Custom { .. });
+        draw_rectangle(
+            popup_x,
+            by,
+            popup_w,
+            BTN_H,
+            if selected {
+                COLOR_BTN_SELECTED
+            } else {
+                COLOR_BTN_UNSELECTED
+            },
+        );
+        let label_dim = measure_text(CUSTOM_LABEL, None, FONT_SIZE as u16, 1.0);
+        self.draw_text_sharp(
+            CUSTOM_LABEL,
+            popup_x + (popup_w - label_dim.width) / 2.0,
+            by + BTN_H * 0.7,
+            FONT_SIZE,
+            COLOR_TEXT,
+        );
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let (mx, my) = mouse_position();
+            if mx >= popup_x && mx <= popup_x + popup_w && my >= by && my <= by + BTN_H {
+                self.open_custom_dialog();
+                while get_char_pressed().is_some() {} // Drop keys typed before it opened
+                self.set_show_size_popup(false);
+                self.set_ignore_next_size_popup_click(true);
+                return;
+            }
+        }
+        // Skin options are listed below the custom size entry
+        for (i, name) in skins.iter().enumerate() {
+            let by = popup_y + (sizes.len() + 2 + i) as f32 * BTN_H;
+            draw_rectangle(
+                popup_x,
+                by,
+                popup_w,
+                BTN_H,
+                if self.skin_name() == name {
+                    COLOR_BTN_SELECTED
+                } else {
+                    COLOR_BTN_UNSELECTED
+                },
+            );
+            let label_dim = measure_text(name, None, FONT_SIZE as u16, 1.0);
+            self.draw_text_sharp(
+                name,
+                popup_x + (popup_w - label_dim.width) / 2.0,
+                by + BTN_H * 0.7,
+                FONT_SIZE,
+                COLOR_TEXT,
+            );
+            // Handle click on a skin option (the game loop loads it on the next frame)
+            if is_mouse_button_pressed(MouseButton::Left) {
+                let (mx, my) = mouse_position();
+                if mx >= popup_x && mx <= popup_x + popup_w && my >= by && my <= by + BTN_H {
+                    self.set_skin_name(name);
+                    self.set_show_size_popup(false);
+                    self.set_ignore_next_size_popup_click(true);
+                    return;
+                }
+            }
+        }
         // Optional: click outside to close the popup
         if is_mouse_button_pressed(MouseButton::Left) {
             let (mx, my) = mouse_position();
@@ -245,21 +816,9 @@ impl MinesweeperApp {
     /// Draws the new game icon and handles click.
     /// Returns the new x position after this section.
     fn draw_new_game_icon(&mut self, x: f32, new_game_texture: &Texture2D, spacing: f32) -> f32 {
-        draw_texture_ex(
-            new_game_texture,
-            x,
-            ICON_Y,
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(Vec2::new(ICON_SIZE, ICON_SIZE)),
-                ..Default::default()
-            },
-        );
-        if is_mouse_button_pressed(MouseButton::Left) {
-            let (mx, my) = mouse_position();
-            if mx >= x && mx <= x + ICON_SIZE && my >= ICON_Y && my <= ICON_Y + ICON_SIZE {
-                self.reset_game();
-            }
+        let rect = Rect::new(x, ICON_Y, ICON_SIZE, ICON_SIZE);
+        if self.draw_button_hoverable(rect, ButtonFace::Icon(new_game_texture)) {
+            self.request_new_game(NewGameRequest::Next);
         }
         x + ICON_SIZE + spacing
     }
@@ -271,21 +830,8 @@ impl MinesweeperApp {
         } else {
             mute_texture// Show volume icon
         };
-        draw_texture_ex(
-            sound_icon,
-            x,
-            ICON_Y,
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(Vec2::new(ICON_SIZE, ICON_SIZE)),
-                ..Default::default()
-            },
-        );
-        let (mx, my) = mouse_position();
-        if is_mouse_button_pressed(MouseButton::Left)
-            && mx >= x && mx <= x + ICON_SIZE
-            && my >= ICON_Y && my <= ICON_Y + ICON_SIZE
-        {
+        let rect = Rect::new(x, ICON_Y, ICON_SIZE, ICON_SIZE);
+        if self.draw_button_hoverable(rect, ButtonFace::Icon(sound_icon)) {
             self.set_sound(!self.sound());
         }
     }
@@ -297,10 +843,48 @@ impl MinesweeperApp {
         flag_texture: &Texture2D,
         clock_texture: &Texture2D,
     ) {
-        let mut x = self.top_bar_start_x();
-        let spacing = self.top_bar_spacing();
-        x = self.draw_flags_left_section(x, flag_texture, spacing);
-        x = self.draw_timer_section(x, clock_texture, spacing);
-        self.draw_board_size_dropdown_menu(x);
+        let layout = self.measure_top_bar_layout();
+        self.draw_flags_left_section(layout.flags_x, flag_texture, layout.spacing);
+        self.draw_timer_section(layout.timer_x, clock_texture, layout.spacing);
+        self.draw_board_size_dropdown_menu(layout.size_button_x);
     }
 }
+
+/// Writes text to the system clipboard using the `clipboard` crate.
+#[cfg(feature = "clipboard")]
+fn copy_to_clipboard(text: &str) -> bool {
+    use clipboard::{ClipboardContext, ClipboardProvider};
+    let ctx: Result<ClipboardContext, _> = ClipboardProvider::new();
+    ctx.and_then(|mut ctx| ctx.set_contents(text.to_owned())).is_ok()
+}
+
+/// Clipboard support is disabled in this build, so nothing is copied.
+#[cfg(not(feature = "clipboard"))]
+fn copy_to_clipboard(_text: &str) -> bool {
+    false
+}
+
+/// Returns today's date in UTC as `YYYY-MM-DD`, the key of the daily puzzle.
+pub(crate) fn today_utc() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (year, month, day) = civil_date(secs);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Returns the UTC (year, month, day) of a Unix timestamp in seconds.
+pub(crate) fn civil_date(secs: u64) -> (i64, i64, i64) {
+    // Convert days since 1970-01-01 to a civil date (proleptic Gregorian calendar)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
@@ -1,11 +1,29 @@
 pub mod board;                // Exposes the board module to others
+pub mod solver;               // Exposes mine probability estimation
+pub mod replay;               // Exposes game replays
+pub use replay::Replay;
 pub use board::*; // Re-exports for easy access
 pub use gui::MinesweeperApp;            // Re-export main app struct
 pub use gui::GameState;
+pub use gui::RevealOrder;
+pub use gui::TimerPrecision;
+pub use gui::NumberPalette;
+pub use gui::CounterMode;
+pub use gui::WaveOrder;
+pub use gui::NewGameRequest;
+pub use gui_skin::available_skins;
+pub use gui_screenshot::screenshot_file_name;
+pub use gui_ui::TopBarLayout;
+pub use gui_popup::CustomBoardDialog;
 pub use particle::Particle;
 mod gui;                      // Keeps gui private, but you re-export types below
 mod particle;             // Exposes particle module
+mod gui_accessibility;    // Exposes screen-reader text descriptions
 mod gui_animation;        // Exposes animation helpers
+mod gui_assets;           // Exposes asset loading with placeholders
 mod gui_board;            // Exposes GUI board helpers
+mod gui_debug;            // Exposes debug overlay helpers
+mod gui_skin;             // Exposes skin (icon set) loading
 mod gui_popup;            // Exposes popup helpers
+mod gui_screenshot;       // Exposes end-of-game screenshot export
 mod gui_ui;               // Exposes UI helpers
@@ -7,11 +7,18 @@
 mod board;
 mod gui;
 use gui::MinesweeperApp;
+mod gui_accessibility;
 mod gui_animation;
+mod gui_assets;
 mod gui_board;
+mod gui_debug;
 mod gui_popup;
+mod gui_screenshot;
+mod gui_skin;
 mod gui_ui;
 mod particle;
+mod replay;
+mod solver;
 
 
 // Medium
@@ -29,6 +36,9 @@ fn window_conf() -> macroquad::conf::Conf {
             window_title: "Minesweeper".to_owned(),
             window_width: (BOARD_WIDTH as f32 * CELL_SIZE) as i32,
             window_height: (BOARD_HEIGHT as f32 * CELL_SIZE + TOP_BAR_HEIGHT) as i32,
+            // Full-resolution framebuffer on retina displays; the window size above stays in
+            // logical pixels and macroquad scales it (and mouse positions) by the DPI factor
+            high_dpi: true,
             ..Default::default()
         },
         ..Default::default()
@@ -0,0 +1,91 @@
+//! Game replays for Minesweeper.
+//!
+//! This module defines `Replay`, a record of one seeded game: the board size, the seed and
+//! opening cell used to place the mines, and every move the player made. Because seeded mine
+//! placement is deterministic, the board at any point of the game can be rebuilt from this
+//! record alone, which lets a replay viewer scrub back and forth through the moves.
+
+use crate::board::*;
+
+/// A recorded game: enough to rebuild the board after any number of moves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    width: usize,
+    height: usize,
+    mines: usize,
+    seed: u64,
+    opening: (usize, usize), // Cell the mines were placed around (the first click)
+    topology: Topology,      // How the board's cells connect (`Topology::Square` by default)
+    moves: Vec<Move>,
+}
+
+impl Replay {
+    /// Starts an empty replay for a board whose mines were placed with
+    /// `place_mines_avoiding_seeded(opening.0, opening.1, seed)`.
+    pub fn new(
+        width: usize,
+        height: usize,
+        mines: usize,
+        seed: u64,
+        opening: (usize, usize),
+    ) -> Self {
+        Replay {
+            width,
+            height,
+            mines,
+            seed,
+            opening,
+            topology: Topology::Square,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Returns the seed the mines were placed from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the cell the mines were placed around.
+    pub fn opening(&self) -> (usize, usize) {
+        self.opening
+    }
+
+    /// Sets the topology of the recorded board, so rebuilt boards number their cells the same.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Returns the recorded moves, in the order they were played.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Returns the number of recorded moves.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Returns true if no move has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Records a move at the end of the replay.
+    pub fn push(&mut self, mv: Move) {
+        self.moves.push(mv);
+    }
+
+    /// Rebuilds the board as it was after the first `index` moves (clamped to the number of
+    /// moves): a fresh board with the seeded mines, with those moves applied in order.
+    /// `board_at(0)` is the board before the first move, with every cell covered.
+    pub fn board_at(&self, index: usize) -> Board {
+        let mut board = Board::new(self.width, self.height, self.mines);
+        let (row, col) = self.opening;
+        board.place_mines_avoiding_seeded(row, col, self.seed);
+        board.set_topology(self.topology); // Also calculates the numbers
+        for &mv in &self.moves[..index.min(self.moves.len())] {
+            board.apply_move(mv);
+        }
+        board
+    }
+}
@@ -0,0 +1,385 @@
+//! Mine probability estimation for Minesweeper.
+//!
+//! This module estimates how likely each covered cell is to hold a mine, using only what the
+//! player can see: the numbers on uncovered cells and the total mine count (flags are not
+//! trusted). Covered cells next to a number (the "frontier") are split into independent groups
+//! that share no number; every valid mine assignment of each group is enumerated, and the
+//! groups are combined with the number of ways the remaining mines fit in the other covered
+//! cells. Covered cells away from the frontier (isolated cells) get the density left over.
+//!
+//! It also provides `solve_step`, which picks the next logically certain move for the
+//! auto-solver, and `subset_deductions`, the pairwise subset rule on its own.
+
+use crate::board::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// --- Solver limits ---
+const MAX_GROUP_STEPS: usize = 1_000_000; // Backtracking steps before a group is treated as isolated
+
+/// A visible number's constraint: its unknown neighbors and how many of them are mines.
+type Constraint = (Vec<(usize, usize)>, usize);
+
+/// Mine-count statistics of one frontier group, indexed by the number of mines in the group:
+/// how many valid assignments have that many mines, and how often each cell is a mine in them.
+struct GroupStats {
+    cells: Vec<(usize, usize)>,
+    solutions: Vec<f64>,       // solutions[k] = assignments with k mines
+    cell_mines: Vec<Vec<f64>>, // cell_mines[k][i] = those assignments where cell i is a mine
+}
+
+/// Returns the estimated probability (0.0 to 1.0) that each covered or flagged cell is a mine.
+/// Only visible information is used. Cells next to numbers are computed exactly from all
+/// valid mine layouts; isolated cells share the global density of the remaining mines.
+/// Returns an empty map when the visible numbers are inconsistent (e.g. a board with no
+/// valid layout), and gives up on frontier groups too large to enumerate, treating their
+/// cells like isolated ones.
+pub fn mine_probabilities(board: &Board) -> HashMap<(usize, usize), f32> {
+    let (width, height) = (board.width(), board.height());
+    let is_unknown = |r: usize, c: usize| board.cell_state(r, c) != Some(CellState::Uncovered);
+
+    // Mines the player can already see (exploded) count against the total
+    let mut visible_mines = 0;
+    let mut constraints: Vec<Constraint> = Vec::new();
+    for row in 0..height {
+        for col in 0..width {
+            if is_unknown(row, col) {
+                continue;
+            }
+            let value = match board.cell(row, col) {
+                Some(Cell::Number(n)) => n as usize,
+                Some(Cell::Empty) => 0,
+                _ => {
+                    visible_mines += 1;
+                    continue;
+                }
+            };
+            let cells =
+                board.neighbors_matching(row, col, |_, state| state != CellState::Uncovered);
+            if cells.is_empty() {
+                continue;
+            }
+            let seen_mines = board
+                .neighbors_matching(row, col, |cell, state| {
+                    cell == Cell::Mine && state == CellState::Uncovered
+                })
+                .len();
+            constraints.push((cells, value.saturating_sub(seen_mines)));
+        }
+    }
+    let unknown: Vec<(usize, usize)> = (0..height)
+        .flat_map(|row| (0..width).map(move |col| (row, col)))
+        .filter(|&(r, c)| is_unknown(r, c))
+        .collect();
+    let mines_left = board.mines().saturating_sub(visible_mines);
+
+    // Enumerate each frontier group; groups that are too large join the isolated cells
+    let mut groups = Vec::new();
+    let mut frontier = HashSet::new();
+    for group_constraints in split_groups(&constraints) {
+        let Some(stats) = enumerate_group(&group_constraints) else {
+            continue;
+        };
+        if stats.solutions.iter().all(|&count| count == 0.0) {
+            return HashMap::new(); // No valid layout
+        }
+        frontier.extend(stats.cells.iter().copied());
+        groups.push(stats);
+    }
+    let isolated: Vec<(usize, usize)> = unknown
+        .iter()
+        .copied()
+        .filter(|cell| !frontier.contains(cell))
+        .collect();
+
+    // Distribution of frontier mines over all groups, and over all groups but one
+    let all = groups
+        .iter()
+        .fold(vec![1.0], |acc, group| convolve(&acc, &group.solutions));
+    let without: Vec<Vec<f64>> = (0..groups.len())
+        .map(|skip| {
+            groups
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != skip)
+                .fold(vec![1.0], |acc, (_, group)| {
+                    convolve(&acc, &group.solutions)
+                })
+        })
+        .collect();
+
+    // Ways to place the remaining mines among the isolated cells, scaled to avoid overflow
+    let isolated_ways = IsolatedWays::new(isolated.len(), mines_left, all.len());
+
+    let mut probabilities = HashMap::new();
+    for (group, others) in groups.iter().zip(&without) {
+        let mut total = 0.0;
+        let mut cell_totals = vec![0.0; group.cells.len()];
+        for (k, &count) in group.solutions.iter().enumerate() {
+            let weight: f64 = others
+                .iter()
+                .enumerate()
+                .map(|(t, &ways)| ways * isolated_ways.get(k + t))
+                .sum();
+            total += weight * count;
+            for (i, cell_total) in cell_totals.iter_mut().enumerate() {
+                *cell_total += weight * group.cell_mines[k][i];
+            }
+        }
+        if total == 0.0 {
+            return HashMap::new(); // Not enough (or too many) mines for the visible numbers
+        }
+        for (i, &cell) in group.cells.iter().enumerate() {
+            probabilities.insert(cell, (cell_totals[i] / total) as f32);
+        }
+    }
+
+    if !isolated.is_empty() {
+        let mut total = 0.0;
+        let mut expected_mines = 0.0;
+        for (t, &ways) in all.iter().enumerate() {
+            let weight = ways * isolated_ways.get(t);
+            total += weight;
+            expected_mines += weight * mines_left.saturating_sub(t) as f64;
+        }
+        if total == 0.0 {
+            return HashMap::new();
+        }
+        let density = (expected_mines / total / isolated.len() as f64) as f32;
+        for cell in isolated {
+            probabilities.insert(cell, density);
+        }
+    }
+    probabilities
+}
+
+/// Returns the next move that is certain from visible information (see `Board::deduce_cells`),
+/// or None if the player would have to guess. Wrong flags and maybe marks on proven-safe
+/// cells are removed first, then proven mines are flagged (maybe marks included), then
+/// proven-safe cells are revealed; within each kind, the first cell in row-major order is
+/// chosen so the solver is deterministic.
+pub fn solve_step(board: &Board) -> Option<Move> {
+    let Deductions { safe, mines } = board.deduce_cells();
+    let state = |cell: &(usize, usize)| board.cell_state(cell.0, cell.1);
+    let first = |cells: &HashSet<(usize, usize)>, wanted: &[CellState]| {
+        cells
+            .iter()
+            .filter(|cell| state(cell).is_some_and(|s| wanted.contains(&s)))
+            .min()
+            .copied()
+    };
+    if let Some((row, col)) = first(&safe, &[CellState::Flagged]) {
+        return Some(Move::Unflag(row, col));
+    }
+    if let Some((row, col)) = first(&safe, &[CellState::Maybe]) {
+        return Some(Move::Unmark(row, col));
+    }
+    if let Some((row, col)) = first(&mines, &[CellState::Covered, CellState::Maybe]) {
+        return Some(Move::Flag(row, col));
+    }
+    first(&safe, &[CellState::Covered]).map(|(row, col)| Move::Reveal(row, col))
+}
+
+/// Deduces safe cells and mines by comparing pairs of uncovered numbers: if one number's
+/// covered cells lie inside another's, the extra cells hold the difference of their mines,
+/// which solves patterns like 1-2-1 along a wall. A number that needs none or all of its
+/// cells settles them too, and proven cells are taken out until nothing new follows.
+/// This is `Board::deduce_cells_from_numbers`, the same engine as `Board::deduce_cells`
+/// without the total mine count, so like it, it uses only what the player can see (flags are
+/// not trusted) and its findings are always a subset of `deduce_cells`.
+pub fn subset_deductions(board: &Board) -> Deductions {
+    board.deduce_cells_from_numbers()
+}
+
+/// Splits the constraints into independent groups: two constraints are in the same group
+/// when they share a cell. Constraints are listed in breadth-first order within each group.
+fn split_groups(constraints: &[Constraint]) -> Vec<Vec<Constraint>> {
+    let mut by_cell: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (i, (cells, _)) in constraints.iter().enumerate() {
+        for &cell in cells {
+            by_cell.entry(cell).or_default().push(i);
+        }
+    }
+    let mut visited = vec![false; constraints.len()];
+    let mut groups = Vec::new();
+    for start in 0..constraints.len() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut queue = VecDeque::from([start]);
+        let mut group_constraints = Vec::new();
+        while let Some(i) = queue.pop_front() {
+            group_constraints.push(constraints[i].clone());
+            for cell in &constraints[i].0 {
+                for &j in &by_cell[cell] {
+                    if !visited[j] {
+                        visited[j] = true;
+                        queue.push_back(j);
+                    }
+                }
+            }
+        }
+        groups.push(group_constraints);
+    }
+    groups
+}
+
+/// Enumerates every mine assignment of a group's cells that satisfies all its constraints.
+/// Returns None if the group needs more than `MAX_GROUP_STEPS` backtracking steps.
+fn enumerate_group(constraints: &[Constraint]) -> Option<GroupStats> {
+    // Cells in order of first appearance, which keeps related cells close together
+    let mut cells = Vec::new();
+    let mut seen = HashSet::new();
+    for cell in constraints
+        .iter()
+        .flat_map(|(constraint_cells, _)| constraint_cells)
+    {
+        if seen.insert(*cell) {
+            cells.push(*cell);
+        }
+    }
+    let index: HashMap<(usize, usize), usize> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, &cell)| (cell, i))
+        .collect();
+    let mut cell_constraints = vec![Vec::new(); cells.len()];
+    let mut needs = Vec::new();
+    let mut unassigned = Vec::new();
+    for (c, (constraint_cells, need)) in constraints.iter().enumerate() {
+        for cell in constraint_cells {
+            cell_constraints[index[cell]].push(c);
+        }
+        needs.push(*need as isize);
+        unassigned.push(constraint_cells.len() as isize);
+    }
+
+    let mut search = GroupSearch {
+        cell_constraints,
+        needs,
+        unassigned,
+        assignment: vec![false; cells.len()],
+        solutions: vec![0.0; cells.len() + 1],
+        cell_mines: vec![vec![0.0; cells.len()]; cells.len() + 1],
+        steps: 0,
+    };
+    if !search.assign(0, 0) {
+        return None;
+    }
+    Some(GroupStats {
+        cells,
+        solutions: search.solutions,
+        cell_mines: search.cell_mines,
+    })
+}
+
+/// Backtracking state for `enumerate_group`.
+/// `needs[c]` is how many more mines constraint `c` needs, `unassigned[c]` how many of its
+/// cells are still undecided.
+struct GroupSearch {
+    cell_constraints: Vec<Vec<usize>>,
+    needs: Vec<isize>,
+    unassigned: Vec<isize>,
+    assignment: Vec<bool>,
+    solutions: Vec<f64>,
+    cell_mines: Vec<Vec<f64>>,
+    steps: usize,
+}
+
+impl GroupSearch {
+    /// Tries both values for cell `i` (with `mines` mines placed so far) and recurses.
+    /// Returns false if the step limit was hit.
+    fn assign(&mut self, i: usize, mines: usize) -> bool {
+        self.steps += 1;
+        if self.steps > MAX_GROUP_STEPS {
+            return false;
+        }
+        if i == self.assignment.len() {
+            self.solutions[mines] += 1.0;
+            for (cell, &is_mine) in self.assignment.iter().enumerate() {
+                if is_mine {
+                    self.cell_mines[mines][cell] += 1.0;
+                }
+            }
+            return true;
+        }
+        for is_mine in [false, true] {
+            let mine = is_mine as isize;
+            let mut valid = true;
+            for &c in &self.cell_constraints[i] {
+                self.needs[c] -= mine;
+                self.unassigned[c] -= 1;
+                // Too many mines, or not enough cells left to reach the number
+                if self.needs[c] < 0 || self.needs[c] > self.unassigned[c] {
+                    valid = false;
+                }
+            }
+            let completed = !valid || {
+                self.assignment[i] = is_mine;
+                self.assign(i + 1, mines + is_mine as usize)
+            };
+            for &c in &self.cell_constraints[i] {
+                self.needs[c] += mine;
+                self.unassigned[c] += 1;
+            }
+            self.assignment[i] = false;
+            if !completed {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns the convolution of two mine-count distributions
+/// (the distribution of the total when combining independent groups).
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+    out
+}
+
+/// Number of ways to place the remaining mines among the isolated cells, for each possible
+/// number of frontier mines. Values are divided by their maximum so they never overflow;
+/// only their ratios matter for probabilities.
+struct IsolatedWays {
+    scaled: Vec<f64>,
+}
+
+impl IsolatedWays {
+    fn new(isolated: usize, mines_left: usize, max_frontier_mines: usize) -> Self {
+        let ln_ways: Vec<Option<f64>> = (0..max_frontier_mines)
+            .map(|frontier_mines| {
+                let rest = mines_left.checked_sub(frontier_mines)?;
+                (rest <= isolated).then(|| ln_choose(isolated, rest))
+            })
+            .collect();
+        let max = ln_ways
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let scaled = ln_ways
+            .iter()
+            .map(|ln| ln.map_or(0.0, |ln| (ln - max).exp()))
+            .collect();
+        IsolatedWays { scaled }
+    }
+
+    /// Returns the scaled number of ways for the given number of frontier mines.
+    fn get(&self, frontier_mines: usize) -> f64 {
+        self.scaled.get(frontier_mines).copied().unwrap_or(0.0)
+    }
+}
+
+/// Returns ln(n choose k).
+fn ln_choose(n: usize, k: usize) -> f64 {
+    let k = k.min(n - k);
+    (0..k)
+        .map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln())
+        .sum()
+}
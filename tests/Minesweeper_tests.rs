@@ -1,5 +1,7 @@
 // use macroquad::prelude::get_time;
 use rust_project::*;
+use std::collections::HashSet;
+
 // Checks that placing 10 mines on an 8x8 board results in exactly 10 mines and correct board dimensions.
 #[test]
 fn test_small_board_mine_count() {
@@ -333,4 +335,2794 @@ fn test_first_click_never_hits_mine_medium_board() {
     app.board_mut().place_mines_avoiding(5, 5);
     app.board_mut().uncover_cell(5, 5);
     assert_ne!(app.board().cell(5, 5), Some(Cell::Mine), "First click should never be a mine");
-}
\ No newline at end of file
+}
+// Checks that a board built from a mine layout has the right size, mines, and numbers,
+// and that its ASCII export rebuilds the exact same board.
+#[test]
+fn test_from_mine_layout_round_trips_through_ascii() {
+    let layout = ["*..", "...", "..*"];
+    let board = Board::from_mine_layout(&layout);
+    assert_eq!(board.width(), 3);
+    assert_eq!(board.height(), 3);
+    assert_eq!(board.mines(), 2);
+    assert!(board.mine_positions().contains(&(0, 0)));
+    assert!(board.mine_positions().contains(&(2, 2)));
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(2)));
+    assert_eq!(board.cell(0, 2), Some(Cell::Empty));
+
+    let ascii = board.to_ascii();
+    assert_eq!(ascii, "*1.\n121\n.1*");
+
+    let lines: Vec<&str> = ascii.lines().collect();
+    let rebuilt = Board::from_mine_layout(&lines);
+    assert_eq!(rebuilt.to_ascii(), ascii);
+    assert_eq!(rebuilt.mine_positions(), board.mine_positions());
+}
+
+// Checks that the Nearest reveal order queues mines by ascending Manhattan distance from the clicked mine,
+// skipping the clicked mine and flagged mines, and queues wrong flags along with them.
+#[test]
+fn test_build_reveal_queue_nearest_is_distance_ascending() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    *app.board_mut() = Board::from_mine_layout(&[
+        "*......*",
+        "........",
+        "...*....",
+        "........",
+        "......*.",
+        "........",
+        "........",
+        "*......*",
+    ]);
+    app.board_mut().flag_cell(7, 7); // Correct flag: should not be revealed
+    app.board_mut().flag_cell(3, 3); // Wrong flag: should be queued as a mistake
+    app.build_reveal_queue(2, 3, RevealOrder::Nearest);
+
+    let queue = app.mine_reveal_queue();
+    assert_eq!(queue.len(), 5, "4 unflagged mines plus 1 wrong flag should be queued");
+    assert!(!queue.iter().any(|&(r, c, _)| (r, c) == (2, 3)), "Clicked mine should not be queued");
+    assert!(!queue.iter().any(|&(r, c, _)| (r, c) == (7, 7)), "Flagged mine should not be queued");
+    assert!(queue.contains(&(3, 3, false)), "Wrong flag should be queued");
+
+    let distances: Vec<usize> = queue
+        .iter()
+        .map(|&(r, c, _)| r.abs_diff(2) + c.abs_diff(3))
+        .collect();
+    assert!(
+        distances.windows(2).all(|w| w[0] <= w[1]),
+        "Nearest order should be distance-ascending, got {:?}",
+        distances
+    );
+}
+
+// Checks that toggling a mine on and off updates the cell, mine positions, mine count, and neighbor numbers.
+#[test]
+fn test_toggle_mine_on_and_off() {
+    let mut board = Board::new(3, 3, 0);
+    board.toggle_mine(0, 0);
+    assert_eq!(board.cell(0, 0), Some(Cell::Mine));
+    assert!(board.mine_positions().contains(&(0, 0)));
+    assert_eq!(board.mines(), 1);
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(1)));
+    assert_eq!(board.cell(2, 2), Some(Cell::Empty));
+
+    board.toggle_mine(0, 1);
+    assert_eq!(board.mines(), 2);
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(2)));
+    assert_eq!(board.cell(0, 2), Some(Cell::Number(1)));
+
+    board.toggle_mine(0, 0);
+    assert_eq!(board.cell(0, 0), Some(Cell::Number(1)), "Removed mine should become a number");
+    assert!(!board.mine_positions().contains(&(0, 0)));
+    assert_eq!(board.mines(), 1);
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(1)));
+
+    // Out-of-bounds toggles are ignored
+    board.toggle_mine(5, 5);
+    assert_eq!(board.mines(), 1);
+}
+
+// Checks that the level editor starts on an empty board and is cleared by reset_game.
+#[test]
+fn test_editor_mode_starts_empty_and_resets() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert!(!app.editor_mode(), "Editor should be off by default");
+    app.start_editor();
+    assert!(app.editor_mode());
+    assert_eq!(app.board().mines(), 0, "Editor should start with no mines");
+    app.board_mut().toggle_mine(4, 4);
+    assert_eq!(app.board().mines(), 1);
+    app.reset_game();
+    assert!(!app.editor_mode(), "Reset should leave the editor");
+    assert_eq!(app.board().mines(), 10);
+}
+
+// Checks that seeded mine placement is deterministic for the same seed and first click.
+#[test]
+fn test_seeded_mine_placement_is_deterministic() {
+    let mut a = Board::new(16, 16, 40);
+    let mut b = Board::new(16, 16, 40);
+    a.place_mines_avoiding_seeded(5, 5, 1234);
+    b.place_mines_avoiding_seeded(5, 5, 1234);
+    assert_eq!(a.mine_positions(), b.mine_positions());
+    assert_eq!(a.mine_positions().len(), 40);
+    assert!(!a.mine_positions().contains(&(5, 5)));
+}
+
+// Checks that the game seed is formatted for display and cleared on reset.
+#[test]
+fn test_seed_is_cleared_on_reset() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.seed(), None, "No seed before the first click");
+    app.set_seed(Some(0xABCD));
+    assert_eq!(app.seed_string(), Some("000000000000abcd".to_string()));
+    app.reset_game();
+    assert_eq!(app.seed(), None, "Seed should be cleared after reset");
+    assert_eq!(app.seed_string(), None);
+}
+
+// Checks that covered_neighbors and flagged_neighbors filter neighbors by cell state.
+#[test]
+fn test_covered_and_flagged_neighbors() {
+    let mut board = Board::new(3, 3, 0);
+    board.flag_cell(0, 0);
+    board.flag_cell(2, 1);
+    board.uncover_cell(0, 1);
+    board.uncover_cell(1, 2);
+
+    let mut covered: Vec<_> = board.covered_neighbors(1, 1).collect();
+    covered.sort();
+    assert_eq!(covered, vec![(0, 2), (1, 0), (2, 0), (2, 2)]);
+
+    let mut flagged: Vec<_> = board.flagged_neighbors(1, 1).collect();
+    flagged.sort();
+    assert_eq!(flagged, vec![(0, 0), (2, 1)]);
+
+    // Corner cell only sees its in-bounds neighbors
+    let flagged_corner: Vec<_> = board.flagged_neighbors(2, 2).collect();
+    assert_eq!(flagged_corner, vec![(2, 1)]);
+}
+
+// Checks that the debug overlay is off by default and that the frame-time average
+// only covers the most recent frames.
+#[test]
+fn test_debug_overlay_frame_time_average() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert!(!app.show_debug_overlay(), "Debug overlay should be hidden by default");
+    assert_eq!(app.average_frame_time_ms(), 0.0);
+
+    for _ in 0..100 {
+        app.record_frame_time(0.1);
+    }
+    for _ in 0..60 {
+        app.record_frame_time(0.02);
+    }
+    assert_eq!(app.frame_times().len(), 60, "Only the last 60 frames should be kept");
+    assert!((app.average_frame_time_ms() - 20.0).abs() < 0.01);
+}
+
+// Checks that with auto-open enabled, reset_game leaves the board Running with an open region
+// around the (default center) auto-open cell, and that the option survives further resets.
+#[test]
+fn test_auto_open_reveals_region_on_reset() {
+    let mut app = MinesweeperApp::new(16, 16, 40);
+    app.set_auto_open(true);
+    app.reset_game();
+
+    assert_eq!(app.state(), GameState::Running, "Auto-open should start the game");
+    assert!(app.seed().is_some(), "Auto-open should capture a seed");
+    assert_eq!(app.board().cell(8, 8), Some(Cell::Empty), "Center should be an empty opening");
+    assert_eq!(app.board().cell_state(8, 8), Some(CellState::Uncovered));
+    for (r, c) in app.board().neighbors(8, 8).collect::<Vec<_>>() {
+        assert_eq!(app.board().cell_state(r, c), Some(CellState::Uncovered));
+        assert_ne!(app.board().cell(r, c), Some(Cell::Mine));
+    }
+
+    app.set_auto_open_cell(Some((0, 0)));
+    app.reset_game();
+    assert!(app.auto_open(), "Auto-open should persist across resets");
+    assert_eq!(app.board().cell_state(0, 0), Some(CellState::Uncovered));
+
+    app.set_auto_open(false);
+    app.reset_game();
+    assert_eq!(app.state(), GameState::NotStarted);
+}
+
+// Checks that visible_pressure sums only uncovered neighboring numbers.
+#[test]
+fn test_visible_pressure_uses_only_visible_numbers() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..*"]);
+    // Nothing uncovered yet: no pressure anywhere
+    assert_eq!(board.visible_pressure(1, 1), 0.0);
+
+    board.uncover_cell(0, 1); // 1
+    board.uncover_cell(1, 1); // 2
+    assert_eq!(board.visible_pressure(0, 0), 3.0);
+    assert_eq!(board.visible_pressure(1, 0), 3.0);
+    // Covered numbers and the cell's own value don't count
+    assert_eq!(board.visible_pressure(2, 2), 2.0);
+}
+
+// Checks that the Random reveal order queues exactly the expected cells (all unflagged mines except
+// the clicked one, plus wrong flags), and that a seeded game always shuffles them the same way.
+#[test]
+fn test_build_reveal_queue_random_contains_expected_cells() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    *app.board_mut() = Board::from_mine_layout(&[
+        "*......*",
+        "........",
+        "...*....",
+        "........",
+        "......*.",
+        "........",
+        "........",
+        "*......*",
+    ]);
+    app.board_mut().flag_cell(7, 7); // Correct flag: excluded
+    app.board_mut().flag_cell(3, 3); // Wrong flag: included as a mistake
+    app.build_reveal_queue(2, 3, RevealOrder::Random);
+
+    let mut queue = app.mine_reveal_queue().clone();
+    queue.sort();
+    assert_eq!(
+        queue,
+        vec![(0, 0, true), (0, 7, true), (3, 3, false), (4, 6, true), (7, 0, true)]
+    );
+
+    app.set_seed(Some(42));
+    app.build_reveal_queue(2, 3, RevealOrder::Random);
+    let first = app.mine_reveal_queue().clone();
+    app.build_reveal_queue(2, 3, RevealOrder::Random);
+    assert_eq!(app.mine_reveal_queue(), &first, "Seeded shuffle should be deterministic");
+}
+
+// Checks that marathon counters accumulate wins, survive board resets, and start over
+// when marathon mode is turned off.
+#[test]
+fn test_marathon_counters() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert!(!app.marathon(), "Marathon should be off by default");
+    app.set_marathon(true);
+    app.record_marathon_win(30.0);
+    app.record_marathon_win(12.5);
+    assert_eq!(app.marathon_count(), 2);
+    assert_eq!(app.marathon_total_time(), 42.5);
+
+    app.reset_game();
+    assert!(app.marathon(), "Marathon mode should survive a reset");
+    assert_eq!(app.marathon_count(), 2, "Counters should survive a reset");
+    assert_eq!(app.marathon_total_time(), 42.5);
+
+    app.set_marathon(false);
+    assert_eq!(app.marathon_count(), 0, "Leaving marathon mode should clear the counters");
+    assert_eq!(app.marathon_total_time(), 0.0);
+}
+
+// Checks that neighbors_radius covers the full Chebyshev square, clipped to the board.
+#[test]
+fn test_neighbors_radius_counts() {
+    let board = Board::new(5, 5, 0);
+    assert_eq!(board.neighbors_radius(2, 2, 1).count(), 8);
+    assert_eq!(board.neighbors_radius(2, 2, 2).count(), 24);
+    assert_eq!(board.neighbors_radius(0, 0, 2).count(), 8);
+    assert_eq!(board.neighbors_radius(2, 2, 0).count(), 0);
+}
+
+// Checks radius-2 numbers on a board that is empty except for mines in the four corners.
+#[test]
+fn test_calculate_numbers_radius_two_corners() {
+    let mut board = Board::from_mine_layout(&["*...*", ".....", ".....", ".....", "*...*"]);
+    // Radius 1: the center sees nothing
+    assert_eq!(board.cell(2, 2), Some(Cell::Empty));
+
+    board.calculate_numbers_radius(2);
+    assert_eq!(board.cell(2, 2), Some(Cell::Number(4)), "Center reaches all four corners");
+    assert_eq!(board.cell(0, 2), Some(Cell::Number(2)), "Top middle reaches both top corners");
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(1)), "Only the top-left corner is within 2");
+    assert_eq!(board.cell(2, 0), Some(Cell::Number(2)));
+    assert_eq!(board.cell(0, 0), Some(Cell::Mine));
+}
+
+// Checks that queued clicks are drained in order and that clicks on cells uncovered
+// in the meantime (or not allowed in the current state) are skipped.
+#[test]
+fn test_click_queue_skips_stale_clicks() {
+    use macroquad::input::MouseButton;
+
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.set_state(GameState::Running);
+    app.queue_click(0, 0, MouseButton::Left);
+    app.queue_click(1, 1, MouseButton::Left);
+    app.queue_click(2, 2, MouseButton::Right);
+    app.queue_click(3, 3, MouseButton::Left);
+    assert_eq!(app.click_queue().len(), 4);
+
+    // (1, 1) is uncovered before its click is processed
+    app.board_mut().uncover_cell(1, 1);
+    assert_eq!(app.next_queued_click(), Some((0, 0, MouseButton::Left)));
+    assert_eq!(app.next_queued_click(), Some((2, 2, MouseButton::Right)));
+
+    // Once the game is over, remaining clicks are dropped
+    app.set_state(GameState::GameOver);
+    assert_eq!(app.next_queued_click(), None);
+    assert!(app.click_queue().is_empty());
+
+    // Right clicks before the first reveal are ignored, as before
+    app.reset_game();
+    app.queue_click(4, 4, MouseButton::Right);
+    assert_eq!(app.next_queued_click(), None);
+}
+
+// Checks that the compact format round-trips cells and states exactly,
+// and is much smaller than an equivalent JSON encoding on a large board.
+#[test]
+fn test_compact_round_trip_and_size() {
+    let mut board = Board::new(24, 24, 99);
+    board.place_mines_avoiding(12, 12);
+    board.calculate_numbers();
+    board.flood_fill_wave(12, 12);
+    board.flag_cell(0, 0);
+    board.flag_cell(23, 23);
+
+    let compact = board.to_compact();
+    assert!(compact.starts_with("24x24:"));
+    let restored = Board::from_compact(&compact).expect("compact board should parse");
+    assert_eq!(restored.width(), 24);
+    assert_eq!(restored.height(), 24);
+    assert_eq!(restored.mines(), 99);
+    assert_eq!(restored.to_ascii(), board.to_ascii());
+    for row in 0..24 {
+        for col in 0..24 {
+            assert_eq!(restored.cell_state(row, col), board.cell_state(row, col));
+        }
+    }
+
+    // Same information as JSON: a grid of {"cell": ..., "state": ...} objects
+    let json = format!(
+        "{{\"width\":24,\"height\":24,\"cells\":[{}]}}",
+        (0..24)
+            .map(|row| format!(
+                "[{}]",
+                (0..24)
+                    .map(|col| format!(
+                        "{{\"cell\":\"{:?}\",\"state\":\"{:?}\"}}",
+                        board.cell(row, col).unwrap(),
+                        board.cell_state(row, col).unwrap()
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    assert!(
+        compact.len() * 10 < json.len(),
+        "compact ({} bytes) should be far smaller than JSON ({} bytes)",
+        compact.len(),
+        json.len()
+    );
+}
+
+// Checks that malformed compact strings are rejected.
+#[test]
+fn test_from_compact_rejects_malformed_input() {
+    assert!(Board::from_compact("3x3").is_err(), "Missing ':'");
+    assert!(Board::from_compact("3by3:9.").is_err(), "Bad header");
+    assert!(Board::from_compact("3x3:8.").is_err(), "Too few cells");
+    assert!(Board::from_compact("3x3:10.").is_err(), "Too many cells");
+    assert!(Board::from_compact("3x3:8.?").is_err(), "Unknown symbol");
+    assert!(Board::from_compact("3x3/6:9.").is_err(), "Unknown topology");
+    assert!(Board::from_compact("0x3:").is_err(), "Empty board");
+    assert!(Board::from_compact("51x5:255.").is_err(), "Wider than MAX_SIDE");
+    let huge = format!("{}x{}:.", usize::MAX, usize::MAX);
+    assert!(Board::from_compact(&huge).is_err(), "Would not fit in memory");
+    let overflow = format!("3x3:2.{}.", usize::MAX);
+    assert!(Board::from_compact(&overflow).is_err(), "Run length overflows the index");
+    assert!(Board::from_compact("3x3:99999999999999999999999.").is_err(), "Run too long");
+    assert!(Board::from_compact("2x2:3.*").is_ok());
+}
+
+// Checks that the compact format keeps a board's topology, and with it the numbers.
+#[test]
+fn test_compact_round_trip_keeps_topology() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "..*."]);
+    board.set_topology(Topology::VonNeumann);
+    board.apply_move(Move::Reveal(1, 1));
+
+    let compact = board.to_compact();
+    assert!(compact.starts_with("4x3/4:"), "{}", compact);
+    let restored = Board::from_compact(&compact).expect("compact board should parse");
+    assert_eq!(restored.topology(), Topology::VonNeumann);
+    assert_eq!(restored, board);
+    let square = Board::from_compact(&Board::new(3, 3, 0).to_compact()).unwrap();
+    assert_eq!(square.topology(), Topology::Square);
+}
+
+// Checks that a board saved to an in-memory buffer reads back identically,
+// and that unreadable data is rejected.
+#[test]
+fn test_save_to_writer_and_load_from_reader() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "..*."]);
+    board.uncover_cell(0, 3);
+    board.flag_cell(0, 0);
+
+    let mut buffer: Vec<u8> = Vec::new();
+    board.save_to_writer(&mut buffer).expect("writing to a Vec should succeed");
+    let restored = Board::load_from_reader(buffer.as_slice()).expect("board should load");
+    assert_eq!(restored, board);
+
+    assert!(Board::load_from_reader(&b"not a board"[..]).is_err());
+    assert!(Board::load_from_reader(&[0xff, 0xfe][..]).is_err(), "Invalid UTF-8");
+}
+
+// Checks that load_from_reader rejects sizes no board can have and stops reading endless input.
+#[test]
+fn test_load_from_reader_rejects_bad_sizes() {
+    let oversized = format!("{}x{}:.\nchecksum:0\n", usize::MAX, 2);
+    assert!(Board::load_from_reader(oversized.as_bytes()).is_err(), "Huge header");
+    assert!(Board::load_from_reader(&b"0x0:\nchecksum:0\n"[..]).is_err(), "Empty board");
+    let endless = std::io::repeat(b'.');
+    assert!(Board::load_from_reader(endless).is_err(), "Longer than any save");
+}
+
+// Checks that the number histogram matches a hand-counted layout and skips mine cells.
+#[test]
+fn test_number_histogram() {
+    let board = Board::from_mine_layout(&["*..", "...", "..*"]);
+    assert_eq!(board.number_histogram(), [2, 4, 1, 0, 0, 0, 0, 0, 0]);
+
+    let surrounded = Board::from_mine_layout(&["***", "*.*", "***"]);
+    assert_eq!(surrounded.number_histogram(), [0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(Board::new(3, 3, 0).number_histogram()[0], 9, "Before placing mines");
+
+    // Radius-2 numbers count up to 24 neighbors; those above 8 are left out
+    let mut crowded = Board::from_mine_layout(&["*****", "*****", "**.**", "*****", "*****"]);
+    crowded.calculate_numbers_radius(2);
+    assert_eq!(crowded.number_histogram(), [0; 9]);
+}
+
+// Checks that numbers above 9 keep one character per cell in the text exports.
+#[test]
+fn test_text_exports_write_large_numbers_as_one_symbol() {
+    let mut board = Board::from_mine_layout(&["*****", "*****", "**.**", "*****", "*****"]);
+    board.calculate_numbers_radius(2);
+    board.uncover_cell(2, 2);
+    assert_eq!(board.to_ascii().lines().nth(2), Some("**o**"));
+    assert_eq!(board.export_visible()[2], vec!['#', '#', 'o', '#', '#']);
+
+    let mut ten = Board::from_mine_layout(&["*****", "**.**", "*...."]);
+    ten.calculate_numbers_radius(2);
+    assert_eq!(ten.cell(1, 2), Some(Cell::Number(10)));
+    assert_eq!(ten.to_ascii().lines().nth(1), Some("**a**"));
+}
+
+// Checks that reveal_safe_neighbors uncovers exactly the unflagged covered neighbors of a
+// correctly flagged number, flood filling through empties, and refuses wrong flags.
+#[test]
+fn test_reveal_safe_neighbors() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "...."]);
+    board.uncover_cell(0, 1);
+    assert!(board.reveal_safe_neighbors(0, 1).is_empty(), "Mine not flagged yet");
+    board.flag_cell(0, 0);
+
+    let mut revealed = board.reveal_safe_neighbors(0, 1);
+    revealed.sort();
+    let expected: Vec<(usize, usize)> = (0..3)
+        .flat_map(|row| (0..4).map(move |col| (row, col)))
+        .filter(|&cell| cell != (0, 0) && cell != (0, 1))
+        .collect();
+    assert_eq!(revealed, expected, "(0, 2) is empty, so the reveal spreads to the whole board");
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Flagged));
+
+    // A wrong flag satisfies the count, but the real mine is still covered
+    let mut board = Board::from_mine_layout(&["*..", "..."]);
+    board.uncover_cell(0, 1);
+    board.flag_cell(1, 0);
+    assert!(board.reveal_safe_neighbors(0, 1).is_empty());
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Covered));
+    assert_eq!(board.cell_state(1, 1), Some(CellState::Covered));
+    assert!(board.reveal_safe_neighbors(1, 1).is_empty(), "Covered cell");
+}
+
+// Checks that the masked view keeps what the player sees and leaks no hidden mines.
+#[test]
+fn test_masked_view_hides_covered_cells() {
+    let mut board = Board::from_mine_layout(&["*..", "...", ".**"]);
+    board.uncover_cell(0, 2);
+    board.uncover_cell(1, 1);
+    board.flag_cell(0, 0);
+
+    let view = board.masked_view();
+    assert!(view.mine_positions().is_empty());
+    assert_eq!(view.mines(), 3, "The total mine count is public");
+    for row in 0..3 {
+        for col in 0..3 {
+            assert_eq!(view.cell_state(row, col), board.cell_state(row, col));
+            if board.cell_state(row, col) == Some(CellState::Uncovered) {
+                assert_eq!(view.cell(row, col), board.cell(row, col));
+            } else {
+                assert_eq!(view.cell(row, col), Some(Cell::Empty), "({}, {})", row, col);
+            }
+        }
+    }
+    assert_eq!(view.cell(1, 1), Some(Cell::Number(3)));
+    assert_eq!(view.export_visible(), board.export_visible());
+    assert!(!view.to_ascii().contains('*'));
+    assert_eq!(board.mine_positions().len(), 3, "The original board is unchanged");
+}
+
+// Checks that a corner 50/50 is detected and broken up by moving one mine, keeping the mine
+// count and the avoided cells intact.
+#[test]
+fn test_reduce_edge_ambiguity_breaks_corner_fifty_fifty() {
+    // (0, 0) and (0, 1) can't be told apart: the cells bordering only one of them are mines
+    let mut board = Board::from_mine_layout(&["*.*.", "..*.", "....", "...."]);
+    assert_eq!(board.edge_fifty_fifty(), Some(((0, 0), (0, 1))));
+    let before = board.clone();
+
+    let avoid: HashSet<(usize, usize)> = [(1, 1)].into_iter().collect();
+    let moved = board.reduce_edge_ambiguity(&avoid);
+    assert!(moved >= 1);
+    assert_ne!(board, before, "The layout was changed");
+    assert_eq!(board.mines(), 3);
+    assert_eq!(board.mine_positions().len(), 3);
+    assert_eq!(board.edge_fifty_fifty(), None);
+    assert_ne!(board.cell(1, 1), Some(Cell::Mine), "Avoided cells stay safe");
+
+    let mut fair = Board::from_mine_layout(&["....", ".*..", "....", "...."]);
+    assert_eq!(fair.reduce_edge_ambiguity(&HashSet::new()), 0);
+}
+
+// Checks that the flag-planting animation grows from nothing, overshoots, and finishes at full size.
+#[test]
+fn test_flag_planting_animation() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.flag_pop_scale(2, 2), 1.0, "No animation: full size");
+
+    app.flag_timers_mut()[2][2] = Some(0.0);
+    assert_eq!(app.flag_pop_scale(2, 2), 0.0, "Animation starts from nothing");
+    app.advance_flag_timer(2, 2, 0.15);
+    assert!(app.flag_pop_scale(2, 2) > 1.0, "Flag should overshoot mid-animation");
+    app.advance_flag_timer(2, 2, 0.2);
+    assert_eq!(app.flag_timers()[2][2], None, "Timer should clear when finished");
+    assert_eq!(app.flag_pop_scale(2, 2), 1.0);
+
+    app.flag_timers_mut()[3][3] = Some(0.1);
+    app.reset_game();
+    assert_eq!(app.flag_timers()[3][3], None, "Reset should clear flag animations");
+}
+
+// Checks flagged and covered neighbor counts around a numbered cell.
+#[test]
+fn test_adjacent_flag_and_covered_counts() {
+    let mut board = Board::from_mine_layout(&["*.*", "...", "*.."]);
+    board.uncover_cell(1, 1);
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(3)));
+    board.flag_cell(0, 0);
+    board.flag_cell(0, 2);
+    board.uncover_cell(1, 2);
+
+    assert_eq!(board.adjacent_flag_count(1, 1), 2);
+    assert_eq!(board.adjacent_covered_count(1, 1), 5);
+    assert_eq!(board.adjacent_flag_count(2, 2), 0);
+    assert_eq!(board.adjacent_covered_count(2, 2), 1);
+}
+
+// Checks that a number counts as satisfied only when dimming is on and its flag count matches,
+// and that it updates as flags are added and removed.
+#[test]
+fn test_satisfied_number_dimming() {
+    let mut app = MinesweeperApp::new(3, 3, 1);
+    *app.board_mut() = Board::from_mine_layout(&["*..", "...", "..."]);
+    app.board_mut().uncover_cell(1, 1);
+    app.board_mut().flag_cell(0, 0);
+    assert!(!app.is_number_satisfied(1, 1), "Dimming is off by default");
+
+    app.set_dim_satisfied_numbers(true);
+    assert!(app.is_number_satisfied(1, 1));
+    app.board_mut().flag_cell(0, 1);
+    assert!(!app.is_number_satisfied(1, 1), "Too many flags is not satisfied");
+    app.board_mut().unflag_cell(0, 1);
+    app.board_mut().unflag_cell(0, 0);
+    assert!(!app.is_number_satisfied(1, 1), "Too few flags is not satisfied");
+    assert!(!app.is_number_satisfied(2, 2), "Empty cells are never satisfied");
+}
+
+// Checks that boards built through different paths compare equal, and differ after a single flag.
+#[test]
+fn test_board_equality() {
+    let from_layout = Board::from_mine_layout(&["*...", "....", "..*."]);
+    let mut toggled = Board::new(4, 3, 0);
+    toggled.toggle_mine(0, 0);
+    toggled.toggle_mine(2, 2);
+    assert_eq!(from_layout, toggled);
+
+    let compact = Board::from_compact(&toggled.to_compact()).unwrap();
+    assert_eq!(compact, from_layout);
+
+    toggled.flag_cell(1, 1);
+    assert_ne!(from_layout, toggled);
+}
+
+// Checks timer formatting in both precisions and that changing text restarts the roll animation.
+#[test]
+fn test_timer_precision_and_roll() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.timer_precision(), TimerPrecision::Seconds, "Whole seconds by default");
+    assert_eq!(app.format_elapsed(83.47), "01:23");
+    app.set_timer_precision(TimerPrecision::Tenths);
+    assert_eq!(app.format_elapsed(83.47), "01:23.4");
+    assert_eq!(app.format_elapsed(0.0), "00:00.0");
+
+    app.update_timer_text("00:01".to_string(), 0.016);
+    app.update_timer_text("00:01".to_string(), 0.05);
+    assert_eq!(app.timer_roll(), 0.05);
+    app.update_timer_text("00:02".to_string(), 0.016);
+    assert_eq!(app.timer_text(), "00:02");
+    assert_eq!(app.timer_prev_text(), "00:01");
+    assert_eq!(app.timer_roll(), 0.0, "A change restarts the roll");
+
+    app.reset_game();
+    assert_eq!(app.timer_precision(), TimerPrecision::Tenths, "Precision survives a reset");
+}
+
+// Checks that an auto-chord pass on a correctly flagged board reveals only safe cells.
+#[test]
+fn test_auto_chord_all_correct_flags() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "...."]);
+    board.uncover_cell(1, 1);
+    board.flag_cell(0, 0);
+
+    let mut revealed = board.auto_chord_all();
+    revealed.sort();
+    assert!(revealed.contains(&(0, 1)));
+    assert!(revealed.contains(&(2, 2)), "Empty neighbors should flood-fill");
+    assert!(!revealed.iter().any(|&(r, c)| board.cell(r, c) == Some(Cell::Mine)));
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Flagged));
+    // Every safe cell is now uncovered
+    for row in 0..3 {
+        for col in 0..4 {
+            if (row, col) != (0, 0) {
+                assert_eq!(board.cell_state(row, col), Some(CellState::Uncovered));
+            }
+        }
+    }
+    assert!(board.auto_chord_all().is_empty(), "Nothing left to chord");
+}
+
+// Checks that an auto-chord pass on an incorrectly flagged board reveals the mine.
+#[test]
+fn test_auto_chord_all_wrong_flags_reveals_mine() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..."]);
+    board.uncover_cell(1, 1);
+    board.flag_cell(2, 2); // Wrong flag: (1, 1) looks satisfied
+
+    let revealed = board.auto_chord_all();
+    assert!(revealed.contains(&(0, 0)), "The unflagged mine should be uncovered");
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Uncovered));
+    assert!(revealed.iter().any(|&(r, c)| board.cell(r, c) == Some(Cell::Mine)));
+}
+
+// Checks that skins are discovered from subfolders, sorted, with the default skin first,
+// and that a missing skins folder yields only the default skin.
+#[test]
+fn test_available_skins() {
+    let dir = std::env::temp_dir().join(format!("minesweeper_skins_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("retro")).unwrap();
+    std::fs::create_dir_all(dir.join("neon")).unwrap();
+    std::fs::write(dir.join("not_a_skin.txt"), "ignored").unwrap();
+
+    let skins = available_skins(dir.to_str().unwrap());
+    assert_eq!(skins, vec!["Default", "neon", "retro"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(available_skins("definitely/not/a/dir"), vec!["Default"]);
+
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.skin_name(), "Default");
+    app.set_skins(vec!["Default".to_string(), "neon".to_string()]);
+    app.set_skin_name("neon");
+    app.reset_game();
+    assert_eq!(app.skin_name(), "neon", "Selected skin survives a reset");
+    assert_eq!(app.skins().len(), 2);
+}
+
+// Checks that the missing-assets message starts empty, can be dismissed,
+// and stays visible across a game reset until dismissed.
+#[test]
+fn test_asset_error_message() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.asset_error(), None);
+    app.set_asset_error(Some("Missing assets: assets/flag.png".to_string()));
+    app.reset_game();
+    assert_eq!(app.asset_error(), Some("Missing assets: assets/flag.png"));
+    app.set_asset_error(None);
+    assert_eq!(app.asset_error(), None);
+}
+
+// Checks the flagging accuracy for all-correct, all-wrong, and mixed flags,
+// and that a board with no flags reports 1.0 (no wrong flags).
+#[test]
+fn test_correct_flag_ratio() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..*"]);
+    assert_eq!(board.correct_flag_ratio(), 1.0, "No flags means no wrong flags");
+
+    board.flag_cell(0, 0);
+    board.flag_cell(2, 2);
+    assert_eq!(board.correct_flag_ratio(), 1.0);
+
+    board.flag_cell(0, 1);
+    board.flag_cell(1, 1);
+    assert_eq!(board.correct_flag_ratio(), 0.5);
+
+    board.unflag_cell(0, 0);
+    board.unflag_cell(2, 2);
+    assert_eq!(board.correct_flag_ratio(), 0.0);
+}
+
+// Checks that is_forced_guess is false before the first uncover and while a logical move remains
+// (found with the subset rule), and true for a classic 50/50 between two cells.
+#[test]
+fn test_is_forced_guess() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..*"]);
+    assert!(!board.is_forced_guess(), "Nothing uncovered yet");
+
+    board.flood_fill_wave(0, 2);
+    assert_eq!(board.deduce_safe_cells(), HashSet::from([(2, 0)]));
+    assert!(!board.is_forced_guess(), "(2, 0) can be proven safe");
+
+    let mut fifty_fifty = Board::from_mine_layout(&["*.", ".."]);
+    fifty_fifty.uncover_cell(1, 0);
+    fifty_fifty.uncover_cell(1, 1);
+    assert!(fifty_fifty.deduce_safe_cells().is_empty());
+    assert!(fifty_fifty.is_forced_guess());
+
+    let mut app = MinesweeperApp::new(2, 2, 1);
+    *app.board_mut() = fifty_fifty;
+    app.update_forced_guess();
+    assert!(!app.forced_guess(), "Only a running game can require a guess");
+    app.set_state(GameState::Running);
+    app.update_forced_guess();
+    assert!(app.forced_guess());
+}
+
+// Checks that the win wave times every safe cell by its distance from the board center,
+// skips mines, and that the setting survives a reset.
+#[test]
+fn test_start_win_wave_ripples_from_center() {
+    let mut app = MinesweeperApp::new(5, 5, 1);
+    *app.board_mut() = Board::from_mine_layout(&["*....", ".....", ".....", ".....", "....."]);
+    app.start_win_wave();
+
+    let timers = app.wave_timers();
+    assert_eq!(timers[0][0], None, "Mines are not part of the wave");
+    assert_eq!(timers[2][2], Some(0.0), "The center goes first");
+    let near = timers[2][3].unwrap();
+    let far = timers[4][4].unwrap();
+    assert!(near > 0.0 && near < far, "Delay grows with distance from the center");
+    assert_eq!(timers[0][4], timers[4][4], "Equal distances get equal delays");
+
+    app.set_win_wave(true);
+    app.reset_game();
+    assert!(app.win_wave());
+}
+
+// Checks that neighbor_coords yields the same cells as neighbors (corners, edges, interior)
+// and that calculate_numbers matches the iterator-based radius-1 calculation on a Large board.
+#[test]
+fn test_neighbor_coords_matches_neighbors() {
+    let (width, height, mines) = BoardSize::Large.params();
+    let mut board = Board::new(width, height, mines);
+    board.place_mines_avoiding_seeded(12, 12, 7);
+    for row in 0..height {
+        for col in 0..width {
+            let (coords, len) = board.neighbor_coords(row, col);
+            let expected: Vec<(usize, usize)> = board.neighbors(row, col).collect();
+            assert_eq!(&coords[..len], expected.as_slice(), "Cell ({}, {})", row, col);
+        }
+    }
+    assert_eq!(board.neighbor_coords(0, 0).1, 3);
+    assert_eq!(board.neighbor_coords(0, 5).1, 5);
+
+    let mut by_iterator = board.clone();
+    by_iterator.calculate_numbers_radius(1);
+    board.calculate_numbers();
+    assert_eq!(board, by_iterator);
+}
+
+// Times calculate_numbers (stack-array neighbors) against calculate_numbers_radius(1) (the
+// iterator it replaced) on the Large board. Run it with
+// `cargo test --release bench_calculate_numbers -- --ignored --nocapture` (and without
+// `--release` for debug builds).
+#[test]
+#[ignore]
+fn bench_calculate_numbers() {
+    let mut board = Board::new(24, 24, 99);
+    board.place_mines_avoiding(12, 12);
+    const RUNS: u32 = 2_000;
+
+    let start = std::time::Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(&mut board).calculate_numbers_radius(1);
+    }
+    let by_iterator = start.elapsed() / RUNS;
+
+    let start = std::time::Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(&mut board).calculate_numbers();
+    }
+    let by_array = start.elapsed() / RUNS;
+    println!("iterator: {:?}, neighbor_coords: {:?}", by_iterator, by_array);
+}
+
+// Checks that both number palettes give each number 1-8 a distinct color, that the classic
+// palette keeps the traditional colors, and that the palette choice survives a reset.
+#[test]
+fn test_number_palettes() {
+    use macroquad::prelude::{BLUE, GREEN, RED};
+    for palette in [NumberPalette::Classic, NumberPalette::OkabeIto] {
+        let colors: Vec<_> = (1..=8).map(|n| palette.number_color(n)).collect();
+        for i in 0..colors.len() {
+            for j in i + 1..colors.len() {
+                assert_ne!(colors[i], colors[j], "{:?}: {} and {}", palette, i + 1, j + 1);
+            }
+        }
+    }
+    assert_eq!(NumberPalette::Classic.number_color(1), BLUE);
+    assert_eq!(NumberPalette::Classic.number_color(2), GREEN);
+    assert_eq!(NumberPalette::Classic.number_color(3), RED);
+
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.number_palette(), NumberPalette::Classic);
+    app.set_number_palette(NumberPalette::OkabeIto);
+    app.set_show_number_legend(true);
+    app.reset_game();
+    assert_eq!(app.number_palette(), NumberPalette::OkabeIto);
+    assert!(app.show_number_legend());
+}
+
+// Checks that weighted placement never puts mines in a zero-weight zone (the top-left quadrant)
+// or around the avoided cell, and still places every mine.
+#[test]
+fn test_place_mines_weighted_forbids_zero_weight_quadrant() {
+    for _ in 0..20 {
+        let mut board = Board::new(16, 16, 40);
+        board.place_mines_weighted((15, 15), |row, col| {
+            if row < 8 && col < 8 {
+                0.0
+            } else {
+                (row + col) as f32
+            }
+        });
+        assert_eq!(board.mine_positions().len(), 40);
+        assert!(board.mine_positions_within(0..8, 0..8).is_empty());
+        assert!(board.mine_positions_within(14..16, 14..16).is_empty());
+        assert_eq!(board.mine_positions_within(0..16, 0..16).len(), 40);
+    }
+}
+
+// Checks that weighted placement replaces earlier mines instead of adding to them, calculates
+// the numbers, and only keeps the avoided cell's neighbors under the board's topology clear.
+#[test]
+fn test_place_mines_weighted_replaces_mines_and_calculates_numbers() {
+    let mut board = Board::new(3, 3, 6);
+    board.set_topology(Topology::VonNeumann);
+    board.place_mines_weighted((2, 2), |_, _| 1.0);
+    assert!(board.mine_positions().contains(&(0, 0)));
+
+    // Only three cells are kept clear, so the other six (the diagonal (1, 1) too) get mines
+    board.place_mines_weighted((0, 0), |_, _| 1.0);
+    let mines = HashSet::from([(0, 2), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    assert_eq!(*board.mine_positions(), mines);
+    let mine_cells = (0..3)
+        .flat_map(|row| (0..3).map(move |col| (row, col)))
+        .filter(|&(row, col)| board.cell(row, col) == Some(Cell::Mine))
+        .count();
+    assert_eq!(mine_cells, 6, "Earlier mines are cleared");
+
+    let mut expected = board.clone();
+    expected.calculate_numbers();
+    assert_eq!(board, expected);
+}
+
+// Checks that flagging mode turns left-clicks into flag clicks (Shift still uncovers),
+// leaves right-clicks alone, and survives a reset.
+#[test]
+fn test_flag_mode_routes_left_clicks_to_flagging() {
+    use macroquad::input::MouseButton;
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.effective_button(MouseButton::Left, false), MouseButton::Left);
+
+    app.set_flag_mode(true);
+    assert_eq!(app.effective_button(MouseButton::Left, false), MouseButton::Right);
+    assert_eq!(app.effective_button(MouseButton::Left, true), MouseButton::Left);
+    assert_eq!(app.effective_button(MouseButton::Right, false), MouseButton::Right);
+
+    app.reset_game();
+    assert!(app.flag_mode(), "Flagging mode survives a reset");
+}
+
+// Checks that the capped flood fill reveals exactly the same cells as the uncapped one,
+// with distances clamped to the cap and unchanged below it.
+#[test]
+fn test_flood_fill_wave_capped_only_clamps_distances() {
+    let mut layout = vec!["........................"; 24];
+    layout[23] = ".......................*";
+    let mut uncapped = Board::from_mine_layout(&layout);
+    let mut capped = uncapped.clone();
+
+    let full = uncapped.flood_fill_wave(0, 0);
+    let clamped = capped.flood_fill_wave_capped(0, 0, 5);
+    assert_eq!(uncapped, capped, "The same cells are revealed");
+    assert_eq!(full.len(), clamped.len());
+    assert!(full.iter().any(|&(_, _, dist)| dist > 5));
+    for (&(r, c, dist), &(cr, cc, cdist)) in full.iter().zip(&clamped) {
+        assert_eq!((r, c), (cr, cc));
+        assert_eq!(cdist, dist.min(5));
+    }
+}
+
+// Checks that chording only acts on a satisfied number, uncovers its remaining neighbors,
+// reports a mine hit through a wrong flag, and that the click queue accepts chord clicks.
+#[test]
+fn test_chord_uncovers_neighbors_of_satisfied_number() {
+    use macroquad::input::MouseButton;
+    let layout = ["*..", "...", "..."];
+    let mut board = Board::from_mine_layout(&layout);
+    board.uncover_cell(1, 1);
+    assert!(board.chord(1, 1).is_empty(), "No flag placed yet");
+
+    board.flag_cell(0, 0);
+    let revealed = board.chord(1, 1);
+    assert_eq!(revealed.len(), 7);
+    assert!(!revealed.contains(&(0, 0)));
+    assert_eq!(board.cell_state(2, 2), Some(CellState::Uncovered));
+
+    let mut wrong = Board::from_mine_layout(&layout);
+    wrong.uncover_cell(1, 1);
+    wrong.flag_cell(0, 1);
+    let revealed = wrong.chord(1, 1);
+    assert!(revealed.contains(&(0, 0)), "The wrong flag lets the chord hit the mine");
+
+    let mut app = MinesweeperApp::new(3, 3, 1);
+    *app.board_mut() = Board::from_mine_layout(&layout);
+    app.board_mut().uncover_cell(1, 1);
+    app.set_state(GameState::Running);
+    app.queue_click(1, 1, MouseButton::Left);
+    assert_eq!(app.next_queued_click(), Some((1, 1, MouseButton::Left)));
+}
+
+// Checks that the visible export hides covered mines as '#' and shows flags, numbers,
+// and empty uncovered cells as the player sees them.
+#[test]
+fn test_export_visible_hides_covered_mines() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..*"]);
+    board.flood_fill_wave(0, 2);
+    board.flag_cell(2, 2);
+    let visible = board.export_visible();
+    assert_eq!(
+        visible,
+        vec![
+            vec!['#', '1', ' '],
+            vec!['#', '2', '1'],
+            vec!['#', '#', 'F'],
+        ]
+    );
+    assert!(visible.iter().flatten().all(|&ch| ch != '*'));
+}
+
+// Checks that time attack is off by default, counts down from the last move, resets on a move,
+// loses the game (like a mine hit: clock stopped, mines queued) once the limit elapses, and
+// keeps its setting across a reset.
+#[test]
+fn test_time_attack_move_limit() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.set_state(GameState::Running);
+    assert_eq!(app.move_time_limit(), None);
+    assert!(!app.check_move_time_limit(1000.0), "Off by default");
+
+    app.set_move_time_limit(Some(5.0));
+    assert!(!app.check_move_time_limit(100.0), "The countdown starts on the first check");
+    assert_eq!(app.last_move_time(), 100.0);
+    assert_eq!(app.move_time_remaining(103.0), Some(2.0));
+
+    app.record_move(104.0);
+    assert!(!app.check_move_time_limit(108.0), "A move resets the limit");
+    *app.board_mut() = Board::from_mine_layout(&["*.......", "........", ".......*"]);
+    assert!(app.check_move_time_limit(109.5));
+    assert_eq!(app.state(), GameState::GameOver);
+    assert_eq!(app.end_time(), Some(109.5));
+    assert_eq!(app.mine_reveal_queue().len(), 2);
+    assert_eq!(app.move_time_remaining(110.0), None);
+
+    app.reset_game();
+    assert_eq!(app.move_time_limit(), Some(5.0));
+    assert_eq!(app.last_move_time(), 0.0);
+}
+
+// Checks each Move variant: reveal (number, flood fill, mine hit), flag, unflag, and chord,
+// and that moves which don't apply change nothing.
+#[test]
+fn test_apply_move_variants() {
+    let layout = ["*..", "...", "..."];
+    let mut board = Board::from_mine_layout(&layout);
+
+    let result = board.apply_move(Move::Reveal(1, 1));
+    assert_eq!(result.revealed, vec![(1, 1, 0)]);
+    assert_eq!(result.mine_hit, None);
+    assert_eq!(board.apply_move(Move::Reveal(1, 1)), MoveResult::default(), "Already uncovered");
+
+    board.apply_move(Move::Flag(0, 0));
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Flagged));
+    assert_eq!(board.apply_move(Move::Reveal(0, 0)), MoveResult::default(), "Flags protect cells");
+    board.apply_move(Move::Flag(1, 1));
+    assert_eq!(
+        board.cell_state(1, 1),
+        Some(CellState::Uncovered),
+        "Uncovered cells can't be flagged"
+    );
+
+    let result = board.apply_move(Move::Chord(1, 1));
+    assert_eq!(result.revealed.len(), 7);
+    assert!(result.revealed.iter().all(|&(_, _, dist)| dist == 1));
+    assert_eq!(result.mine_hit, None);
+
+    board.apply_move(Move::Unflag(0, 0));
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Covered));
+    let result = board.apply_move(Move::Reveal(0, 0));
+    assert_eq!(result.mine_hit, Some((0, 0)));
+
+    let mut empty = Board::from_mine_layout(&["*...", "....", "...."]);
+    let result = empty.apply_move(Move::Reveal(2, 3));
+    assert_eq!(result.revealed.len(), 11);
+    assert!(result.revealed.contains(&(2, 3, 0)));
+    assert_eq!(result.mine_hit, None);
+}
+
+// Checks that mine probabilities come out exact for small frontiers: an even 50/50,
+// a certain mine (1.0), and a cell proven safe by another number (0.0).
+#[test]
+fn test_mine_probabilities_frontier() {
+    let mut board = Board::from_mine_layout(&["*", ".", "."]);
+    board.apply_move(Move::Reveal(1, 0));
+    let probabilities = solver::mine_probabilities(&board);
+    assert_eq!(probabilities.len(), 2);
+    assert!((probabilities[&(0, 0)] - 0.5).abs() < 1e-6);
+    assert!((probabilities[&(2, 0)] - 0.5).abs() < 1e-6);
+
+    let mut board = Board::from_mine_layout(&[".*.."]);
+    board.apply_move(Move::Reveal(0, 0));
+    board.apply_move(Move::Reveal(0, 2));
+    board.apply_move(Move::Flag(0, 3)); // A wrong flag must not change the estimate
+    let probabilities = solver::mine_probabilities(&board);
+    assert!((probabilities[&(0, 1)] - 1.0).abs() < 1e-6);
+    assert!(probabilities[&(0, 3)].abs() < 1e-6);
+}
+
+// Checks that isolated covered cells get the density of the mines left over after the
+// frontier, and that the app only keeps estimates while the overlay is on and a game runs.
+#[test]
+fn test_mine_probabilities_isolated_density() {
+    let layout = ["*....", ".....", ".....", ".....", "....*"];
+    let mut board = Board::from_mine_layout(&layout);
+    board.apply_move(Move::Reveal(0, 1));
+    let probabilities = solver::mine_probabilities(&board);
+    assert_eq!(probabilities.len(), 24);
+    for cell in [(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)] {
+        assert!((probabilities[&cell] - 0.2).abs() < 1e-6, "Frontier cell {:?}", cell);
+    }
+    assert!((probabilities[&(4, 4)] - 1.0 / 19.0).abs() < 1e-6);
+    let expected_mines: f32 = probabilities.values().sum();
+    assert!((expected_mines - 2.0).abs() < 1e-4);
+
+    let mut app = MinesweeperApp::new(5, 5, 2);
+    *app.board_mut() = board;
+    app.set_state(GameState::Running);
+    app.update_mine_probabilities();
+    assert!(app.mine_probabilities().is_empty(), "Overlay is off by default");
+    app.set_show_probability_overlay(true);
+    assert_eq!(app.mine_probabilities().len(), 24);
+    app.reset_game();
+    assert!(app.show_probability_overlay());
+    assert!(app.mine_probabilities().is_empty(), "No estimates before the game starts");
+}
+
+// Checks that with the first-click grace on, the first reveal after a reset never hits a
+// mine even when the auto-open placed mines beforehand, and that it opens an empty region
+// whenever there is room elsewhere for the mines around it.
+#[test]
+fn test_first_click_grace_after_reset() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    app.set_auto_open(true);
+    app.set_auto_open_cell(Some((0, 0)));
+    app.set_first_click_grace(true);
+    for i in 0..200 {
+        app.reset_game();
+        assert!(app.first_reveal_pending());
+        let (row, col) = (3 + i % 6, 3 + (i * 7) % 6); // Away from the auto-open corner
+        if app.board().cell_state(row, col) != Some(CellState::Covered) {
+            continue;
+        }
+        let board = app.board();
+        let in_opening = |r: usize, c: usize| r.abs_diff(row) <= 1 && c.abs_diff(col) <= 1;
+        let mines_around = board.mine_positions().iter().filter(|&&(r, c)| in_opening(r, c));
+        let room = (0..9)
+            .flat_map(|r| (0..9).map(move |c| (r, c)))
+            .filter(|&(r, c)| !in_opening(r, c) && board.cell(r, c) != Some(Cell::Mine))
+            .filter(|&(r, c)| board.cell_state(r, c) == Some(CellState::Covered))
+            .count();
+        let fits = mines_around.count() <= room;
+
+        app.prepare_reveal(row, col);
+        assert!(!app.first_reveal_pending());
+        assert_ne!(app.board().cell(row, col), Some(Cell::Mine), "Click at {:?}", (row, col));
+        if fits {
+            assert_eq!(app.board().cell(row, col), Some(Cell::Empty), "Room at {:?}", (row, col));
+        }
+        assert_eq!(app.board().mine_positions().len(), 10);
+
+        // Later reveals get no help
+        let mine = *app.board().mine_positions().iter().next().unwrap();
+        let before = app.board().clone();
+        app.prepare_reveal(mine.0, mine.1);
+        assert_eq!(app.board(), &before);
+    }
+
+    app.set_first_click_grace(false);
+    app.reset_game();
+    let before = app.board().clone();
+    let mine = *before.mine_positions().iter().next().unwrap();
+    app.prepare_reveal(mine.0, mine.1);
+    assert_eq!(app.board(), &before, "The grace is off");
+}
+
+// Checks that flags_on_mines returns exactly the correct flags, and that together with the
+// wrong flags they partition all flagged cells.
+#[test]
+fn test_flags_on_mines_partition() {
+    let mut board = Board::from_mine_layout(&["*..*", "....", ".*.."]);
+    for (row, col) in [(0, 0), (0, 1), (2, 1), (2, 3)] {
+        board.apply_move(Move::Flag(row, col));
+    }
+    assert_eq!(board.flags_on_mines(), vec![(0, 0), (2, 1)]);
+
+    let flagged: Vec<(usize, usize)> = (0..board.height())
+        .flat_map(|row| (0..board.width()).map(move |col| (row, col)))
+        .filter(|&(row, col)| board.cell_state(row, col) == Some(CellState::Flagged))
+        .collect();
+    let wrong: Vec<(usize, usize)> = flagged
+        .iter()
+        .copied()
+        .filter(|cell| !board.flags_on_mines().contains(cell))
+        .collect();
+    assert_eq!(wrong, vec![(0, 1), (2, 3)]);
+    assert!(wrong.iter().all(|&(row, col)| board.cell(row, col) != Some(Cell::Mine)));
+    assert_eq!(board.correct_flag_ratio(), 0.5);
+}
+
+// Checks that the daily puzzle is the same for the same date and size, differs between
+// dates and sizes, and always has an empty opening at the board center.
+#[test]
+fn test_daily_puzzle_is_deterministic() {
+    let first = Board::daily(BoardSize::Medium, "2026-10-16");
+    let second = Board::daily(BoardSize::Medium, "2026-10-16");
+    assert_eq!(first, second);
+    assert_eq!(first.mine_positions().len(), 40);
+    assert_eq!(first.cell(8, 8), Some(Cell::Empty));
+
+    assert_ne!(first, Board::daily(BoardSize::Medium, "2026-10-17"));
+    assert_ne!(
+        Board::daily_seed(BoardSize::Small, "2026-10-16"),
+        Board::daily_seed(BoardSize::Large, "2026-10-16")
+    );
+
+    let mut app = MinesweeperApp::new(16, 16, 40);
+    app.start_daily("2026-10-16");
+    assert_eq!(app.state(), GameState::Running);
+    assert_eq!(app.seed(), Some(Board::daily_seed(BoardSize::Medium, "2026-10-16")));
+    assert_eq!(app.board().mine_positions(), first.mine_positions());
+    assert_eq!(app.board().cell_state(8, 8), Some(CellState::Uncovered));
+}
+
+// Checks that a hint picks the deduced-safe cell next to the highest-value numbers.
+#[test]
+fn test_hint_prefers_most_constrained_safe_cell() {
+    let mut board = Board::from_mine_layout(&["....", "....", "***."]);
+    for (row, col) in [(0, 0), (1, 1), (1, 2)] {
+        board.set_cell_state(row, col, CellState::Uncovered);
+    }
+    // (0, 0) is empty, so (0, 1) and (1, 0) are both safe
+    let safe = board.deduce_safe_cells();
+    assert!(safe.contains(&(0, 1)) && safe.contains(&(1, 0)));
+    assert_eq!(board.adjacent_number_sum(0, 1), 5); // Next to the 3 and the 2
+    assert_eq!(board.adjacent_number_sum(1, 0), 3); // Next to the 3 only
+
+    let mut app = MinesweeperApp::new(4, 3, 3);
+    *app.board_mut() = board;
+    assert_eq!(app.request_hint(), None, "No hints before the game starts");
+    app.set_state(GameState::Running);
+    assert_eq!(app.request_hint(), Some((0, 1)));
+    assert_eq!(app.hint(), Some((0, 1)));
+}
+
+// Checks that a board without mines is won by its first click: mine placement places
+// nothing, the reveal flood-fills every cell, and the win check fires exactly once.
+#[test]
+fn test_zero_mine_board_first_click_wins() {
+    let mut app = MinesweeperApp::new(6, 5, 0);
+    app.set_win_wave(true);
+    app.prepare_reveal(2, 3);
+    assert_eq!(app.state(), GameState::Running);
+    assert!(app.board().mine_positions().is_empty());
+
+    let result = app.board_mut().apply_move(Move::Reveal(2, 3));
+    assert_eq!(result.revealed.len(), 30);
+    assert_eq!(result.mine_hit, None);
+    assert!(app.check_win_at(12.5, app.cell_size()));
+    assert_eq!(app.state(), GameState::Won);
+    assert_eq!(app.end_time(), Some(12.5));
+    assert!(!app.check_win_at(13.0, app.cell_size()), "A won game is not won again");
+    assert_eq!(app.board().correct_flag_ratio(), 1.0);
+}
+
+// Checks that a replay rebuilds the board at any move index exactly as it was during live
+// play, and that moves which change nothing are not recorded.
+#[test]
+fn test_replay_board_at_matches_live_play() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    app.prepare_reveal(4, 4);
+    let replay = app.replay().expect("Seeded games get a replay");
+    assert_eq!(replay.seed(), app.seed().unwrap());
+    assert_eq!(replay.opening(), (4, 4));
+    let mut snapshots = vec![app.board().clone()];
+
+    app.play_move(Move::Reveal(4, 4));
+    snapshots.push(app.board().clone());
+    let mines: Vec<(usize, usize)> = {
+        let mut mines: Vec<_> = app.board().mine_positions().iter().copied().collect();
+        mines.sort();
+        mines
+    };
+    app.play_move(Move::Flag(mines[0].0, mines[0].1));
+    snapshots.push(app.board().clone());
+    app.play_move(Move::Reveal(4, 4)); // Already uncovered: not recorded
+    let safe = (0..9)
+        .flat_map(|row| (0..9).map(move |col| (row, col)))
+        .find(|&(row, col)| {
+            app.board().cell_state(row, col) == Some(CellState::Covered)
+                && app.board().cell(row, col) != Some(Cell::Mine)
+        });
+    if let Some((row, col)) = safe {
+        app.play_move(Move::Reveal(row, col));
+        snapshots.push(app.board().clone());
+    }
+    app.play_move(Move::Unflag(mines[0].0, mines[0].1));
+    snapshots.push(app.board().clone());
+
+    let replay = app.replay().unwrap();
+    assert_eq!(replay.len(), snapshots.len() - 1);
+    for (index, snapshot) in snapshots.iter().enumerate() {
+        assert_eq!(&replay.board_at(index), snapshot, "Board after {} moves", index);
+    }
+    assert_eq!(replay.board_at(100), *app.board(), "Indexes past the end clamp");
+}
+
+// Checks remaining_mines with no flags, some flags, exactly as many flags as mines,
+// and more flags than mines (negative).
+#[test]
+fn test_remaining_mines_with_flags() {
+    let mut board = Board::from_mine_layout(&["*..", ".*.", "..."]);
+    assert_eq!(board.flag_count(), 0);
+    assert_eq!(board.remaining_mines(), 2);
+
+    board.apply_move(Move::Flag(0, 0));
+    assert_eq!(board.remaining_mines(), 1);
+    board.apply_move(Move::Flag(2, 2)); // Wrong flags count too
+    assert_eq!(board.flag_count(), 2);
+    assert_eq!(board.remaining_mines(), 0);
+
+    board.apply_move(Move::Flag(0, 2));
+    board.apply_move(Move::Flag(2, 0));
+    assert_eq!(board.remaining_mines(), -2);
+
+    let empty = Board::new(3, 3, 0);
+    assert_eq!(empty.remaining_mines(), 0);
+}
+
+// Checks that the auto-solver plays a no-guess board to a win one certain move at a time,
+// never hitting a mine, and then reports that no move is left.
+#[test]
+fn test_auto_solve_to_win() {
+    let layout = ["*....", ".....", "..*..", ".....", "....*"];
+    let mut app = MinesweeperApp::new(5, 5, 3);
+    *app.board_mut() = Board::from_mine_layout(&layout);
+    app.set_state(GameState::Running);
+    app.play_move(Move::Reveal(0, 4));
+    app.play_move(Move::Flag(1, 1)); // A wrong flag the solver must take back
+
+    let mut steps = 0;
+    while app.auto_solve_step() {
+        steps += 1;
+        assert!(steps < 100, "The solver keeps making progress");
+        assert_ne!(app.state(), GameState::Lost);
+    }
+    assert!(app.check_win_at(3.0, app.cell_size()));
+    assert_eq!(app.board().flags_on_mines().len(), 3);
+    assert_eq!(app.board().flag_count(), 3);
+    assert!(!app.auto_solve_step(), "Nothing to do once the game is won");
+}
+
+// Checks neighbors_matching with predicates for flagged neighbors and for covered mines.
+#[test]
+fn test_neighbors_matching_predicates() {
+    let mut board = Board::from_mine_layout(&["*.*", "...", "*.."]);
+    board.apply_move(Move::Flag(0, 0));
+    board.apply_move(Move::Flag(0, 1));
+    board.apply_move(Move::Reveal(2, 2));
+
+    let flagged = board.neighbors_matching(1, 1, |_, state| state == CellState::Flagged);
+    assert_eq!(flagged, vec![(0, 0), (0, 1)]);
+    let covered_mines = board.neighbors_matching(1, 1, |cell, state| {
+        cell == Cell::Mine && state == CellState::Covered
+    });
+    assert_eq!(covered_mines, vec![(0, 2), (2, 0)]);
+    assert!(board.neighbors_matching(0, 0, |cell, _| cell == Cell::Number(5)).is_empty());
+}
+
+// Checks that the game-over mine reveal speeds up for long queues and that skipping it
+// empties the queue in a single frame.
+#[test]
+fn test_mine_reveal_speed_and_skip() {
+    // Counts the frames (at 60 FPS) until the reveal queue is empty
+    fn frames_to_reveal(app: &mut MinesweeperApp) -> usize {
+        let mut timer = 0.0;
+        let mut frames = 0;
+        while !app.mine_reveal_queue().is_empty() {
+            app.mine_reveal_step(&mut timer, 1.0 / 60.0);
+            frames += 1;
+            assert!(frames < 100_000);
+        }
+        frames
+    }
+    fn lost_game(size: BoardSize) -> MinesweeperApp {
+        let (width, height, mines) = size.params();
+        let mut app = MinesweeperApp::new(width, height, mines);
+        app.board_mut().place_mines_avoiding_seeded(0, 0, 7);
+        app.board_mut().calculate_numbers();
+        let &(row, col) = app.board().mine_positions().iter().min().unwrap();
+        app.set_state(GameState::GameOver);
+        app.build_reveal_queue(row, col, RevealOrder::RowMajor);
+        app
+    }
+
+    let mut small = lost_game(BoardSize::Small);
+    let mut large = lost_game(BoardSize::Large);
+    assert!(large.mine_reveal_delay() < small.mine_reveal_delay());
+    let small_frames = frames_to_reveal(&mut small);
+    let large_frames = frames_to_reveal(&mut large);
+    assert!(large_frames as f32 / 60.0 < 7.0, "Large boards finish in a few seconds");
+    assert!(large_frames < small_frames * 3, "98 mines take far less than 10x as long as 9");
+
+    let mut skipped = lost_game(BoardSize::Large);
+    skipped.set_skip_mine_reveal(true);
+    let mut timer = 0.0;
+    assert_eq!(skipped.mine_reveal_step(&mut timer, 0.0).len(), 98);
+    assert!(skipped.mine_reveal_queue().is_empty());
+}
+
+// Checks that the mine count can change before placement (within capacity) but not after.
+#[test]
+fn test_set_mine_count_before_placement_only() {
+    let mut board = Board::new(8, 8, 10);
+    assert_eq!(board.set_mine_count(20), Ok(()));
+    assert_eq!(board.mines(), 20);
+    assert!(board.set_mine_count(56).is_err(), "The first click's 3x3 area must stay free");
+    assert_eq!(board.set_mine_count(55), Ok(()));
+    assert_eq!(board.set_mine_count(20), Ok(()));
+
+    board.place_mines_avoiding(4, 4);
+    assert_eq!(board.mine_positions().len(), 20);
+    assert!(board.set_mine_count(5).is_err());
+    assert_eq!(board.mines(), 20);
+}
+
+// Checks that clicks map to cells in logical pixels and that text sharpening follows the DPI scale.
+#[test]
+fn test_dpi_scale_point_to_cell_and_font_params() {
+    let top_bar = 60.0;
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    assert_eq!(app.dpi_scale(), 1.0);
+    assert_eq!(app.sharp_font_params(20.0), (20, 1.0));
+    assert_eq!(app.point_to_cell(45.0, top_bar + 95.0, 30.0), Some((3, 1)));
+    assert_eq!(app.point_to_cell(45.0, top_bar - 1.0, 30.0), None);
+    assert_eq!(app.point_to_cell(9.0 * 30.0, top_bar, 30.0), None);
+
+    app.set_dpi_scale(2.0);
+    assert_eq!(app.sharp_font_params(20.0), (40, 0.5));
+    assert_eq!(app.point_to_cell(45.0, top_bar + 95.0, 30.0), Some((3, 1)));
+    app.reset_game();
+    assert_eq!(app.dpi_scale(), 2.0);
+    app.set_dpi_scale(0.5);
+    assert_eq!(app.dpi_scale(), 1.0);
+}
+
+// Checks that maybe marks count for chording but not as flags, and can't be uncovered.
+#[test]
+fn test_maybe_marks_counting() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..."]);
+    assert_eq!(board.apply_move(Move::Mark(0, 0)), MoveResult::default());
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Maybe));
+    assert_eq!(board.flag_count(), 0);
+    assert_eq!(board.maybe_count(), 1);
+    assert_eq!(board.remaining_mines(), 1, "Maybe marks don't use up the flags-left counter");
+    assert_eq!(board.export_visible()[0][0], '?');
+    let decoded = Board::from_compact(&board.to_compact()).unwrap();
+    assert_eq!(decoded.cell_state(0, 0), Some(CellState::Maybe));
+
+    // A maybe mark can't be uncovered, but it satisfies a number for chording
+    board.apply_move(Move::Reveal(0, 0));
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Maybe));
+    board.apply_move(Move::Reveal(1, 1));
+    let result = board.apply_move(Move::Chord(1, 1));
+    assert!(result.mine_hit.is_none());
+    assert_eq!(result.revealed.len(), 7);
+
+    // Flagging a maybe commits it; marking a flag turns it back into a maybe
+    board.apply_move(Move::Flag(0, 0));
+    assert_eq!((board.flag_count(), board.maybe_count()), (1, 0));
+    board.apply_move(Move::Mark(0, 0));
+    assert_eq!((board.flag_count(), board.maybe_count()), (0, 1));
+    board.apply_move(Move::Unmark(0, 0));
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Covered));
+}
+
+// Checks that a game whose only mine is marked as maybe is won once every safe cell is open.
+#[test]
+fn test_maybe_mark_does_not_block_win() {
+    let mut app = MinesweeperApp::new(3, 3, 1);
+    *app.board_mut() = Board::from_mine_layout(&["*..", "...", "..."]);
+    app.set_state(GameState::Running);
+    app.board_mut().apply_move(Move::Mark(0, 0));
+    for (row, col) in [(0, 1), (0, 2), (1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+        app.board_mut().apply_move(Move::Reveal(row, col));
+    }
+    assert!(app.check_win_at(1.0, 30.0));
+    assert_eq!(app.state(), GameState::Won);
+}
+
+// Checks that connected_empty_region predicts exactly what flood_fill_wave uncovers, read-only.
+#[test]
+fn test_connected_empty_region_matches_flood_fill() {
+    let mut board = Board::from_mine_layout(&[
+        "......", "......", "..*...", "......", "....*.", "......",
+    ]);
+    board.flag_cell(5, 0);
+    board.uncover_cell(0, 5);
+    for (row, col) in [(0, 0), (5, 5), (1, 2), (2, 2), (0, 5)] {
+        let before = board.clone();
+        let region = board.connected_empty_region(row, col);
+        assert_eq!(board, before, "The board must not change");
+
+        let mut flooded = board.clone();
+        let wave: Vec<(usize, usize)> = flooded
+            .flood_fill_wave(row, col)
+            .into_iter()
+            .map(|(r, c, _)| (r, c))
+            .collect();
+        assert_eq!(region, wave);
+    }
+    assert!(board.connected_empty_region(6, 0).is_empty());
+}
+
+// Checks that change_board_size switches dimensions and starts a fresh game, and that sizes cycle.
+#[test]
+fn test_change_board_size_resets_game() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.set_board_size(BoardSize::Small);
+    app.board_mut().place_mines_avoiding(4, 4);
+    app.board_mut().flag_cell(0, 0);
+    app.set_state(GameState::Running);
+
+    assert!(app.change_board_size(BoardSize::Small.next()));
+    assert_eq!(app.board_size(), BoardSize::Medium);
+    assert_eq!((app.board().width(), app.board().height(), app.board().mines()), (16, 16, 40));
+    assert_eq!(app.state(), GameState::NotStarted);
+    assert_eq!(app.board().flag_count(), 0);
+    assert!(app.board().mine_positions_is_empty());
+    assert_eq!(app.cell_size(), BoardSize::Medium.cell_size());
+
+    assert!(!app.change_board_size(BoardSize::Medium), "Same size is a no-op");
+    assert_eq!(BoardSize::Medium.next(), BoardSize::Large);
+    assert_eq!(BoardSize::Large.next(), BoardSize::Small);
+}
+
+// Checks that unrevealed_safe_cells lists exactly the safe cells a partial flood fill left covered.
+#[test]
+fn test_unrevealed_safe_cells_after_flood_fill() {
+    let mut board = Board::from_mine_layout(&["....", "....", "**..", "*..*"]);
+    assert_eq!(board.unrevealed_safe_cells().len(), 16 - 4);
+
+    board.flood_fill_wave(0, 0);
+    board.flag_cell(3, 1);
+    board.apply_move(Move::Mark(3, 2));
+    // The flood fill stops at the numbers above row 3, leaving its two safe cells
+    assert_eq!(board.unrevealed_safe_cells(), vec![(3, 1), (3, 2)]);
+
+    for (row, col) in board.unrevealed_safe_cells() {
+        board.uncover_cell(row, col);
+    }
+    assert!(board.unrevealed_safe_cells().is_empty());
+}
+
+// Checks that with retry on, "New Game" replays the same seeded layout, and reset_game doesn't.
+#[test]
+fn test_retry_same_board_reproduces_mines() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.set_board_size(BoardSize::Small);
+    app.prepare_reveal(2, 5);
+    let mines = app.board().mine_positions().clone();
+    let seed = app.seed();
+    assert!(seed.is_some());
+
+    app.set_retry_same_board(true);
+    app.new_game();
+    assert_eq!(app.board().mine_positions(), &mines);
+    assert_eq!(app.seed(), seed);
+    assert_eq!(app.state(), GameState::Running);
+    assert_eq!(app.board().cell_state(2, 5), Some(CellState::Uncovered), "Same opening");
+    assert!(app.retry_same_board(), "The setting survives the restart");
+
+    // Retrying again still gives the same board; a plain reset starts a new random one
+    app.new_game();
+    assert_eq!(app.board().mine_positions(), &mines);
+    app.reset_game();
+    assert!(app.board().mine_positions_is_empty());
+    assert_eq!(app.seed(), None);
+    app.new_game();
+    assert_eq!(app.state(), GameState::NotStarted, "Nothing to retry before a seeded game");
+}
+
+// Checks that the bulk cell and state accessors match the board's dimensions and contents.
+#[test]
+fn test_cells_and_states_accessors() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "...."]);
+    board.flag_cell(0, 0);
+    assert_eq!(board.cells().len(), board.height());
+    assert_eq!(board.states().len(), board.height());
+    assert!(board.cells().iter().all(|row| row.len() == board.width()));
+    assert!(board.states().iter().all(|row| row.len() == board.width()));
+    assert_eq!(board.cells()[0][0], Cell::Mine);
+    assert_eq!(board.cells()[1][1], Cell::Number(1));
+    assert_eq!(board.states()[0][0], CellState::Flagged);
+    assert_eq!(board.states()[2][3], CellState::Covered);
+}
+
+// Checks that screenshots are only taken of finished games and are named by difficulty and time.
+#[test]
+fn test_screenshot_request_and_file_name() {
+    // 2026-10-16 14:25:30 UTC
+    let name = screenshot_file_name(BoardSize::Medium, 1_792_160_730);
+    assert_eq!(name, "result-medium-20261016-142530.png");
+    assert_eq!(screenshot_file_name(BoardSize::Small, 0), "result-small-19700101-000000.png");
+
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert!(!app.request_screenshot(), "Nothing to export before the game ends");
+    app.set_state(GameState::Running);
+    assert!(!app.request_screenshot());
+    assert!(!app.screenshot_pending());
+    app.set_state(GameState::Won);
+    assert!(app.request_screenshot());
+    assert!(app.screenshot_pending());
+    app.set_screenshot_pending(false);
+    app.set_state(GameState::Lost);
+    assert!(app.request_screenshot());
+}
+
+// Checks that the grid line width defaults to 2px, can be turned off, and survives a new game.
+#[test]
+fn test_grid_line_width_setting() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.grid_line_width(), 2.0);
+    app.set_grid_line_width(-1.0);
+    assert_eq!(app.grid_line_width(), 0.0, "Negative widths mean no borders");
+    app.reset_game();
+    assert_eq!(app.grid_line_width(), 0.0);
+    app.set_grid_line_width(1.0);
+    assert_eq!(app.grid_line_width(), 1.0);
+}
+
+// Checks that the frontier is exactly the covered cells touching an uncovered number.
+#[test]
+fn test_frontier_cells_on_partial_board() {
+    let mut board = Board::from_mine_layout(&["....", "....", "**..", "*..*"]);
+    assert!(board.frontier_cells().is_empty(), "Nothing is uncovered yet");
+
+    // The flood fill opens rows 0-1 and stops at the numbers next to the mines
+    board.flood_fill_wave(0, 0);
+    board.flag_cell(2, 1);
+    let expected = vec![(2, 0), (3, 1), (3, 2), (3, 3)];
+    assert_eq!(board.frontier_cells(), expected);
+    assert!(board.is_frontier(3, 2));
+    assert!(!board.is_frontier(2, 1), "Flagged cells are not frontier cells");
+    assert!(!board.is_frontier(3, 0), "Covered, but no uncovered number next to it");
+    assert!(!board.is_frontier(0, 0), "Uncovered cells are never frontier cells");
+    assert!(!board.is_frontier(9, 9));
+}
+
+// Checks that the start countdown runs down, starts the clock on "go", and holds the solver.
+#[test]
+fn test_start_countdown() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.reset_game();
+    assert_eq!(app.countdown(), None, "Off by default");
+
+    app.set_countdown_enabled(true);
+    app.set_auto_open(true);
+    app.reset_game();
+    assert_eq!(app.countdown(), Some(3.0));
+    assert!(!app.auto_solve_step(), "No moves until the countdown ends");
+    assert!(!app.tick_countdown(1.0, 101.0));
+    assert_eq!(app.countdown(), Some(2.0));
+    assert_eq!(app.start_time(), 0.0, "The clock waits for the countdown");
+
+    assert!(app.tick_countdown(2.5, 103.0));
+    assert_eq!(app.countdown(), None);
+    assert_eq!(app.start_time(), 103.0);
+    assert!(!app.tick_countdown(1.0, 104.0), "Nothing left to count");
+    assert_eq!(app.start_time(), 103.0);
+
+    app.start_editor();
+    assert_eq!(app.countdown(), None, "The editor skips the countdown");
+}
+
+// Checks that place_mines_excluding never puts a mine on a protected cell.
+#[test]
+fn test_place_mines_excluding_protected_cells() {
+    // Protect the whole top row and the left column: only the 7x7 rest is eligible
+    let protected: HashSet<(usize, usize)> =
+        (0..8).map(|i| (0, i)).chain((0..8).map(|i| (i, 0))).collect();
+    for _ in 0..20 {
+        let mut board = Board::new(8, 8, 30);
+        board.place_mines_excluding(&protected);
+        assert_eq!(board.mine_positions().len(), 30);
+        assert!(board.mine_positions().is_disjoint(&protected));
+    }
+
+    // More mines than eligible cells: every unprotected cell gets one
+    let mut board = Board::new(8, 8, 60);
+    board.place_mines_excluding(&protected);
+    assert_eq!(board.mine_positions().len(), 49);
+}
+
+// Checks that the fifth win in a row queues the fanfare and a banner that dismisses itself.
+#[test]
+fn test_win_streak_milestone() {
+    let mut app = MinesweeperApp::new(3, 3, 1);
+    for win in 1..=5 {
+        *app.board_mut() = Board::from_mine_layout(&["*..", "...", "..."]);
+        for (row, col) in [(0, 1), (0, 2), (1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            app.board_mut().uncover_cell(row, col);
+        }
+        app.set_state(GameState::Running);
+        assert!(app.check_win_at(10.0 * win as f64, 30.0));
+        assert_eq!(app.win_streak(), win);
+        assert_eq!(app.fanfare_pending(), win == 5);
+    }
+    assert_eq!(app.streak_banner(), Some((5, 50.0)));
+    app.reset_game();
+    assert_eq!(app.win_streak(), 5, "The streak carries over to the next game");
+    assert_eq!(app.update_streak_banner(51.0), Some(5));
+    assert_eq!(app.update_streak_banner(53.0), None, "The banner dismisses itself");
+    assert_eq!(app.streak_banner(), None);
+
+    // Losing (here by running out of time) resets the streak
+    app.set_move_time_limit(Some(1.0));
+    app.set_state(GameState::Running);
+    app.check_move_time_limit(100.0);
+    assert!(app.check_move_time_limit(102.0));
+    assert_eq!(app.win_streak(), 0);
+}
+
+// Checks each toggle_flag outcome along the right-click cycle.
+#[test]
+fn test_toggle_flag_outcomes() {
+    let mut board = Board::from_mine_layout(&["*.", ".."]);
+    assert_eq!(board.toggle_flag(0, 0), FlagChange::Flagged);
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Flagged));
+    assert_eq!(board.toggle_flag(0, 0), FlagChange::Unflagged);
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Maybe));
+    assert_eq!(board.toggle_flag(0, 0), FlagChange::Unmarked);
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Covered));
+
+    board.uncover_cell(1, 1);
+    assert_eq!(board.toggle_flag(1, 1), FlagChange::NoChange);
+    assert_eq!(board.cell_state(1, 1), Some(CellState::Uncovered));
+    assert_eq!(board.toggle_flag(5, 5), FlagChange::NoChange);
+    assert_eq!(FlagChange::Flagged.as_move(0, 1), Some(Move::Flag(0, 1)));
+    assert_eq!(FlagChange::NoChange.as_move(0, 1), None);
+}
+
+// Checks that the loss flash fades out over its duration and can be turned off.
+#[test]
+fn test_loss_flash_fades_and_can_be_disabled() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    app.start_loss_flash();
+    let start = app.update_loss_flash(0.0).unwrap();
+    let later = app.update_loss_flash(0.15).unwrap();
+    assert!(later < start && later > 0.0);
+    assert_eq!(app.update_loss_flash(0.2), None);
+    assert_eq!(app.loss_flash(), None);
+
+    app.start_loss_flash();
+    app.reset_game();
+    assert_eq!(app.loss_flash(), None);
+
+    app.set_loss_flash_enabled(false);
+    app.start_loss_flash();
+    assert_eq!(app.loss_flash(), None);
+}
+
+// Checks that double-click chording only chords on a second click on the same cell
+// within the window, and that single clicks chord when it's off.
+#[test]
+fn test_register_chord_click_double_click_window() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    assert!(app.register_chord_click(2, 2, 1.0), "Single-click chording by default");
+    assert_eq!(app.last_click(), None);
+
+    app.set_double_click_chord(true);
+    assert!((app.double_click_window() - 0.3).abs() < 1e-9);
+    assert!(!app.register_chord_click(2, 2, 1.0), "First click only arms the double-click");
+    assert!(app.register_chord_click(2, 2, 1.2), "Second click within the window chords");
+    assert_eq!(app.last_click(), None);
+
+    assert!(!app.register_chord_click(2, 2, 2.0));
+    assert!(!app.register_chord_click(2, 2, 2.5), "Too slow");
+    assert!(!app.register_chord_click(3, 3, 2.6), "Different cell");
+    assert!(app.register_chord_click(3, 3, 2.7));
+
+    app.set_double_click_window(1.0);
+    app.reset_game();
+    assert!(app.double_click_chord());
+    assert!((app.double_click_window() - 1.0).abs() < 1e-9);
+}
+
+// Checks the mine density boundaries of the custom board difficulty label.
+#[test]
+fn test_difficulty_label_thresholds() {
+    let label = |mines| Board::new(10, 10, mines).difficulty_label();
+    assert_eq!(label(0), "Easy");
+    assert_eq!(label(12), "Easy");
+    assert_eq!(label(13), "Medium");
+    assert_eq!(label(16), "Medium");
+    assert_eq!(label(17), "Hard");
+    assert_eq!(label(20), "Hard");
+    assert_eq!(label(21), "Insane");
+    assert_eq!(label(90), "Insane");
+    assert_eq!(Board::new(16, 16, 40).difficulty_label(), "Medium");
+    assert_eq!(Board::new(24, 24, 99).difficulty_label(), "Hard");
+    assert_eq!(Board::new(0, 0, 0).difficulty_label(), "Easy", "Empty board");
+}
+
+// Checks that "New Game" asks for confirmation only while a game with uncovered cells is
+// running, and that the confirmation can be turned off.
+#[test]
+fn test_request_new_game_confirmation() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    assert!(app.request_new_game(NewGameRequest::Next), "Not started: no confirmation");
+    assert!(!app.new_game_confirm_pending());
+
+    app.prepare_reveal(4, 4);
+    app.board_mut().flood_fill_wave(4, 4);
+    assert_eq!(app.state(), GameState::Running);
+    assert!(!app.request_new_game(NewGameRequest::Next), "Game in progress: ask first");
+    assert!(app.new_game_confirm_pending());
+    assert_eq!(app.state(), GameState::Running, "Nothing reset yet");
+
+    app.set_pending_new_game(None);
+    app.set_confirm_new_game(false);
+    assert!(app.request_new_game(NewGameRequest::Next), "Confirmation turned off");
+    assert_eq!(app.state(), GameState::NotStarted);
+    assert!(!app.confirm_new_game(), "Setting survives the reset");
+}
+
+// Checks the screen-reader text summary of the game and of the cell under the cursor.
+#[test]
+fn test_describe_state_for_screen_readers() {
+    let mut app = MinesweeperApp::new(16, 16, 40);
+    assert_eq!(
+        app.describe_state(None),
+        "Medium board, 16 by 16. Not started. 40 mines left. Time 00:00."
+    );
+
+    *app.board_mut() = Board::from_mine_layout(&["*..", "...", "..."]);
+    app.set_state(GameState::Running);
+    app.board_mut().flag_cell(0, 0);
+    app.board_mut().uncover_cell(1, 1);
+    let text = app.describe_state(Some((0, 0)));
+    assert!(text.contains("Game in progress. 0 mines left."), "{}", text);
+    assert!(text.ends_with("Row 1, column 1: flagged."), "{}", text);
+    assert_eq!(app.describe_cell(1, 1).as_deref(), Some("1"));
+    assert_eq!(app.describe_cell(2, 2).as_deref(), Some("covered"));
+    assert_eq!(app.describe_cell(5, 5), None);
+    assert!(!app.describe_state(Some((5, 5))).contains("Row"), "Cursor off the board");
+}
+
+// Checks that clear_timers empties the animation timer grids in place when the size is
+// unchanged, and resizes them when the board size changes.
+#[test]
+fn test_clear_timers_in_place_and_on_resize() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.pop_timers_mut()[1][2] = Some(0.3);
+    app.wave_timers_mut()[7][7] = Some(0.1);
+    app.flag_timers_mut()[0][0] = Some(0.0);
+    let pop_ptr = app.pop_timers().as_ptr();
+    let row_ptr = app.pop_timers()[1].as_ptr();
+
+    app.clear_timers();
+    assert_eq!(app.pop_timers().as_ptr(), pop_ptr, "Cleared in place");
+    assert_eq!(app.pop_timers()[1].as_ptr(), row_ptr);
+    for grid in [app.pop_timers(), app.wave_timers(), app.flag_timers()] {
+        assert!(grid.iter().flatten().all(|timer| timer.is_none()));
+    }
+
+    app.wave_timers_mut()[3][3] = Some(0.2);
+    app.reset_game();
+    assert!(app.wave_timers().iter().flatten().all(|timer| timer.is_none()));
+
+    *app.board_mut() = Board::new(5, 3, 2);
+    app.clear_timers();
+    assert_eq!(app.flag_timers().len(), 3);
+    assert!(app.flag_timers().iter().all(|row| row.len() == 5));
+}
+
+// Checks that the emergency safe reveal opens the safe cell nearest the last click, never a
+// mine, and that a win using it counts as a win with help.
+#[test]
+fn test_reveal_one_safe_nearest_last_click() {
+    let mut app = MinesweeperApp::new(4, 3, 0);
+    *app.board_mut() = Board::from_mine_layout(&["*..*", "*.**", "****"]);
+    assert_eq!(app.reveal_one_safe(), None, "Game not running");
+    app.set_state(GameState::Running);
+    assert_eq!(app.board().nearest_safe_covered(2, 3), Some((0, 2)));
+
+    app.set_last_reveal_cell(Some((1, 0)));
+    assert_eq!(app.reveal_one_safe(), Some((1, 1)));
+    assert_eq!(app.board().cell_state(1, 1), Some(CellState::Uncovered));
+    assert_eq!(app.pop_timers()[1][1], Some(0.0));
+
+    app.set_last_reveal_cell(Some((0, 3)));
+    assert_eq!(app.reveal_one_safe(), Some((0, 2)));
+    assert_eq!(app.reveal_one_safe(), Some((0, 1)));
+    assert_eq!(app.reveal_one_safe(), None, "No safe cells left");
+    assert_eq!(app.safe_reveals_used(), 3);
+
+    assert!(app.check_win_at(1.0, 40.0));
+    assert_eq!(app.wins_with_help(), 1);
+    app.reset_game();
+    assert_eq!(app.safe_reveals_used(), 0);
+    assert_eq!(app.wins_with_help(), 1, "Session count survives the reset");
+}
+
+// Checks in_bounds and the accessors built on it at corners, edges, and out-of-range cells.
+#[test]
+fn test_in_bounds_corners_edges_and_out_of_range() {
+    let mut board = Board::new(4, 3, 0);
+    for &(row, col) in &[(0, 0), (0, 3), (2, 0), (2, 3), (1, 2)] {
+        assert!(board.in_bounds(row, col), "({}, {})", row, col);
+    }
+    for &(row, col) in &[(3, 0), (0, 4), (3, 4), (usize::MAX, 0), (0, usize::MAX)] {
+        assert!(!board.in_bounds(row, col), "({}, {})", row, col);
+        assert_eq!(board.cell(row, col), None);
+        assert_eq!(board.cell_state(row, col), None);
+    }
+
+    assert_eq!(board.neighbors(0, 0).count(), 3, "Corner");
+    assert_eq!(board.neighbors(0, 1).count(), 5, "Edge");
+    assert_eq!(board.neighbors(1, 1).count(), 8, "Interior");
+    assert_eq!(board.neighbors(2, 3).count(), 3, "Opposite corner");
+
+    // Setters ignore out-of-range cells instead of panicking
+    board.flag_cell(3, 0);
+    board.uncover_cell(0, 4);
+    board.set_cell(usize::MAX, 0, Cell::Mine);
+    assert_eq!(board.flag_count(), 0);
+    assert!(board.cells().iter().flatten().all(|&cell| cell == Cell::Empty));
+}
+
+// Checks that the top-bar counter shows flags left, covered cells, or both, and follows
+// reveals and flags.
+#[test]
+fn test_counter_modes_follow_reveals_and_flags() {
+    let mut app = MinesweeperApp::new(3, 3, 1);
+    *app.board_mut() = Board::from_mine_layout(&["*..", "...", "..."]);
+    assert_eq!(app.counter_mode(), CounterMode::Flags);
+    assert_eq!(app.counter_text(), "1");
+
+    app.set_counter_mode(CounterMode::Cells);
+    assert_eq!(app.counter_text(), "9");
+    app.board_mut().uncover_cell(2, 2);
+    app.board_mut().flag_cell(0, 0);
+    assert_eq!(app.counter_text(), "8", "Flagged cells are still covered");
+    assert_eq!(app.board().count_by_state(CellState::Uncovered), 1);
+
+    app.set_counter_mode(CounterMode::Cells.next());
+    assert_eq!(app.counter_text(), "0|8");
+    app.board_mut().flag_cell(0, 1);
+    assert_eq!(app.counter_text(), "-1|8");
+    assert_eq!(CounterMode::Both.next(), CounterMode::Flags);
+
+    app.reset_game();
+    assert_eq!(app.counter_mode(), CounterMode::Both, "Preference survives resets");
+}
+
+// Checks that the top-bar layout keeps the sound icon inside a crowded Small bar by
+// compressing the spacing, and keeps the usual spacing when there is room.
+#[test]
+fn test_top_bar_layout_fits_small_board() {
+    let app = MinesweeperApp::new(8, 8, 10);
+    let bar_width = 8.0 * app.cell_size();
+    for &(counter_width, timer_width) in &[(20.0, 50.0), (50.0, 70.0), (60.0, 80.0)] {
+        let layout = app.top_bar_layout(counter_width, timer_width);
+        assert!(
+            layout.sound_x + 32.0 <= bar_width,
+            "Sound icon at {} overflows the {}px bar",
+            layout.sound_x,
+            bar_width
+        );
+        assert!(layout.spacing <= app.top_bar_spacing());
+        assert!(layout.flags_x < layout.timer_x && layout.timer_x < layout.size_button_x);
+        assert!(layout.size_button_x < layout.new_game_x && layout.new_game_x < layout.sound_x);
+    }
+
+    let roomy = MinesweeperApp::new(24, 24, 99);
+    let layout = roomy.top_bar_layout(20.0, 50.0);
+    assert_eq!(layout.spacing, roomy.top_bar_spacing());
+    assert_eq!(layout.flags_x, roomy.top_bar_start_x());
+}
+
+// Checks that the opening assist opens the size's preferred cell (center by default) and keeps it across games
+#[test]
+fn test_preferred_opening_per_size() {
+    let mut app = MinesweeperApp::new(16, 16, 40);
+    assert!(!app.opening_assist());
+    assert_eq!(app.preferred_opening(), (8, 8));
+
+    app.set_opening_assist(true);
+    app.set_preferred_opening(BoardSize::Medium, Some((0, 0)));
+    app.set_preferred_opening(BoardSize::Small, Some((2, 5)));
+    assert_eq!(app.preferred_opening(), (0, 0));
+
+    // Off-board cells fall back to the center
+    app.set_preferred_opening(BoardSize::Medium, Some((40, 3)));
+    assert_eq!(app.preferred_opening(), (8, 8));
+
+    // The settings survive a new game; clearing a size goes back to the center
+    app.set_preferred_opening(BoardSize::Medium, Some((3, 4)));
+    app.reset_game();
+    assert!(app.opening_assist());
+    assert_eq!(app.preferred_opening(), (3, 4));
+    assert_eq!(app.preferred_openings().get(&BoardSize::Small), Some(&(2, 5)));
+    app.set_preferred_opening(BoardSize::Medium, None);
+    assert_eq!(app.preferred_opening(), (8, 8));
+}
+
+// Checks that total_cells and safe_cell_count match the three standard sizes
+#[test]
+fn test_total_and_safe_cell_counts() {
+    for (width, height, mines) in [(8, 8, 10), (16, 16, 40), (24, 24, 99)] {
+        let board = Board::new(width, height, mines);
+        assert_eq!(board.total_cells(), width * height);
+        assert_eq!(board.safe_cell_count(), width * height - mines);
+    }
+    let mut board = Board::new(8, 8, 10);
+    board.set_mine_count(0).unwrap();
+    assert_eq!(board.safe_cell_count(), 64);
+}
+
+// Checks that the flag limit refuses the flag after the last mine's worth and frees up when a flag is removed
+#[test]
+fn test_flag_limit_refuses_extra_flag() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.set_flag_limit(true);
+    for col in 0..8 {
+        assert_eq!(app.play_flag_toggle(0, col), Some(FlagChange::Flagged));
+    }
+    for col in 0..2 {
+        assert_eq!(app.play_flag_toggle(1, col), Some(FlagChange::Flagged));
+    }
+    assert_eq!(app.board().flag_count(), 10);
+
+    // The 11th flag is refused and the cell stays covered
+    assert_eq!(app.play_flag_toggle(1, 2), None);
+    assert_eq!(app.board().cell_state(1, 2), Some(CellState::Covered));
+
+    // Turning a flag into a maybe mark frees a flag; without the limit flags are unrestricted
+    assert_eq!(app.play_flag_toggle(0, 0), Some(FlagChange::Unflagged));
+    assert_eq!(app.play_flag_toggle(1, 2), Some(FlagChange::Flagged));
+    app.set_flag_limit(false);
+    assert_eq!(app.play_flag_toggle(1, 3), Some(FlagChange::Flagged));
+    assert_eq!(app.board().flag_count(), 11);
+}
+
+// Checks that the wave orders give the same revealed cells different reveal delays
+#[test]
+fn test_wave_orders_assign_different_delays() {
+    // A 3x3 block opened from its center, plus one cell further right
+    let mut revealed = vec![(4, 4, 0)];
+    for (r, c) in [(3, 3), (3, 4), (3, 5), (4, 3), (4, 5), (5, 3), (5, 4), (5, 5)] {
+        revealed.push((r, c, 1));
+    }
+    revealed.push((4, 6, 2));
+
+    let delays = |order: WaveOrder| {
+        let mut app = MinesweeperApp::new(8, 8, 10);
+        app.assign_wave_delays(&revealed, order);
+        revealed
+            .iter()
+            .map(|&(r, c, _)| app.wave_timers()[r][c].unwrap())
+            .collect::<Vec<f32>>()
+    };
+    let radial = delays(WaveOrder::Radial);
+    let spiral = delays(WaveOrder::Spiral);
+    let left_to_right = delays(WaveOrder::LeftToRight);
+
+    // Radial: every cell of a ring starts together, and the click goes first
+    assert_eq!(radial[0], 0.0);
+    assert!(radial[1..9].iter().all(|&d| d == radial[1]));
+    assert!(radial[9] > radial[1]);
+    // Spiral: the click still goes first, but the first ring is staggered around it
+    assert_eq!(spiral[0], 0.0);
+    assert!(spiral[1..9].iter().any(|&d| d != spiral[1]));
+    // Left to right: whole columns start together, from the leftmost revealed column
+    assert_eq!(left_to_right[1], 0.0); // (3, 3)
+    assert_eq!(left_to_right[1], left_to_right[4]); // (4, 3)
+    assert!(left_to_right[0] > left_to_right[1]);
+    assert!(left_to_right[9] > left_to_right[3]); // (4, 6) after (3, 5)
+    assert_ne!(radial, spiral);
+    assert_ne!(radial, left_to_right);
+}
+
+// Checks that tally_neighbors counts mines and each cell state around a mixed neighborhood
+#[test]
+fn test_tally_neighbors_mixed_states() {
+    let mut board = Board::from_mine_layout(&["**...", ".....", "..*..", ".....", "....."]);
+    board.apply_move(Move::Flag(0, 0));
+    board.apply_move(Move::Mark(0, 1));
+    board.apply_move(Move::Flag(1, 0)); // A wrong flag
+    board.apply_move(Move::Reveal(2, 1));
+    board.apply_move(Move::Reveal(1, 2));
+
+    let tally = board.tally_neighbors(1, 1);
+    assert_eq!(tally.mines, 3);
+    assert_eq!(tally.flagged, 2);
+    assert_eq!(tally.maybe, 1);
+    assert_eq!(tally.uncovered, 2);
+    assert_eq!(tally.covered, 3);
+    assert_eq!(board.adjacent_marked_count(1, 1), 3);
+
+    // A corner only has three neighbors
+    let corner = board.tally_neighbors(4, 4);
+    assert_eq!(corner.covered + corner.flagged + corner.maybe + corner.uncovered, 3);
+    assert_eq!(corner.mines, 0);
+}
+
+// Checks that zen mode flags a clicked or chorded mine instead of losing, and only in zen mode
+#[test]
+fn test_zen_mode_absorbs_mines() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    *app.board_mut() = Board::from_mine_layout(&["*..", "...", "..*"]);
+    app.set_state(GameState::Running);
+    assert!(!app.absorb_mine(0, 0));
+
+    app.set_zen_mode(true);
+    assert!(app.absorb_mine(0, 0));
+    assert_eq!(app.state(), GameState::Running);
+    assert_eq!(app.board().cell_state(0, 0), Some(CellState::Flagged));
+    assert!(!app.absorb_mine(1, 1)); // Not a mine
+
+    // A chord through a wrong flag uncovers the mine; zen mode undoes it and flags it
+    app.board_mut().apply_move(Move::Reveal(1, 1));
+    app.board_mut().apply_move(Move::Flag(1, 2));
+    let result = app.board_mut().apply_move(Move::Chord(1, 1));
+    assert_eq!(result.mine_hit, Some((2, 2)));
+    assert!(app.absorb_mine(2, 2));
+    assert_eq!(app.board().cell_state(2, 2), Some(CellState::Flagged));
+    assert_eq!(app.state(), GameState::Running);
+}
+
+// Checks that the checksum changes with a single cell and that saved boards verify on load
+#[test]
+fn test_board_checksum_detects_tampering() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "..*.", "...."]);
+    board.apply_move(Move::Reveal(3, 0));
+    board.apply_move(Move::Flag(0, 0));
+    let original = board.checksum();
+    assert_eq!(board.clone().checksum(), original);
+
+    let mut edited = board.clone();
+    edited.set_cell_state(0, 0, CellState::Covered);
+    assert_ne!(edited.checksum(), original);
+
+    // An untouched save round-trips and keeps its checksum
+    let mut saved = Vec::new();
+    board.save_to_writer(&mut saved).unwrap();
+    let loaded = Board::load_from_reader(saved.as_slice()).expect("save should verify");
+    assert_eq!(loaded.checksum(), original);
+
+    // Editing the board in the file (a flagged mine turned into a flagged safe cell) is rejected
+    let text = String::from_utf8(saved).unwrap();
+    let tampered = text.replacen('F', "f", 1);
+    assert!(Board::load_from_reader(tampered.as_bytes()).is_err());
+    let unsigned = text.lines().next().unwrap().to_string();
+    assert!(Board::load_from_reader(unsigned.as_bytes()).is_err());
+}
+
+// Checks that a 4-neighbor board saves and loads with a matching checksum (its numbers differ
+// from the same mines on an 8-neighbor board).
+#[test]
+fn test_von_neumann_board_save_round_trip() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "..*.", "...."]);
+    board.set_topology(Topology::VonNeumann);
+    board.apply_move(Move::Reveal(3, 0));
+    board.apply_move(Move::Flag(0, 0));
+
+    let mut saved = Vec::new();
+    board.save_to_writer(&mut saved).unwrap();
+    let loaded = Board::load_from_reader(saved.as_slice()).expect("save should verify");
+    assert_eq!(loaded, board);
+    assert_eq!(loaded.checksum(), board.checksum());
+}
+
+// Checks that compute_cell_size fits each board into several window sizes, within the size limits
+#[test]
+fn test_compute_cell_size_fits_window() {
+    let windows = [(400.0, 500.0), (800.0, 600.0), (1280.0, 720.0), (1920.0, 1080.0)];
+    for (width, height, mines) in [(8, 8, 10), (16, 16, 40), (24, 24, 99)] {
+        let app = MinesweeperApp::new(width, height, mines);
+        for (window_w, window_h) in windows {
+            let cell_size = app.compute_cell_size((window_w, window_h));
+            assert!((16.0..=64.0).contains(&cell_size));
+            assert_eq!(cell_size, cell_size.floor(), "Cells are whole pixels");
+            if cell_size > 16.0 {
+                assert!(width as f32 * cell_size <= window_w);
+                assert!(height as f32 * cell_size + 60.0 <= window_h);
+            }
+        }
+    }
+    // A Large board fills a 4K window up to the size cap, well past its 28px preset
+    let large = MinesweeperApp::new(24, 24, 99);
+    assert_eq!(large.compute_cell_size((3840.0, 2160.0)), 64.0);
+    assert_eq!(large.compute_cell_size((1920.0, 1080.0)), 42.0);
+    // Windows too small for the board still get readable cells
+    assert_eq!(large.compute_cell_size((200.0, 200.0)), 16.0);
+}
+
+// Checks that adding then removing a mine restores the numbers and keeps the mine list in sync
+#[test]
+fn test_add_and_remove_mine_restore_numbers() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "..*.", "...."]);
+    let before = board.cells().to_vec();
+
+    assert!(board.add_mine(1, 1));
+    assert!(!board.add_mine(1, 1), "Already a mine");
+    assert_eq!(board.mines(), 3);
+    assert!(board.mine_positions().contains(&(1, 1)));
+    assert_eq!(board.cell(0, 1), Some(Cell::Number(2)));
+    assert_eq!(board.cell(1, 2), Some(Cell::Number(2)));
+
+    assert!(board.remove_mine(1, 1));
+    assert!(!board.remove_mine(1, 1), "No mine left to remove");
+    assert_eq!(board.mines(), 2);
+    assert!(!board.mine_positions().contains(&(1, 1)));
+    assert_eq!(board.cells(), before.as_slice());
+}
+
+// Checks that editor layouts round-trip through the .mine format and bad files are rejected
+#[test]
+fn test_mine_file_round_trip() {
+    let board = Board::from_mine_layout(&["*...", "....", "..*.", "...*"]);
+    let text = board.to_mine_file();
+    assert_eq!(text, "4x4\n0,0\n2,2\n3,3\n");
+    let loaded = Board::from_mine_file(&text).expect("layout should parse");
+    assert_eq!(loaded.mine_positions(), board.mine_positions());
+    assert_eq!(loaded.cells(), board.cells());
+
+    assert!(Board::from_mine_file("").is_err(), "Missing size line");
+    assert!(Board::from_mine_file("4x4\n1;2").is_err(), "Malformed mine line");
+    assert!(Board::from_mine_file("4x4\n4,0").is_err(), "Mine off the board");
+    assert!(Board::from_mine_file("4x4\n1,1\n1,1").is_err(), "Duplicate mine");
+    assert!(Board::from_mine_file("0x4").is_err(), "Empty board");
+    assert!(Board::from_mine_file("51x5").is_err(), "Wider than MAX_SIDE");
+    let huge = format!("{}x{}", usize::MAX, usize::MAX);
+    assert!(Board::from_mine_file(&huge).is_err(), "Would not fit in memory");
+}
+
+// Checks that the 4-neighbor topology gives cells only orthogonal neighbors and counts mines that way
+#[test]
+fn test_von_neumann_topology_neighbors_and_numbers() {
+    let mut board = Board::from_mine_layout(&["*.*", "...", "*.*"]);
+    assert_eq!(board.topology(), Topology::Square);
+    assert_eq!(board.neighbors(1, 1).count(), 8);
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(4)));
+
+    board.set_topology(Topology::VonNeumann);
+    let mut neighbors: Vec<(usize, usize)> = board.neighbors(1, 1).collect();
+    neighbors.sort();
+    assert_eq!(neighbors, vec![(0, 1), (1, 0), (1, 2), (2, 1)]);
+    assert_eq!(board.neighbor_coords(1, 1).1, 4);
+    assert_eq!(board.neighbor_coords(0, 0).1, 2);
+    // The center only touches the corner mines diagonally, so it is empty
+    assert_eq!(board.cell(1, 1), Some(Cell::Empty));
+    assert_eq!(board.cell(0, 1), Some(Cell::Number(2)));
+    assert_eq!(board.cell(1, 0), Some(Cell::Number(2)));
+
+    // The flood fill from the empty center spreads only orthogonally
+    let result = board.apply_move(Move::Reveal(1, 1));
+    assert_eq!(result.revealed.len(), 5);
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Covered));
+}
+
+// Checks that the safe-reveal budget forgives mine hits until it runs out, and resets each game
+#[test]
+fn test_safe_budget_absorbs_mine_hits() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.set_safe_budget_size(2);
+    app.reset_game();
+    assert_eq!(app.safe_budget(), 2);
+    *app.board_mut() = Board::from_mine_layout(&["*.*", "...", "*.*"]);
+    app.set_state(GameState::Running);
+
+    assert!(app.absorb_mine(0, 0));
+    assert_eq!(app.safe_budget(), 1);
+    assert!(!app.absorb_mine(1, 1), "Not a mine, no life used");
+    assert_eq!(app.safe_budget(), 1);
+    assert!(app.absorb_mine(0, 2));
+    assert_eq!(app.safe_budget(), 0);
+    assert_eq!(app.board().cell_state(0, 2), Some(CellState::Flagged));
+
+    // With no lives left the next hit isn't forgiven (the click then loses the game)
+    assert!(!app.can_absorb_mines(1));
+    assert!(!app.absorb_mine(2, 0));
+    assert_eq!(app.board().cell_state(2, 0), Some(CellState::Covered));
+
+    // Zen mode forgives hits without using lives
+    app.set_zen_mode(true);
+    assert!(app.absorb_mine(2, 0));
+    assert_eq!(app.safe_budget(), 0);
+
+    app.reset_game();
+    assert_eq!(app.safe_budget(), 2);
+}
+
+// Checks that feature_grid encodes covered, marked, and uncovered cells with the documented values
+#[test]
+fn test_feature_grid_encoding() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..*"]);
+    board.apply_move(Move::Flag(0, 0));
+    board.apply_move(Move::Mark(2, 2));
+    board.apply_move(Move::Reveal(1, 1));
+    board.apply_move(Move::Reveal(2, 0));
+
+    let grid = board.feature_grid();
+    assert_eq!(grid.len(), 3);
+    assert!(grid.iter().all(|row| row.len() == 3));
+    assert_eq!(grid[0][0], -0.5); // Flagged
+    assert_eq!(grid[2][2], -0.75); // Maybe
+    assert_eq!(grid[0][1], -1.0); // Covered
+    assert_eq!(grid[1][1], 2.0 / 8.0); // Uncovered 2
+    assert_eq!(grid[2][0], 0.0); // Uncovered empty
+
+    // An exploded mine
+    board.apply_move(Move::Unflag(0, 0));
+    board.apply_move(Move::Unmark(0, 0));
+    board.apply_move(Move::Reveal(0, 0));
+    assert_eq!(board.feature_grid()[0][0], -0.25);
+}
+
+// Checks that Board::try_new rejects sides out of range and mine counts that don't fit
+#[test]
+fn test_board_try_new_validation() {
+    assert!(Board::try_new(10, 12, 20).is_ok());
+    assert!(Board::try_new(Board::MIN_SIDE - 1, 10, 5).is_err());
+    assert!(Board::try_new(10, Board::MAX_SIDE + 1, 5).is_err());
+    assert!(Board::try_new(10, 10, 92).is_err()); // At most 100 cells minus the 3x3 opening
+    let board = Board::try_new(10, 12, 20).unwrap();
+    assert_eq!((board.width(), board.height(), board.mines()), (10, 12, 20));
+}
+
+// Checks that the custom board dialog keeps invalid input open with an error
+// and switches to a Custom size on a valid confirm
+#[test]
+fn test_custom_board_dialog() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.open_custom_dialog();
+    assert_eq!(app.custom_dialog().unwrap().fields, ["8", "8", "10"].map(String::from));
+
+    // Clear the width and confirm: the dialog stays open with an error
+    app.custom_dialog_backspace();
+    assert!(!app.confirm_custom_dialog());
+    assert!(app.custom_dialog().unwrap().error.is_some());
+
+    // Too many mines is refused too
+    app.custom_dialog_type('x'); // Non-digits are ignored
+    app.custom_dialog_type('9');
+    app.custom_dialog_next_field();
+    app.custom_dialog_next_field();
+    app.custom_dialog_type('0');
+    assert_eq!(app.custom_dialog().unwrap().fields[2], "100");
+    assert!(!app.confirm_custom_dialog());
+    assert!(app.custom_dialog().is_some());
+
+    // A valid entry closes the dialog and starts a custom board
+    app.custom_dialog_backspace();
+    assert!(app.confirm_custom_dialog());
+    assert!(app.custom_dialog().is_none());
+    let size = BoardSize::Custom {
+        width: 9,
+        height: 8,
+        mines: 10,
+    };
+    assert_eq!(app.board_size(), size);
+    assert_eq!((app.board().width(), app.board().height(), app.board().mines()), (9, 8, 10));
+
+    // New games keep the custom size
+    app.reset_game();
+    assert_eq!(app.board_size(), size);
+    assert_eq!(app.board().width(), 9);
+}
+
+// Checks that a flood fill stops at a flagged empty cell, leaving it flagged, and that a fill
+// started on a flagged cell uncovers nothing
+#[test]
+fn test_flood_fill_stops_at_flagged_cell() {
+    // A wall of mines down column 5 bounds an open region of empty cells and numbers
+    let mut board = Board::from_mine_layout(&[
+        ".....*.",
+        ".....*.",
+        ".....*.",
+        ".....*.",
+        ".....*.",
+    ]);
+    assert_eq!(board.cell(2, 2), Some(Cell::Empty));
+    board.toggle_flag(2, 2);
+
+    let revealed = board.flood_fill_wave(0, 0);
+    assert!(!revealed.iter().any(|&(r, c, _)| (r, c) == (2, 2)));
+    assert_eq!(board.cell_state(2, 2), Some(CellState::Flagged));
+    // The fill still went around the flag to the rest of the region
+    assert_eq!(board.cell_state(4, 0), Some(CellState::Uncovered));
+    assert_eq!(board.cell_state(2, 4), Some(CellState::Uncovered));
+
+    let mut board = Board::from_mine_layout(&["....", "....", "...*"]);
+    board.toggle_flag(0, 0);
+    assert!(board.flood_fill_wave(0, 0).is_empty());
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Flagged));
+    assert_eq!(board.cell_state(0, 1), Some(CellState::Covered));
+}
+
+// Checks that sounds played in a burst are ducked by the number of recent plays
+// and that the volume recovers once the burst is over
+#[test]
+fn test_sound_ducking_volume() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert_eq!(app.ducked_volume(0.6, 10.0), 0.6);
+    assert_eq!(app.ducked_volume(0.6, 10.02), 0.3);
+    assert_eq!(app.ducked_volume(0.6, 10.04), 0.2);
+    assert_eq!(app.ducked_volume(0.6, 10.06), 0.15);
+    // 10.0 and 10.02 are now more than 0.1s old, leaving two recent plays
+    assert_eq!(app.ducked_volume(0.6, 10.13), 0.2);
+    // Long after the burst the full volume is back
+    assert_eq!(app.ducked_volume(0.6, 11.0), 0.6);
+}
+
+// Checks that a board is symmetric-equivalent to its rotations and mirror images
+// but not to a board with a genuinely different layout
+#[test]
+fn test_board_symmetric_equivalence() {
+    let board = Board::from_mine_layout(&["*...", "..*.", "...."]);
+    let rotated = board.rotated();
+    assert_eq!((rotated.width(), rotated.height()), (3, 4));
+    assert_eq!(rotated.cell(0, 2), Some(Cell::Mine));
+    assert_eq!(rotated.cell(2, 1), Some(Cell::Mine));
+
+    let mut turned = board.clone();
+    for _ in 0..4 {
+        assert!(board.is_symmetric_equivalent(&turned));
+        assert!(board.is_symmetric_equivalent(&turned.mirrored()));
+        turned = turned.rotated();
+    }
+    assert_eq!(turned, board); // Four quarter turns come back around
+
+    let different = Board::from_mine_layout(&["*...", ".*..", "...."]);
+    assert!(!board.is_symmetric_equivalent(&different));
+    let other_size = Board::from_mine_layout(&["*....", "..*..", "....."]);
+    assert!(!board.is_symmetric_equivalent(&other_size));
+}
+
+// Checks that the flags-left counter counts toward a new flag count over about 0.2s
+// and settles exactly on it
+#[test]
+fn test_flags_counter_counts_up() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    app.advance_displayed_flags(0.016);
+    assert_eq!(app.displayed_flags(), 10.0);
+
+    for col in 0..4 {
+        app.board_mut().toggle_flag(0, col);
+    }
+    assert_eq!(app.counter_text(), "6");
+    app.advance_displayed_flags(0.1);
+    assert!(app.displayed_flags() > 6.0 && app.displayed_flags() < 10.0);
+    assert_eq!(app.displayed_counter_text(), "8");
+    app.advance_displayed_flags(0.1);
+    assert_eq!(app.displayed_flags(), 6.0);
+    app.advance_displayed_flags(0.1);
+    assert_eq!(app.displayed_flags(), 6.0);
+
+    // Removing flags counts back up
+    app.board_mut().toggle_flag(0, 0);
+    for _ in 0..20 {
+        app.advance_displayed_flags(0.016);
+    }
+    assert_eq!(app.displayed_flags(), 7.0);
+    assert_eq!(app.displayed_counter_text(), "7");
+}
+
+// Checks that mine_clusters groups touching mines (diagonals included) into separate clusters
+#[test]
+fn test_mine_clusters() {
+    let board = Board::from_mine_layout(&[
+        "**.....",
+        ".*.....",
+        "..*...*",
+        "......*",
+        ".......",
+    ]);
+    let clusters = board.mine_clusters();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0], vec![(0, 0), (0, 1), (1, 1), (2, 2)]);
+    assert_eq!(clusters[1], vec![(2, 6), (3, 6)]);
+    assert_eq!(clusters.iter().map(Vec::len).max(), Some(4));
+
+    assert!(Board::from_mine_layout(&["...", "..."]).mine_clusters().is_empty());
+}
+
+// Checks that numbers show only at the end of the pop animation by default
+// and throughout it with instant numbers on
+#[test]
+fn test_pop_number_visibility() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert!(!app.instant_numbers());
+    assert!(!app.pop_number_visible(0.0));
+    assert!(!app.pop_number_visible(0.5));
+    assert!(app.pop_number_visible(1.0));
+
+    app.set_instant_numbers(true);
+    assert!(app.pop_number_visible(0.0));
+    assert!(app.pop_number_visible(0.5));
+    assert!(app.pop_number_visible(1.0));
+
+    // The setting carries over to new games
+    app.reset_game();
+    assert!(app.instant_numbers());
+}
+
+// Checks that restoring a snapshot after more moves brings back the exact board it was taken from
+#[test]
+fn test_board_snapshot_restore() {
+    let mut board = Board::from_mine_layout(&["*...", "....", "..*.", "...."]);
+    board.toggle_flag(0, 0);
+    board.apply_move(Move::Reveal(3, 0));
+    let before = board.clone();
+    let snapshot = board.snapshot();
+
+    board.toggle_flag(0, 0);
+    board.toggle_flag(2, 2);
+    board.apply_move(Move::Reveal(0, 3));
+    board.add_mine(3, 3);
+    assert_ne!(board, before);
+
+    board.restore(snapshot);
+    assert_eq!(board, before);
+
+    // The topology comes back too, so the restored numbers keep their neighbor rule
+    let snapshot = board.snapshot();
+    board.set_topology(Topology::VonNeumann);
+    board.restore(snapshot);
+    assert_eq!(board.topology(), Topology::Square);
+    assert_eq!(board, before);
+}
+
+// Times snapshot and restore on the Large board. Run it with
+// `cargo test --release bench_large_board_snapshot -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn bench_large_board_snapshot() {
+    let mut board = Board::new(24, 24, 99);
+    board.place_mines_avoiding(12, 12);
+    board.calculate_numbers();
+    board.flood_fill_wave(12, 12);
+    const RUNS: u32 = 10_000;
+
+    let start = std::time::Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(board.snapshot());
+    }
+    let snapshot_time = start.elapsed() / RUNS;
+
+    let snapshot = board.snapshot();
+    let start = std::time::Instant::now();
+    for _ in 0..RUNS {
+        board.restore(std::hint::black_box(snapshot.clone()));
+    }
+    let restore_time = start.elapsed() / RUNS;
+    println!("snapshot: {:?}, clone + restore: {:?}", snapshot_time, restore_time);
+}
+
+// Checks that the reveal pulse fades out over time, is cleared when the game ends,
+// and isn't started while turned off
+#[test]
+fn test_reveal_pulse() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert!(app.reveal_pulse());
+    app.set_state(GameState::Running);
+    app.start_reveal_pulse(2, 3);
+    assert_eq!(app.reveal_pulse_alpha(), Some(1.0));
+    app.advance_reveal_pulse(0.6);
+    let (row, col, _) = app.last_revealed().unwrap();
+    assert_eq!((row, col), (2, 3));
+    assert!(app.reveal_pulse_alpha().unwrap() < 1.0);
+    app.advance_reveal_pulse(0.6);
+    assert_eq!(app.last_revealed(), None);
+
+    // Ending the game clears it
+    app.start_reveal_pulse(1, 1);
+    app.set_state(GameState::Won);
+    app.advance_reveal_pulse(0.0);
+    assert_eq!(app.last_revealed(), None);
+
+    app.set_state(GameState::Running);
+    app.set_reveal_pulse(false);
+    app.start_reveal_pulse(1, 1);
+    assert_eq!(app.reveal_pulse_alpha(), None);
+}
+
+// Checks that subset_deductions solves the classic 1-2-1 pattern: the outer cells are mines
+// and the middle one is safe
+#[test]
+fn test_subset_deductions_one_two_one() {
+    let mut board = Board::from_mine_layout(&["*.*", "...", "..."]);
+    for row in 1..3 {
+        for col in 0..3 {
+            board.uncover_cell(row, col);
+        }
+    }
+    assert_eq!(board.cell(1, 1), Some(Cell::Number(2)));
+    assert_eq!(board.covered_neighbor_set(1, 0), HashSet::from([(0, 0), (0, 1)]));
+
+    let found = solver::subset_deductions(&board);
+    assert_eq!(found.mines, HashSet::from([(0, 0), (0, 2)]));
+    assert_eq!(found.safe, HashSet::from([(0, 1)]));
+
+    // Flags aren't trusted, as in deduce_cells, so a wrong flag can't mislead the deduction
+    board.toggle_flag(0, 1);
+    let found = solver::subset_deductions(&board);
+    assert_eq!(found.mines, HashSet::from([(0, 0), (0, 2)]));
+    assert_eq!(found.safe, HashSet::from([(0, 1)]));
+    let all = board.deduce_cells();
+    assert!(found.safe.is_subset(&all.safe) && found.mines.is_subset(&all.mines));
+}
+
+// Checks that Board::reveal reports each outcome: mine, number, empty with its flood fill,
+// already revealed, and flagged (or marked)
+#[test]
+fn test_board_reveal_outcomes() {
+    let mut board = Board::from_mine_layout(&["*..", "...", "..."]);
+    assert_eq!(board.reveal(0, 1), RevealOutcome::Number(1));
+    assert_eq!(board.cell_state(0, 1), Some(CellState::Uncovered));
+    assert_eq!(board.reveal(0, 1), RevealOutcome::AlreadyRevealed);
+    assert_eq!(board.reveal(5, 5), RevealOutcome::AlreadyRevealed);
+
+    board.toggle_flag(1, 1);
+    assert_eq!(board.reveal(1, 1), RevealOutcome::Flagged);
+    board.toggle_flag(1, 1); // Flag -> maybe
+    assert_eq!(board.reveal(1, 1), RevealOutcome::Flagged);
+    assert_eq!(board.cell_state(1, 1), Some(CellState::Maybe));
+    board.toggle_flag(1, 1); // Maybe -> covered
+
+    match board.reveal(2, 2) {
+        RevealOutcome::Empty { flooded } => {
+            assert_eq!(flooded[0], (2, 2, 0));
+            assert!(flooded.iter().any(|&(r, c, _)| (r, c) == (1, 1)));
+            assert!(flooded.iter().all(|&(r, c, _)| board.cell(r, c) != Some(Cell::Mine)));
+        }
+        other => panic!("expected an empty reveal, got {:?}", other),
+    }
+
+    assert_eq!(board.reveal(0, 0), RevealOutcome::Mine);
+    assert_eq!(board.cell_state(0, 0), Some(CellState::Uncovered));
+}
+
+// Checks that reveal_all_safe uncovers exactly the non-mine cells and leaves the mines covered
+#[test]
+fn test_reveal_all_safe() {
+    let mut board = Board::from_mine_layout(&["*..", "...", ".*."]);
+    board.toggle_flag(0, 2); // A wrong flag is uncovered too
+    board.reveal_all_safe();
+    for row in 0..3 {
+        for col in 0..3 {
+            let expected = if board.cell(row, col) == Some(Cell::Mine) {
+                CellState::Covered
+            } else {
+                CellState::Uncovered
+            };
+            assert_eq!(board.cell_state(row, col), Some(expected));
+        }
+    }
+}
+
+// Checks that the safe-cell peek only starts during a game, allows a screenshot,
+// and leaves the real board untouched
+#[test]
+fn test_safe_peek_restores_board() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    assert!(!app.toggle_safe_peek()); // No game running yet
+
+    app.prepare_reveal(4, 4);
+    app.play_move(Move::Reveal(4, 4));
+    app.board_mut().toggle_flag(0, 0);
+    let before = app.board().clone();
+    assert!(app.toggle_safe_peek());
+    assert!(app.safe_peek_active());
+    assert_eq!(app.safe_peek().unwrap().unrevealed_safe_cells(), Vec::new());
+    assert_eq!(app.board(), &before, "The peek is a copy");
+    assert!(app.request_screenshot());
+
+    assert!(!app.toggle_safe_peek());
+    assert!(!app.safe_peek_active());
+    assert_eq!(app.board(), &before);
+}
+
+// Checks that announcements are only printed once screen-reader output is turned on, and that
+// the setting survives a new game.
+#[test]
+fn test_announce_requires_screen_reader() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    assert!(!app.screen_reader(), "Off by default");
+    assert!(!app.announce("hello"));
+    app.set_screen_reader(true);
+    assert!(app.announce("hello"));
+    app.reset_game();
+    assert!(app.screen_reader(), "Kept across resets");
+}
+
+// Checks that the custom dialog, the new-game confirmation, and the safe peek each count as
+// a modal overlay (which blocks board-changing hotkeys).
+#[test]
+fn test_modal_active() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    assert!(!app.modal_active());
+    app.open_custom_dialog();
+    assert!(app.modal_active(), "Custom dialog");
+    app.set_custom_dialog(None);
+    app.set_pending_new_game(Some(NewGameRequest::Next));
+    assert!(app.modal_active(), "New game confirmation");
+    app.set_pending_new_game(None);
+    app.set_state(GameState::Running);
+    app.toggle_safe_peek();
+    assert!(app.modal_active(), "Safe peek");
+    app.toggle_safe_peek();
+    assert!(!app.modal_active());
+}
+
+// Checks that a solver step taken while the safe-cell peek shows plays on the real board, so
+// the peek's uncovered cells never count towards a win and the game goes on afterwards.
+#[test]
+fn test_safe_peek_does_not_win_the_game() {
+    let layout = ["*....", ".....", "..*..", ".....", "....*"];
+    let mut app = MinesweeperApp::new(5, 5, 3);
+    *app.board_mut() = Board::from_mine_layout(&layout);
+    app.set_state(GameState::Running);
+    app.play_move(Move::Reveal(0, 4));
+    assert!(app.toggle_safe_peek());
+
+    assert!(app.auto_solve_step());
+    assert!(!app.check_win_at(1.0, app.cell_size()));
+    assert_eq!(app.state(), GameState::Running);
+    assert!(!app.board().unrevealed_safe_cells().is_empty());
+
+    let after_step = app.board().clone();
+    assert!(!app.toggle_safe_peek());
+    assert_eq!(app.board(), &after_step, "Closing the peek keeps the solver's move");
+    assert_eq!(app.state(), GameState::Running);
+}
+
+// Checks that a size change or a new random layout asks before abandoning a game in
+// progress, and that the confirmed request starts the game it asked for.
+#[test]
+fn test_every_new_game_request_asks_first() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    app.prepare_reveal(4, 4);
+    app.board_mut().flood_fill_wave(4, 4);
+    assert!(!app.request_new_game(NewGameRequest::Random));
+    assert_eq!(app.pending_new_game(), Some(NewGameRequest::Random));
+
+    let medium = BoardSize::Small.next();
+    assert!(!app.request_new_game(NewGameRequest::Size(medium)));
+    assert_eq!(app.pending_new_game(), Some(NewGameRequest::Size(medium)));
+    assert_eq!(app.board_size(), BoardSize::Small, "Nothing changed yet");
+    assert_eq!(app.state(), GameState::Running);
+    assert!(!app.request_new_game(NewGameRequest::Size(BoardSize::Small)), "Same size");
+
+    app.set_pending_new_game(None);
+    app.start_requested_game(NewGameRequest::Size(medium));
+    assert_eq!(app.board_size(), medium);
+    assert_eq!(app.state(), GameState::NotStarted);
+}
+
+// Checks that flagging a forgiven, already uncovered mine is recorded in the replay, so the
+// replayed board matches the one on screen.
+#[test]
+fn test_absorbed_mine_is_replayed() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    app.set_zen_mode(true);
+    app.prepare_reveal(4, 4);
+    app.play_move(Move::Reveal(4, 4));
+    let &(row, col) = app.board().mine_positions().iter().next().unwrap();
+    app.play_move(Move::Reveal(row, col)); // As a chord through a wrong flag would
+    assert!(app.absorb_mine(row, col));
+    assert_eq!(app.board().cell_state(row, col), Some(CellState::Flagged));
+
+    let replay = app.replay().unwrap();
+    assert_eq!(replay.moves().last(), Some(&Move::Absorb(row, col)));
+    assert_eq!(replay.board_at(replay.len()), *app.board());
+}
+
+// Checks that the time attack countdown pauses while a modal overlay is open and resumes with
+// the time that was left, and that running out of time spreads the mine reveal from the cell
+// of the last reveal.
+#[test]
+fn test_time_attack_pauses_for_modals() {
+    let mut app = MinesweeperApp::new(8, 8, 10);
+    *app.board_mut() = Board::from_mine_layout(&["*.......", "........", ".......*"]);
+    app.set_reveal_order(RevealOrder::Nearest);
+    app.set_state(GameState::Running);
+    app.set_move_time_limit(Some(5.0));
+    app.record_move(100.0);
+
+    app.open_custom_dialog();
+    assert!(!app.check_move_time_limit(102.0));
+    assert!(!app.check_move_time_limit(200.0), "Paused while the dialog is open");
+    assert_eq!(app.move_time_remaining(200.0), Some(3.0));
+    app.set_custom_dialog(None);
+    assert!(!app.check_move_time_limit(201.0));
+    assert_eq!(app.move_time_remaining(202.0), Some(2.0), "Resumes where it paused");
+
+    app.set_last_reveal_cell(Some((2, 6)));
+    assert!(app.check_move_time_limit(204.0));
+    assert_eq!(app.mine_reveal_queue()[0].0, 2, "Spreads from the last revealed cell");
+    assert_eq!(app.mine_reveal_queue()[0].1, 7);
+}
+
+// Checks that the replay view steps back and forth through a finished game's moves, goes back
+// to the real board past the last move, and is off while the game runs.
+#[test]
+fn test_replay_view_steps_through_finished_game() {
+    let mut app = MinesweeperApp::new(9, 9, 10);
+    app.prepare_reveal(4, 4);
+    app.play_move(Move::Reveal(4, 4));
+    let mine = *app.board().mine_positions().iter().next().unwrap();
+    app.play_move(Move::Flag(mine.0, mine.1));
+    assert_eq!(app.step_replay_view(-1), None, "Only once the game has ended");
+
+    app.set_state(GameState::Won);
+    assert_eq!(app.step_replay_view(-1), Some(1));
+    let (_, board) = app.replay_view().unwrap();
+    assert_eq!(board.cell_state(mine.0, mine.1), Some(CellState::Covered));
+    assert_eq!(app.step_replay_view(-1), Some(0));
+    assert_eq!(app.step_replay_view(-1), Some(0), "Stops at the first step");
+    assert_eq!(app.step_replay_view(1), Some(1));
+    assert_eq!(app.step_replay_view(1), None);
+    assert!(app.replay_view().is_none(), "Back to the real board");
+}